@@ -8,6 +8,14 @@ enum ArithmeticError {
     IntegerOverflow { a: u64, b: u64 },
 }
 
+#[derive(Debug, thiserror::Error)]
+enum ComputationError {
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Overflow multiplying {a} and {b}")]
+    Overflow { a: u64, b: u64 },
+}
+
 fn add(a: u64, b: u64) -> Result<u64> {
     a.checked_add(b)
         .ok_or(ArithmeticError::IntegerOverflow { a, b })
@@ -25,6 +33,16 @@ fn div(dividend: u64, divisor: u64) -> u64 {
     dividend / divisor
 }
 
+fn checked_div(dividend: u64, divisor: u64) -> Result<u64, ComputationError> {
+    dividend
+        .checked_div(divisor)
+        .ok_or(ComputationError::DivisionByZero)
+}
+
+fn checked_mul(a: u64, b: u64) -> Result<u64, ComputationError> {
+    a.checked_mul(b).ok_or(ComputationError::Overflow { a, b })
+}
+
 fn equal(a: u64, b: u64) -> bool {
     a == b
 }
@@ -22,6 +22,23 @@ try {
     // It's okay!
 }
 
+assert(checkedDiv(8u, 4u) == 2uL)
+
+try {
+    checkedDiv(8u, 0u)
+    throw RuntimeException("Should have thrown a DivisionByZero exception!")
+} catch (e: ComputationErrorException.DivisionByZero) {
+    // It's okay!
+}
+
+try {
+    checkedMul(ULong.MAX_VALUE, 2uL)
+    throw RuntimeException("Should have thrown an Overflow exception!")
+} catch (e: ComputationErrorException.Overflow) {
+    assert(e.a == ULong.MAX_VALUE)
+    assert(e.b == 2uL)
+}
+
 assert(equal(2u, 2uL))
 assert(equal(4u, 4uL))
 
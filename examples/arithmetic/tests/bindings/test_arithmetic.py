@@ -1,3 +1,4 @@
+import warnings
 from arithmetic import *
 
 try:
@@ -22,6 +23,14 @@ assert sub(8, 4) == 4
 
 assert div(8, 4) == 2
 
+# `div` is deprecated, so calling it warns.
+with warnings.catch_warnings(record=True) as caught:
+    warnings.simplefilter("always")
+    div(8, 4)
+assert len(caught) == 1
+assert caught[0].category is DeprecationWarning
+assert str(caught[0].message) == "Use checked_div, which doesn't panic when dividing by zero"
+
 try:
     div(8, 0)
 except InternalError:
@@ -30,6 +39,24 @@ except InternalError:
 else:
     assert(not("Should have panicked when dividing by zero"))
 
+assert checked_div(8, 4) == 2
+
+try:
+    checked_div(8, 0)
+    assert(not("Should have thrown a DivisionByZero exception!"))
+except ComputationError.DivisionByZero:
+    # It's okay!
+    pass
+
+assert checked_mul(2, 3) == 6
+
+try:
+    checked_mul(18446744073709551615, 2)
+    assert(not("Should have thrown an Overflow exception!"))
+except ComputationError.Overflow as e:
+    assert e.a == 18446744073709551615
+    assert e.b == 2
+
 assert equal(2, 2)
 assert equal(4, 4)
 
@@ -25,6 +25,23 @@ assert(div(dividend: 8, divisor: 4) == 2, "div works")
 // We can't test panicking in Swift because we force unwrap the error in
 // `div`, which we can't catch.
 
+assert(try! checkedDiv(dividend: 8, divisor: 4) == 2, "checkedDiv works")
+
+do {
+    let _ = try checkedDiv(dividend: 8, divisor: 0)
+    fatalError("Should have thrown a DivisionByZero error!")
+} catch ComputationError.DivisionByZero {
+    // It's okay!
+}
+
+do {
+    let _ = try checkedMul(a: 18446744073709551615, b: 2)
+    fatalError("Should have thrown an Overflow error!")
+} catch let ComputationError.Overflow(a, b) {
+    assert(a == 18446744073709551615, "Overflow carries its fields")
+    assert(b == 2, "Overflow carries its fields")
+}
+
 assert(equal(a: 2, b: 2), "equal works")
 assert(equal(a: 4, b: 4), "equal works")
 
@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone)]
+pub struct Blob {
+    name: String,
+    data: Vec<u8>,
+}
+
+fn reverse(mut data: Vec<u8>) -> Vec<u8> {
+    data.reverse();
+    data
+}
+
+fn checksum(data: Vec<u8>) -> u32 {
+    data.iter()
+        .fold(0u32, |sum, byte| sum.wrapping_add(u32::from(*byte)))
+}
+
+fn make_blob(data: Vec<u8>) -> Blob {
+    Blob {
+        name: format!("{} bytes", data.len()),
+        data,
+    }
+}
+
+fn blob_data(blob: Blob) -> Vec<u8> {
+    blob.data
+}
+
+// In rust, `bytes` and `sequence<u8>` are both a `Vec<u8>`, but the foreign-language
+// bindings see them as different types.
+fn as_sequence(data: Vec<u8>) -> Vec<u8> {
+    data
+}
+
+include!(concat!(env!("OUT_DIR"), "/blobs.uniffi.rs"));
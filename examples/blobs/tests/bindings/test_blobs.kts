@@ -0,0 +1,19 @@
+import uniffi.blobs.*
+
+assert(reverse(byteArrayOf(1, 2, 3)).contentEquals(byteArrayOf(3, 2, 1)))
+assert(reverse(byteArrayOf()).isEmpty())
+
+// Large values are passed in one go.
+val data = ByteArray(1024 * 1024) { it.toByte() }
+assert(reverse(data).contentEquals(data.reversedArray()))
+assert(checksum(data) == data.sumBy { it.toInt() and 0xFF }.toUInt())
+
+// Bytes can be nested in other types.
+val blob = makeBlob(byteArrayOf(0x89.toByte(), 0x50, 0x4E, 0x47))
+assert(blob.name == "4 bytes")
+assert(blob.data.contentEquals(byteArrayOf(0x89.toByte(), 0x50, 0x4E, 0x47)))
+assert(blobData(Blob("empty", byteArrayOf())).isEmpty())
+assert(blobData(Blob("image.png", data)).contentEquals(data))
+
+// A `sequence<u8>` is a list of unsigned integers, rather than a `ByteArray`.
+assert(asSequence(byteArrayOf(1, 2, 3)) == listOf(1u.toUByte(), 2u.toUByte(), 3u.toUByte()))
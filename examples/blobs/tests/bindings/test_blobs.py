@@ -0,0 +1,19 @@
+from blobs import *
+
+assert reverse(b"\x01\x02\x03") == b"\x03\x02\x01"
+assert reverse(b"") == b""
+
+# Large values are passed in one go.
+data = bytes(range(256)) * 4096
+assert reverse(data) == data[::-1]
+assert checksum(data) == sum(data)
+
+# Bytes can be nested in other types.
+blob = make_blob(b"\x89PNG")
+assert blob.name == "4 bytes"
+assert blob.data == b"\x89PNG"
+assert blob_data(Blob("empty", b"")) == b""
+assert blob_data(Blob("image.png", data)) == data
+
+# A `sequence<u8>` is a list of integers, rather than bytes.
+assert as_sequence(b"\x01\x02\x03") == [1, 2, 3]
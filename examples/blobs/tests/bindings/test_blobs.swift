@@ -0,0 +1,20 @@
+import blobs
+import Foundation
+
+assert(reverse(data: Data([1, 2, 3])) == Data([3, 2, 1]), "reverse works")
+assert(reverse(data: Data()) == Data(), "reverse works on empty data")
+
+// Large values are passed in one go.
+let data = Data((0..<1024 * 1024).map { UInt8(truncatingIfNeeded: $0) })
+assert(reverse(data: data) == Data(data.reversed()), "reverse works on large data")
+assert(checksum(data: data) == data.reduce(0) { $0 &+ UInt32($1) }, "checksum works")
+
+// Bytes can be nested in other types.
+let blob = makeBlob(data: Data([0x89, 0x50, 0x4E, 0x47]))
+assert(blob.name == "4 bytes", "blob has a name")
+assert(blob.data == Data([0x89, 0x50, 0x4E, 0x47]), "blob has data")
+assert(blobData(blob: Blob(name: "empty", data: Data())) == Data(), "empty blobs work")
+assert(blobData(blob: Blob(name: "image.png", data: data)) == data, "large blobs work")
+
+// A `sequence<u8>` is an array of integers, rather than `Data`.
+assert(asSequence(data: Data([1, 2, 3])) == [1, 2, 3], "asSequence works")
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/blobs.idl",
+    [
+        "tests/bindings/test_blobs.py",
+        "tests/bindings/test_blobs.kts",
+        "tests/bindings/test_blobs.swift",
+    ]
+);
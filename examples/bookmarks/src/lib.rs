@@ -0,0 +1,117 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A component for keeping bookmarks in folders.
+
+// This component doesn't have an IDL file. Instead, the items that make up its interface are
+// marked with `#[uniffi_macros::export]`, and `uniffi_bindgen` reads them from this file.
+
+use std::sync::{Arc, RwLock};
+
+/// A link that's been saved to come back to later.
+#[derive(Debug, Clone)]
+#[uniffi_macros::export]
+pub struct Bookmark {
+    url: String,
+    title: String,
+}
+
+/// The order to list the bookmarks in a folder.
+#[derive(Debug, Clone, Copy)]
+#[uniffi_macros::export]
+pub enum SortOrder {
+    Added,
+    Title,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[uniffi_macros::export(error)]
+pub enum BookmarkError {
+    #[error("Not a web address: {0}")]
+    InvalidUrl(String),
+    #[error("That url is already bookmarked")]
+    Duplicate,
+}
+
+type Result<T, E = BookmarkError> = std::result::Result<T, E>;
+
+/// Check that a url is a web address, and tidy it up.
+#[uniffi_macros::export]
+fn normalize_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(BookmarkError::InvalidUrl(url.to_string()));
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Make a new folder holding the bookmarks from both of the given folders.
+#[uniffi_macros::export]
+fn merge<S: Into<String>>(name: S, first: Arc<Folder>, second: Arc<Folder>) -> Arc<Folder> {
+    let merged = Folder::new(name);
+    for folder in &[first, second] {
+        for bookmark in folder.list(SortOrder::Added) {
+            // The same url may well be in both of the folders.
+            let _ = merged.add(bookmark);
+        }
+    }
+    Arc::new(merged)
+}
+
+// Folders are shared between rust and the foreign-language code, so they need to be threadsafe.
+#[derive(Debug)]
+pub struct Folder {
+    name: String,
+    bookmarks: RwLock<Vec<Bookmark>>,
+}
+
+/// A named folder of bookmarks.
+#[uniffi_macros::export(threadsafe)]
+impl Folder {
+    fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            bookmarks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// The longest title that a bookmark can have.
+    fn max_title_length() -> u32 {
+        100
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Add a bookmark to the folder, unless it already has one for the same url.
+    fn add(&self, bookmark: Bookmark) -> Result<()> {
+        let url = normalize_url(&bookmark.url)?;
+        let mut bookmarks = self.bookmarks.write().unwrap();
+        if bookmarks.iter().any(|b| b.url == url) {
+            return Err(BookmarkError::Duplicate);
+        }
+        let title = bookmark
+            .title
+            .chars()
+            .take(Self::max_title_length() as usize)
+            .collect();
+        bookmarks.push(Bookmark { url, title });
+        Ok(())
+    }
+
+    fn list(&self, order: SortOrder) -> Vec<Bookmark> {
+        let mut bookmarks = self.bookmarks.read().unwrap().clone();
+        if let SortOrder::Title = order {
+            bookmarks.sort_by(|a, b| a.title.cmp(&b.title));
+        }
+        bookmarks
+    }
+
+    fn count(&self) -> u32 {
+        self.bookmarks.read().unwrap().len() as u32
+    }
+}
+
+uniffi_macros::include_scaffolding!("bookmarks");
@@ -0,0 +1,42 @@
+import uniffi.bookmarks.*
+
+// Exported functions throw the error type of the `Result` they return.
+assert(normalizeUrl(" https://example.com/ ") == "https://example.com")
+try {
+    normalizeUrl("example.com")
+    throw RuntimeException("Should have thrown an InvalidUrl error")
+} catch (e: BookmarkErrorException.InvalidUrl) {
+    // It's okay, that's not a web address!
+}
+
+// An exported `impl` block becomes a class, with `new` as its constructor.
+val reading = Folder("Reading")
+assert(reading.name() == "Reading")
+reading.add(Bookmark("https://example.com/bees/", "Bees"))
+reading.add(Bookmark("https://example.com/ants", "Ants"))
+try {
+    reading.add(Bookmark("https://example.com/ants/", "Ants again"))
+    throw RuntimeException("Should have thrown a Duplicate error")
+} catch (e: BookmarkErrorException.Duplicate) {
+    // It's okay, it's the same url!
+}
+assert(reading.count() == 2u)
+
+// Exported structs are records, and enums without fields are enums.
+assert(reading.list(SortOrder.ADDED).map { it.title } == listOf("Bees", "Ants"))
+assert(reading.list(SortOrder.TITLE).map { it.title } == listOf("Ants", "Bees"))
+assert(reading.list(SortOrder.TITLE)[0].url == "https://example.com/ants")
+
+// Functions that don't take `self` are functions on the companion object.
+assert(Folder.maxTitleLength() == 100u)
+reading.add(Bookmark("https://example.com/long", "x".repeat(200)))
+assert(reading.list(SortOrder.ADDED).last().title.length == 100)
+
+// Functions can take and return objects, which are passed as an `Arc` in rust.
+val work = Folder("Work")
+work.add(Bookmark("https://example.com/ants", "Ants at work"))
+work.add(Bookmark("https://example.com/wasps", "Wasps"))
+val everything = merge("Everything", reading, work)
+assert(everything.name() == "Everything")
+assert(everything.count() == 4u)
+assert(reading.count() == 3u)
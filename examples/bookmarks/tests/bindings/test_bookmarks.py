@@ -0,0 +1,40 @@
+from bookmarks import *
+
+# Exported functions throw the error type of the `Result` they return.
+assert normalize_url(" https://example.com/ ") == "https://example.com"
+try:
+    normalize_url("example.com")
+    raise RuntimeError("Should have thrown an InvalidUrl error")
+except BookmarkError.InvalidUrl:
+    pass
+
+# An exported `impl` block becomes a class, with `new` as its constructor.
+reading = Folder("Reading")
+assert reading.name() == "Reading"
+reading.add(Bookmark("https://example.com/bees/", "Bees"))
+reading.add(Bookmark("https://example.com/ants", "Ants"))
+try:
+    reading.add(Bookmark("https://example.com/ants/", "Ants again"))
+    raise RuntimeError("Should have thrown a Duplicate error")
+except BookmarkError.Duplicate:
+    pass
+assert reading.count() == 2
+
+# Exported structs are records, and enums without fields are enums.
+assert [b.title for b in reading.list(SortOrder.ADDED)] == ["Bees", "Ants"]
+assert [b.title for b in reading.list(SortOrder.TITLE)] == ["Ants", "Bees"]
+assert reading.list(SortOrder.TITLE)[0].url == "https://example.com/ants"
+
+# Functions that don't take `self` are static methods.
+assert Folder.max_title_length() == 100
+reading.add(Bookmark("https://example.com/long", "x" * 200))
+assert len(reading.list(SortOrder.ADDED)[-1].title) == 100
+
+# Functions can take and return objects, which are passed as an `Arc` in rust.
+work = Folder("Work")
+work.add(Bookmark("https://example.com/ants", "Ants at work"))
+work.add(Bookmark("https://example.com/wasps", "Wasps"))
+everything = merge("Everything", reading, work)
+assert everything.name() == "Everything"
+assert everything.count() == 4
+assert reading.count() == 3
@@ -0,0 +1,42 @@
+import bookmarks
+
+// Exported functions throw the error type of the `Result` they return.
+assert(try! normalizeUrl(url: " https://example.com/ ") == "https://example.com", "urls are tidied up")
+do {
+    let _ = try normalizeUrl(url: "example.com")
+    fatalError("Should have thrown an InvalidUrl error")
+} catch BookmarkError.InvalidUrl {
+    // It's okay, that's not a web address!
+}
+
+// An exported `impl` block becomes a class, with `new` as its constructor.
+let reading = Folder(name: "Reading")
+assert(reading.name() == "Reading", "the folder has a name")
+try! reading.add(bookmark: Bookmark(url: "https://example.com/bees/", title: "Bees"))
+try! reading.add(bookmark: Bookmark(url: "https://example.com/ants", title: "Ants"))
+do {
+    try reading.add(bookmark: Bookmark(url: "https://example.com/ants/", title: "Ants again"))
+    fatalError("Should have thrown a Duplicate error")
+} catch BookmarkError.Duplicate {
+    // It's okay, it's the same url!
+}
+assert(reading.count() == 2, "duplicates aren't added")
+
+// Exported structs are records, and enums without fields are enums.
+assert(reading.list(order: .added).map { $0.title } == ["Bees", "Ants"], "bookmarks are in the order they were added")
+assert(reading.list(order: .title).map { $0.title } == ["Ants", "Bees"], "bookmarks can be sorted by title")
+assert(reading.list(order: .title)[0].url == "https://example.com/ants", "urls are tidied up when added")
+
+// Functions that don't take `self` are static methods.
+assert(Folder.maxTitleLength() == 100, "static methods work")
+try! reading.add(bookmark: Bookmark(url: "https://example.com/long", title: String(repeating: "x", count: 200)))
+assert(reading.list(order: .added).last!.title.count == 100, "long titles are cut short")
+
+// Functions can take and return objects, which are passed as an `Arc` in rust.
+let work = Folder(name: "Work")
+try! work.add(bookmark: Bookmark(url: "https://example.com/ants", title: "Ants at work"))
+try! work.add(bookmark: Bookmark(url: "https://example.com/wasps", title: "Wasps"))
+let everything = merge(name: "Everything", first: reading, second: work)
+assert(everything.name() == "Everything", "the merged folder has a name")
+assert(everything.count() == 4, "duplicates are merged")
+assert(reading.count() == 3, "the folders that were merged are unchanged")
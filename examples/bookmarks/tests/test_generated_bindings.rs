@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/lib.rs",
+    [
+        "tests/bindings/test_bookmarks.kts",
+        "tests/bindings/test_bookmarks.swift",
+        "tests/bindings/test_bookmarks.py",
+    ]
+);
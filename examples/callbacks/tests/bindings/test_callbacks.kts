@@ -0,0 +1,44 @@
+import uniffi.callbacks.*
+
+class OnCallAnsweredImpl : OnCallAnswered {
+    var yesCount: Int = 0
+    var busyCount: Int = 0
+    var stringReceived = ""
+
+    override fun hello(): String {
+        yesCount ++
+        return "Hi hi $yesCount"
+    }
+
+    override fun busy() {
+        busyCount ++
+    }
+
+    override fun textReceived(text: String) {
+        stringReceived = text
+    }
+}
+
+val cbObject = OnCallAnsweredImpl()
+val telephone = Telephone()
+
+telephone.call(true, cbObject)
+assert(cbObject.busyCount == 0) { "yesCount=${cbObject.busyCount} (should be 0)" }
+assert(cbObject.yesCount == 1) { "yesCount=${cbObject.yesCount} (should be 1)" }
+
+telephone.call(true, cbObject)
+assert(cbObject.busyCount == 0) { "yesCount=${cbObject.busyCount} (should be 0)" }
+assert(cbObject.yesCount == 2) { "yesCount=${cbObject.yesCount} (should be 2)" }
+
+telephone.call(false, cbObject)
+assert(cbObject.busyCount == 1) { "yesCount=${cbObject.busyCount} (should be 1)" }
+assert(cbObject.yesCount == 2) { "yesCount=${cbObject.yesCount} (should be 2)" }
+
+val cbObjet2 = OnCallAnsweredImpl()
+telephone.call(true, cbObjet2)
+assert(cbObjet2.busyCount == 0) { "yesCount=${cbObjet2.busyCount} (should be 0)" }
+assert(cbObjet2.yesCount == 1) { "yesCount=${cbObjet2.yesCount} (should be 1)" }
+
+assert(cbObject.stringReceived == "Not now, I'm on another call!")
+
+telephone.destroy()
@@ -0,0 +1,39 @@
+from callbacks import *
+
+class OnCallAnsweredImpl(OnCallAnswered):
+    def __init__(self):
+        self.yes_count = 0
+        self.busy_count = 0
+        self.string_received = ""
+
+    def hello(self):
+        self.yes_count += 1
+        return "Hi hi {}".format(self.yes_count)
+
+    def busy(self):
+        self.busy_count += 1
+
+    def text_received(self, text):
+        self.string_received = text
+
+cb_object = OnCallAnsweredImpl()
+telephone = Telephone()
+
+telephone.call(True, cb_object)
+assert cb_object.busy_count == 0, "yes_count={} (should be 0)".format(cb_object.busy_count)
+assert cb_object.yes_count == 1, "yes_count={} (should be 1)".format(cb_object.yes_count)
+
+telephone.call(True, cb_object)
+assert cb_object.busy_count == 0, "yes_count={} (should be 0)".format(cb_object.busy_count)
+assert cb_object.yes_count == 2, "yes_count={} (should be 2)".format(cb_object.yes_count)
+
+telephone.call(False, cb_object)
+assert cb_object.busy_count == 1, "yes_count={} (should be 1)".format(cb_object.busy_count)
+assert cb_object.yes_count == 2, "yes_count={} (should be 2)".format(cb_object.yes_count)
+
+cb_object2 = OnCallAnsweredImpl()
+telephone.call(True, cb_object2)
+assert cb_object2.busy_count == 0, "yes_count={} (should be 0)".format(cb_object2.busy_count)
+assert cb_object2.yes_count == 1, "yes_count={} (should be 1)".format(cb_object2.yes_count)
+
+assert cb_object.string_received == "Not now, I'm on another call!"
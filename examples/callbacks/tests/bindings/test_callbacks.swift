@@ -0,0 +1,42 @@
+import callbacks
+
+class OnCallAnsweredImpl: OnCallAnswered {
+    var yesCount = 0
+    var busyCount = 0
+    var stringReceived = ""
+
+    func hello() -> String {
+        yesCount += 1
+        return "Hi hi \(yesCount)"
+    }
+
+    func busy() {
+        busyCount += 1
+    }
+
+    func textReceived(text: String) {
+        stringReceived = text
+    }
+}
+
+let cbObject = OnCallAnsweredImpl()
+let telephone = Telephone()
+
+telephone.call(domestic: true, callResponder: cbObject)
+assert(cbObject.busyCount == 0, "yesCount=\(cbObject.busyCount) (should be 0)")
+assert(cbObject.yesCount == 1, "yesCount=\(cbObject.yesCount) (should be 1)")
+
+telephone.call(domestic: true, callResponder: cbObject)
+assert(cbObject.busyCount == 0, "yesCount=\(cbObject.busyCount) (should be 0)")
+assert(cbObject.yesCount == 2, "yesCount=\(cbObject.yesCount) (should be 2)")
+
+telephone.call(domestic: false, callResponder: cbObject)
+assert(cbObject.busyCount == 1, "yesCount=\(cbObject.busyCount) (should be 1)")
+assert(cbObject.yesCount == 2, "yesCount=\(cbObject.yesCount) (should be 2)")
+
+let cbObject2 = OnCallAnsweredImpl()
+telephone.call(domestic: true, callResponder: cbObject2)
+assert(cbObject2.busyCount == 0, "yesCount=\(cbObject2.busyCount) (should be 0)")
+assert(cbObject2.yesCount == 1, "yesCount=\(cbObject2.yesCount) (should be 1)")
+
+assert(cbObject.stringReceived == "Not now, I'm on another call!")
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/callbacks.idl",
+    [
+        "tests/bindings/test_callbacks.py",
+        "tests/bindings/test_callbacks.kts",
+        "tests/bindings/test_callbacks.swift",
+    ]
+);
@@ -0,0 +1,47 @@
+import java.time.Duration
+import java.time.Instant
+import uniffi.chronological.*
+
+// Test passing timestamp and duration while returning timestamp
+assert(add(Instant.ofEpochSecond(100, 100), Duration.ofSeconds(1, 1))
+    .equals(Instant.ofEpochSecond(101, 101)))
+
+// Timestamps before the epoch round-trip too
+assert(add(Instant.ofEpochSecond(-100, 500_000_000), Duration.ofSeconds(1))
+    .equals(Instant.ofEpochSecond(-99, 500_000_000)))
+
+// Test passing timestamp while returning duration
+assert(diff(Instant.ofEpochSecond(101, 101), Instant.ofEpochSecond(100, 100))
+    .equals(Duration.ofSeconds(1, 1)))
+
+// Test exceptions are propagated
+try {
+    diff(Instant.ofEpochSecond(100), Instant.ofEpochSecond(101))
+    throw RuntimeException("Should have thrown a TimeDiffError exception!")
+} catch (e: ChronologicalErrorException) {
+    // It's okay!
+}
+
+// Negative durations can't be passed to rust
+try {
+    add(Instant.ofEpochSecond(100), Duration.ofSeconds(-1))
+    throw RuntimeException("Should have thrown an IllegalArgumentException!")
+} catch (e: IllegalArgumentException) {
+    // It's okay!
+}
+
+// Test that rust timestamps behave like kotlin timestamps
+val kotlinBefore = Instant.now()
+val rustNow = now()
+val kotlinAfter = Instant.now()
+
+assert(kotlinBefore.isBefore(rustNow) || kotlinBefore.equals(rustNow))
+assert(kotlinAfter.isAfter(rustNow) || kotlinAfter.equals(rustNow))
+
+// Test that timestamps compare equal across the FFI
+assert(equal(kotlinBefore, kotlinBefore))
+assert(!equal(kotlinBefore, kotlinAfter.plusSeconds(1)))
+
+// Test durations nested in compound types
+assert(checkedDiff(Instant.ofEpochSecond(101), Instant.ofEpochSecond(100)) == Duration.ofSeconds(1))
+assert(checkedDiff(Instant.ofEpochSecond(100), Instant.ofEpochSecond(101)) == null)
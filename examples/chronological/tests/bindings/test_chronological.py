@@ -0,0 +1,46 @@
+from chronological import *
+from datetime import datetime, timedelta, timezone
+
+# Test passing timestamp and duration while returning timestamp
+assert add(datetime.fromtimestamp(100.000001, timezone.utc), timedelta(seconds=1, microseconds=1)) \
+    == datetime.fromtimestamp(101.000002, timezone.utc)
+
+# Timestamps before the epoch round-trip too
+assert add(datetime.fromtimestamp(-100.5, timezone.utc), timedelta(seconds=1)) \
+    == datetime.fromtimestamp(-99.5, timezone.utc)
+
+# Test passing timestamp while returning duration
+assert diff(datetime.fromtimestamp(101.000002, timezone.utc), datetime.fromtimestamp(100.000001, timezone.utc)) \
+    == timedelta(seconds=1, microseconds=1)
+
+# Test exceptions are propagated
+try:
+    diff(datetime.fromtimestamp(100, timezone.utc), datetime.fromtimestamp(101, timezone.utc))
+    assert(not("Should have thrown a TimeDiffError exception!"))
+except ChronologicalError.TimeDiffError:
+    # It's okay!
+    pass
+
+# Negative durations can't be passed to rust
+try:
+    add(datetime.fromtimestamp(100, timezone.utc), timedelta(seconds=-1))
+    assert(not("Should have thrown a ValueError!"))
+except ValueError:
+    # It's okay!
+    pass
+
+# Test that rust timestamps behave like python timestamps
+pythonBefore = datetime.now(timezone.utc)
+rustNow = now()
+pythonAfter = datetime.now(timezone.utc)
+
+assert pythonBefore <= rustNow <= pythonAfter
+
+# Test that timestamps compare equal across the FFI
+assert equal(pythonBefore, pythonBefore)
+assert not equal(pythonBefore, pythonAfter + timedelta(seconds=1))
+
+# Test durations nested in compound types
+assert checked_diff(datetime.fromtimestamp(101, timezone.utc), datetime.fromtimestamp(100, timezone.utc)) \
+    == timedelta(seconds=1)
+assert checked_diff(datetime.fromtimestamp(100, timezone.utc), datetime.fromtimestamp(101, timezone.utc)) is None
@@ -0,0 +1,35 @@
+import chronological
+import Foundation
+
+// Test passing timestamp and duration while returning timestamp
+assert(try! add(a: Date(timeIntervalSince1970: 100.5), b: 1.25) == Date(timeIntervalSince1970: 101.75), "add works")
+
+// Timestamps before the epoch round-trip too
+assert(try! add(a: Date(timeIntervalSince1970: -100.5), b: 1) == Date(timeIntervalSince1970: -99.5), "add works before the epoch")
+
+// Test passing timestamp while returning duration
+assert(try! diff(a: Date(timeIntervalSince1970: 101.75), b: Date(timeIntervalSince1970: 100.5)) == 1.25, "diff works")
+
+// Test exceptions are propagated
+do {
+    let _ = try diff(a: Date(timeIntervalSince1970: 100), b: Date(timeIntervalSince1970: 101))
+    fatalError("Should have thrown a TimeDiffError exception!")
+} catch ChronologicalError.TimeDiffError {
+    // It's okay!
+}
+
+// Test that rust timestamps behave like swift timestamps
+let swiftBefore = Date()
+let rustNow = now()
+let swiftAfter = Date()
+
+assert(swiftBefore <= rustNow, "rust now is after swift before")
+assert(swiftAfter >= rustNow, "rust now is before swift after")
+
+// Test that timestamps compare equal across the FFI
+assert(equal(a: swiftBefore, b: swiftBefore), "equal works")
+assert(!equal(a: swiftBefore, b: swiftAfter.addingTimeInterval(1)), "non-equal works")
+
+// Test durations nested in compound types
+assert(checkedDiff(a: Date(timeIntervalSince1970: 101), b: Date(timeIntervalSince1970: 100)) == 1, "checkedDiff works")
+assert(checkedDiff(a: Date(timeIntervalSince1970: 100), b: Date(timeIntervalSince1970: 101)) == nil, "checkedDiff works when b is later")
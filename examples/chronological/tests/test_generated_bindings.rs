@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/chronological.idl",
+    [
+        "tests/bindings/test_chronological.py",
+        "tests/bindings/test_chronological.kts",
+        "tests/bindings/test_chronological.swift",
+    ]
+);
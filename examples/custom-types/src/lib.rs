@@ -0,0 +1,67 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi::deps::anyhow::{bail, Result};
+
+// A unique identifier, which is passed over the FFI as a string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guid(String);
+
+impl uniffi::CustomTypeWrapper for Guid {
+    type Wrapped = String;
+
+    fn wrap(val: String) -> Result<Self> {
+        if val.is_empty() {
+            bail!("Guids must not be empty");
+        }
+        Ok(Guid(val))
+    }
+
+    fn unwrap(&self) -> String {
+        self.0.clone()
+    }
+}
+
+// A number of milliseconds since the unix epoch, which is passed over the FFI as an i64.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Millis(i64);
+
+impl uniffi::CustomTypeWrapper for Millis {
+    type Wrapped = i64;
+
+    fn wrap(val: i64) -> Result<Self> {
+        if val < 0 {
+            bail!("Timestamps before the unix epoch are not supported");
+        }
+        Ok(Millis(val))
+    }
+
+    fn unwrap(&self) -> i64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    id: Guid,
+    created: Millis,
+    modified: Millis,
+}
+
+fn make_guid(seed: u64) -> Guid {
+    Guid(format!("guid-{:016x}", seed))
+}
+
+fn touch(entry: Entry, now: Millis) -> Entry {
+    Entry {
+        modified: if now > entry.modified {
+            now
+        } else {
+            entry.modified
+        },
+        ..entry
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/custom_types.uniffi.rs"));
@@ -0,0 +1,22 @@
+import java.time.Instant
+import uniffi.custom_types.*
+
+// Guids are plain strings, but Millis are represented as java.time.Instant.
+val guid: Guid = makeGuid(255UL)
+assert(guid == "guid-00000000000000ff")
+
+val created = Instant.ofEpochMilli(1604188800000)
+val later = created.plusMillis(1500)
+
+val entry = Entry(guid, created, created)
+val touched = touch(entry, later)
+assert(touched == Entry(guid, created, later))
+assert(touch(touched, created) == touched)
+
+// The rust code refuses to wrap timestamps from before the epoch.
+try {
+    touch(entry, Instant.ofEpochMilli(-1))
+    throw RuntimeException("Should have thrown when wrapping a negative timestamp")
+} catch (e: InternalException) {
+    // It's okay!
+}
@@ -0,0 +1,18 @@
+import datetime
+from custom_types import *
+
+created = datetime.datetime(2020, 11, 1, tzinfo=datetime.timezone.utc)
+later = created + datetime.timedelta(milliseconds=1500)
+
+# Guids are plain strings, but Millis are represented as datetimes.
+entry = Entry("guid-1", created, created)
+touched = touch(entry, later)
+assert touched == Entry("guid-1", created, later)
+assert touch(touched, created) == touched
+
+# The rust code refuses to wrap timestamps from before the epoch.
+try:
+    touch(entry, datetime.datetime(1969, 12, 31, tzinfo=datetime.timezone.utc))
+    raise RuntimeError("Should have thrown when wrapping a negative timestamp")
+except InternalError:
+    pass
@@ -0,0 +1,14 @@
+import Foundation
+import custom_types
+
+// Guids are plain strings, but Millis are represented as Date.
+let guid: Guid = makeGuid(seed: 255)
+assert(guid == "guid-00000000000000ff")
+
+let created = Date(timeIntervalSince1970: 1604188800)
+let later = created.addingTimeInterval(1.5)
+
+let entry = Entry(id: guid, created: created, modified: created)
+let touched = touch(entry: entry, now: later)
+assert(touched == Entry(id: guid, created: created, modified: later))
+assert(touch(entry: touched, now: created) == touched)
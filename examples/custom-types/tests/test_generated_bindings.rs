@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/custom_types.idl",
+    [
+        "tests/bindings/test_custom_types.py",
+        "tests/bindings/test_custom_types.kts",
+        "tests/bindings/test_custom_types.swift",
+    ]
+);
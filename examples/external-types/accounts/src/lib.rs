@@ -0,0 +1,21 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountKind {
+    Personal,
+    Business,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountId {
+    pub number: u64,
+    pub kind: AccountKind,
+}
+
+fn open_account(number: u64, kind: AccountKind) -> AccountId {
+    AccountId { number, kind }
+}
+
+include!(concat!(env!("OUT_DIR"), "/accounts.uniffi.rs"));
@@ -0,0 +1,4 @@
+import uniffi.accounts.*
+
+val account = openAccount(42UL, AccountKind.BUSINESS)
+assert(account == AccountId(42UL, AccountKind.BUSINESS))
@@ -0,0 +1,4 @@
+from accounts import *
+
+account = open_account(42, AccountKind.BUSINESS)
+assert account == AccountId(42, AccountKind.BUSINESS)
@@ -0,0 +1,4 @@
+import accounts
+
+let account = openAccount(number: 42, kind: .business)
+assert(account == AccountId(number: 42, kind: .business))
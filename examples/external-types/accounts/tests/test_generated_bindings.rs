@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/accounts.idl",
+    [
+        "tests/bindings/test_accounts.py",
+        "tests/bindings/test_accounts.kts",
+        "tests/bindings/test_accounts.swift",
+    ]
+);
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// The external types used in our IDL come from the crate of the component that defines them.
+use uniffi_accounts::{AccountId, AccountKind};
+
+fn kind_of(account: AccountId) -> AccountKind {
+    account.kind
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncResult {
+    account: AccountId,
+    version: u32,
+}
+
+#[derive(Debug)]
+struct Syncer {
+    versions: Mutex<HashMap<u64, u32>>,
+    last_synced: Mutex<Option<AccountId>>,
+}
+
+impl Syncer {
+    fn new() -> Self {
+        Syncer {
+            versions: Mutex::new(HashMap::new()),
+            last_synced: Mutex::new(None),
+        }
+    }
+
+    fn sync(&self, account: AccountId) -> SyncResult {
+        let mut versions = self.versions.lock().unwrap();
+        let version = versions.entry(account.number).or_insert(0);
+        *version += 1;
+        *self.last_synced.lock().unwrap() = Some(account.clone());
+        SyncResult {
+            account,
+            version: *version,
+        }
+    }
+
+    fn last_synced(&self) -> Option<AccountId> {
+        self.last_synced.lock().unwrap().clone()
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/sync.uniffi.rs"));
@@ -0,0 +1,16 @@
+// The external types are the very same classes as in the bindings of the `accounts` component.
+import uniffi.accounts.*
+import uniffi.sync.*
+
+val personal = openAccount(1UL, AccountKind.PERSONAL)
+val business = AccountId(2UL, AccountKind.BUSINESS)
+
+assert(kindOf(personal) == AccountKind.PERSONAL)
+assert(kindOf(business) == AccountKind.BUSINESS)
+
+val syncer = Syncer()
+assert(syncer.lastSynced() == null)
+assert(syncer.sync(personal) == SyncResult(personal, 1U))
+assert(syncer.sync(business) == SyncResult(business, 1U))
+assert(syncer.sync(personal) == SyncResult(personal, 2U))
+assert(syncer.lastSynced() == personal)
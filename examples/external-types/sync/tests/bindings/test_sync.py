@@ -0,0 +1,17 @@
+# The external types are the very same classes as in the bindings of the `accounts` component.
+from accounts import AccountId, AccountKind, open_account
+from sync import *
+
+personal = open_account(1, AccountKind.PERSONAL)
+business = AccountId(2, AccountKind.BUSINESS)
+
+assert kind_of(personal) == AccountKind.PERSONAL
+assert kind_of(business) == AccountKind.BUSINESS
+
+syncer = Syncer()
+assert syncer.last_synced() is None
+assert syncer.sync(personal) == SyncResult(personal, 1)
+assert syncer.sync(business) == SyncResult(business, 1)
+assert syncer.sync(personal) == SyncResult(personal, 2)
+assert isinstance(syncer.last_synced(), AccountId)
+assert syncer.last_synced() == personal
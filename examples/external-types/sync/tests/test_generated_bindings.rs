@@ -0,0 +1,7 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/sync.idl", "../accounts/src/accounts.idl"],
+    [
+        "tests/bindings/test_sync.py",
+        "tests/bindings/test_sync.kts",
+    ]
+);
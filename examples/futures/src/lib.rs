@@ -0,0 +1,107 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+// A future that completes after the given number of milliseconds.
+//
+// uniffi polls futures on a single thread, so they mustn't block it. A real component would
+// get its timers from its async runtime, but to keep this example free of dependencies the
+// timer waits on a thread of its own and wakes the future when it's done.
+struct Timer {
+    state: Arc<Mutex<TimerState>>,
+}
+
+#[derive(Default)]
+struct TimerState {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl Timer {
+    fn new(ms: u32) -> Self {
+        let state = Arc::new(Mutex::new(TimerState::default()));
+        let thread_state = Arc::clone(&state);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(ms.into()));
+            let mut state = thread_state.lock().unwrap();
+            state.done = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        Self { state }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.done {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+async fn say_after(ms: u32, who: String) -> String {
+    Timer::new(ms).await;
+    format!("Hello, {}!", who)
+}
+
+async fn sleep(ms: u32) {
+    Timer::new(ms).await;
+}
+
+#[derive(Debug, thiserror::Error)]
+enum FuturesError {
+    #[error("Can't divide by zero")]
+    DivisionByZero,
+}
+
+async fn divide_after(ms: u32, dividend: u32, divisor: u32) -> Result<u32, FuturesError> {
+    Timer::new(ms).await;
+    dividend
+        .checked_div(divisor)
+        .ok_or(FuturesError::DivisionByZero)
+}
+
+// Async methods take `&self` like the methods of any other threadsafe object.
+#[derive(Debug, Default)]
+pub struct Megaphone {
+    count: AtomicU32,
+}
+
+impl Megaphone {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn say_after(&self, ms: u32, who: String) -> String {
+        let greeting = say_after(ms, who).await;
+        self.count.fetch_add(1, Ordering::SeqCst);
+        greeting.to_uppercase()
+    }
+
+    async fn new_after(ms: u32) -> Arc<Self> {
+        Timer::new(ms).await;
+        Arc::new(Self::new())
+    }
+
+    fn get_count(&self) -> u32 {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/futures.uniffi.rs"));
@@ -0,0 +1,52 @@
+import java.util.concurrent.ConcurrentLinkedQueue
+import java.util.concurrent.CountDownLatch
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.EmptyCoroutineContext
+import kotlin.coroutines.startCoroutine
+import uniffi.futures.*
+
+// Run a suspend function to completion, blocking the current thread until it's done.
+// This saves the test from depending on kotlinx.coroutines just for `runBlocking`.
+fun <T> runBlocking(block: suspend () -> T): T {
+    val done = CountDownLatch(1)
+    var outcome: Result<T>? = null
+    block.startCoroutine(Continuation(EmptyCoroutineContext) { result ->
+        outcome = result
+        done.countDown()
+    })
+    done.await()
+    return outcome!!.getOrThrow()
+}
+
+runBlocking {
+    assert(sayAfter(10u, "Alice") == "Hello, Alice!")
+    sleep(10u)
+
+    assert(divideAfter(10u, 42u, 6u) == 7u)
+    try {
+        divideAfter(10u, 42u, 0u)
+        throw RuntimeException("Should have thrown")
+    } catch (e: FuturesErrorException.DivisionByZero) {
+        // It's okay!
+    }
+
+    val megaphone = Megaphone.newAfter(10u)
+    assert(megaphone.sayAfter(10u, "Dave") == "HELLO, DAVE!")
+    assert(megaphone.getCount() == 1u)
+    megaphone.destroy()
+}
+
+// The calls run concurrently, and starting them doesn't block the calling thread.
+val start = System.nanoTime()
+val done = CountDownLatch(2)
+val greetings = ConcurrentLinkedQueue<String>()
+for (who in listOf("Bob", "Carol")) {
+    suspend { sayAfter(200u, who) }.startCoroutine(Continuation(EmptyCoroutineContext) { result ->
+        greetings.add(result.getOrThrow())
+        done.countDown()
+    })
+}
+assert(System.nanoTime() - start < 100_000_000L)
+done.await()
+assert(System.nanoTime() - start < 400_000_000L)
+assert(greetings.sorted() == listOf("Hello, Bob!", "Hello, Carol!"))
@@ -0,0 +1,37 @@
+import asyncio
+import time
+from futures import *
+
+async def main():
+    assert await say_after(10, "Alice") == "Hello, Alice!"
+
+    # The calls run concurrently, rather than one after the other.
+    start = time.monotonic()
+    results = await asyncio.gather(say_after(200, "Bob"), say_after(200, "Carol"), sleep(200))
+    assert results == ["Hello, Bob!", "Hello, Carol!", None]
+    assert time.monotonic() - start < 0.4
+
+    # The event loop isn't blocked while rust is waiting.
+    ticks = 0
+    async def tick():
+        nonlocal ticks
+        while True:
+            ticks += 1
+            await asyncio.sleep(0.01)
+    ticker = asyncio.ensure_future(tick())
+    await sleep(100)
+    ticker.cancel()
+    assert ticks > 1
+
+    assert await divide_after(10, 42, 6) == 7
+    try:
+        await divide_after(10, 42, 0)
+        raise AssertionError("should have thrown")
+    except FuturesError.DivisionByZero:
+        pass
+
+    megaphone = await Megaphone.new_after(10)
+    assert await megaphone.say_after(10, "Dave") == "HELLO, DAVE!"
+    assert megaphone.get_count() == 1
+
+asyncio.run(main())
@@ -0,0 +1,36 @@
+import Foundation
+import futures
+
+// Scripts can't `await` at the top level, so the test runs in a task
+// and the main thread waits for it to finish.
+let done = DispatchSemaphore(value: 0)
+Task {
+    let greeting = await sayAfter(ms: 10, who: "Alice")
+    assert(greeting == "Hello, Alice!", "async functions return their value")
+    await sleep(ms: 10)
+
+    let quotient = try! await divideAfter(ms: 10, dividend: 42, divisor: 6)
+    assert(quotient == 7, "async functions that can throw return their value")
+    do {
+        let _ = try await divideAfter(ms: 10, dividend: 42, divisor: 0)
+        fatalError("Should have thrown")
+    } catch FuturesError.DivisionByZero {
+        // It's okay!
+    }
+
+    // The calls run concurrently, rather than one after the other.
+    let start = Date()
+    async let bob = sayAfter(ms: 200, who: "Bob")
+    async let carol = sayAfter(ms: 200, who: "Carol")
+    let greetings = await [bob, carol]
+    assert(greetings == ["Hello, Bob!", "Hello, Carol!"], "concurrent calls return their values")
+    assert(Date().timeIntervalSince(start) < 0.4, "the calls ran concurrently")
+
+    let megaphone = await Megaphone.newAfter(ms: 10)
+    let shout = await megaphone.sayAfter(ms: 10, who: "Dave")
+    assert(shout == "HELLO, DAVE!", "async methods return their value")
+    assert(megaphone.getCount() == 1, "async methods can use their object")
+
+    done.signal()
+}
+done.wait()
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/futures.idl",
+    [
+        "tests/bindings/test_futures.kts",
+        "tests/bindings/test_futures.swift",
+        "tests/bindings/test_futures.py",
+    ]
+);
@@ -44,4 +44,22 @@ pub fn intersection(ln1: Line, ln2: Line) -> Option<Point> {
     })
 }
 
+// A line whose ends are in the same place is really just a point.
+pub fn simplify(ln: Line) -> PointOrLine {
+    if (ln.start.coord_x - ln.end.coord_x).abs() < EPSILON
+        && (ln.start.coord_y - ln.end.coord_y).abs() < EPSILON
+    {
+        PointOrLine::Point(ln.start)
+    } else {
+        PointOrLine::Line(ln)
+    }
+}
+
+pub fn start_of(shape: PointOrLine) -> Point {
+    match shape {
+        PointOrLine::Point(p) => p,
+        PointOrLine::Line(ln) => ln.start,
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/geometry.uniffi.rs"));
@@ -8,3 +8,9 @@ assert( gradient(ln2) == 1.0 )
 
 assert( intersection(ln1, ln2) == Point(0.0, 0.0) )
 assert( intersection(ln1, ln1) == null )
+
+assert( simplify(Line(Point(1.0,1.0), Point(1.0,1.0))) == PointOrLine.PointValue(Point(1.0,1.0)) )
+assert( simplify(ln1) == PointOrLine.LineValue(ln1) )
+
+assert( startOf(PointOrLine.PointValue(Point(3.0,4.0))) == Point(3.0,4.0) )
+assert( startOf(PointOrLine.LineValue(ln2)) == Point(1.0,1.0) )
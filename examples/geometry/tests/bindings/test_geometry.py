@@ -8,3 +8,14 @@ assert gradient(ln2) == 1
 
 assert intersection(ln1, ln2) == Point(0, 0)
 assert intersection(ln1, ln1) is None
+
+assert simplify(Line(Point(1,1), Point(1,1))) == PointOrLine.Point(Point(1,1))
+assert simplify(ln1) == PointOrLine.Line(ln1)
+
+assert start_of(PointOrLine.Point(Point(3,4))) == Point(3,4)
+assert start_of(PointOrLine.Line(ln2)) == Point(1,1)
+
+# Doc comments from the IDL file become docstrings.
+assert gradient.__doc__ == "Calculate the gradient of a line, i.e. how far it rises for each unit it runs."
+assert Point.__doc__ == "A point in two-dimensional space."
+assert Line.__doc__ is None
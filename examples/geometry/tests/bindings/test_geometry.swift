@@ -8,3 +8,9 @@ assert(gradient(ln: ln2) == 1.0)
 
 assert(intersection(ln1: ln1, ln2: ln2) == Point(coordX: 0, coordY: 0))
 assert(intersection(ln1: ln1, ln2: ln1) == nil)
+
+assert(simplify(ln: Line(start: Point(coordX: 1, coordY: 1), end: Point(coordX: 1, coordY: 1))) == .point(Point(coordX: 1, coordY: 1)))
+assert(simplify(ln: ln1) == .line(ln1))
+
+assert(startOf(shape: .point(Point(coordX: 3, coordY: 4))) == Point(coordX: 3, coordY: 4))
+assert(startOf(shape: .line(ln2)) == Point(coordX: 1, coordY: 1))
@@ -0,0 +1,70 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `Animal` and `Pet` are inherited from by other interfaces, so the scaffolding declares them
+// as traits, with `Pet` having `Animal` as a supertrait. The structs for the interfaces that
+// inherit from them implement those traits.
+
+struct Dog {
+    name: String,
+    owner: Option<String>,
+    fetched: u32,
+}
+
+impl Dog {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            owner: None,
+            fetched: 0,
+        }
+    }
+
+    fn fetch(&mut self) -> u32 {
+        self.fetched += 1;
+        self.fetched
+    }
+}
+
+impl Animal for Dog {
+    fn name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn speak(&mut self) -> String {
+        "Woof!".to_string()
+    }
+}
+
+impl Pet for Dog {
+    fn owner(&mut self) -> Option<String> {
+        self.owner.clone()
+    }
+
+    fn adopt(&mut self, owner: String) {
+        self.owner = Some(owner);
+    }
+}
+
+struct Fish {
+    name: String,
+}
+
+impl Fish {
+    fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Animal for Fish {
+    fn name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn speak(&mut self) -> String {
+        "...".to_string()
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/inheritance.uniffi.rs"));
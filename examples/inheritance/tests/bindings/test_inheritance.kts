@@ -0,0 +1,24 @@
+import uniffi.inheritance.*
+
+val dog = Dog("Rex")
+assert(dog.name() == "Rex")
+assert(dog.speak() == "Woof!")
+assert(dog.owner() == null)
+dog.adopt("Alice")
+assert(dog.owner() == "Alice")
+assert(dog.fetch() == 1u)
+assert(dog.fetch() == 2u)
+
+val fish = Fish("Wanda")
+assert(fish.name() == "Wanda")
+assert(fish.speak() == "...")
+
+// The objects can be used wherever one of the interfaces they inherit from is expected.
+fun introduce(animal: Animal) = "${animal.name()} says ${animal.speak()}"
+
+val animals: List<Animal> = listOf(dog, fish)
+assert(animals.map { introduce(it) } == listOf("Rex says Woof!", "Wanda says ..."))
+assert(animals.filterIsInstance<Pet>() == listOf(dog))
+
+dog.destroy()
+fish.destroy()
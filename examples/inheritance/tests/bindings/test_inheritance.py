@@ -0,0 +1,26 @@
+from inheritance import *
+
+dog = Dog("Rex")
+assert dog.name() == "Rex"
+assert dog.speak() == "Woof!"
+assert dog.owner() is None
+dog.adopt("Alice")
+assert dog.owner() == "Alice"
+assert dog.fetch() == 1
+assert dog.fetch() == 2
+
+fish = Fish("Wanda")
+assert fish.name() == "Wanda"
+assert fish.speak() == "..."
+
+# The objects are instances of the classes for the interfaces they inherit from.
+assert isinstance(dog, Pet)
+assert isinstance(dog, Animal)
+assert isinstance(fish, Animal)
+assert not isinstance(fish, Pet)
+
+# So they can be used wherever one of those is expected.
+def introduce(animal):
+    return "{} says {}".format(animal.name(), animal.speak())
+
+assert [introduce(animal) for animal in [dog, fish]] == ["Rex says Woof!", "Wanda says ..."]
@@ -0,0 +1,23 @@
+import inheritance
+
+let dog = Dog(name: "Rex")
+assert(dog.name() == "Rex")
+assert(dog.speak() == "Woof!")
+assert(dog.owner() == nil)
+dog.adopt(owner: "Alice")
+assert(dog.owner() == "Alice")
+assert(dog.fetch() == 1)
+assert(dog.fetch() == 2)
+
+let fish = Fish(name: "Wanda")
+assert(fish.name() == "Wanda")
+assert(fish.speak() == "...")
+
+// The objects can be used wherever one of the interfaces they inherit from is expected.
+func introduce(_ animal: Animal) -> String {
+    return "\(animal.name()) says \(animal.speak())"
+}
+
+let animals: [Animal] = [dog, fish]
+assert(animals.map(introduce) == ["Rex says Woof!", "Wanda says ..."])
+assert(animals.compactMap { $0 as? Pet }.count == 1)
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/inheritance.idl",
+    [
+        "tests/bindings/test_inheritance.kts",
+        "tests/bindings/test_inheritance.swift",
+        "tests/bindings/test_inheritance.py",
+    ]
+);
@@ -0,0 +1,91 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// The iterators handed out to the foreign-language code must be `Send + 'static`, since they
+// outlive the call that created them. To let the tests check that the bindings free them, each
+// one is wrapped in a `Tracked` iterator that counts how many of them are still alive.
+static LIVE_ITERATORS: AtomicU32 = AtomicU32::new(0);
+
+struct Tracked<I>(I);
+
+impl<I: Iterator> Tracked<I> {
+    fn new(iter: I) -> Self {
+        LIVE_ITERATORS.fetch_add(1, Ordering::SeqCst);
+        Self(iter)
+    }
+}
+
+impl<I: Iterator> Iterator for Tracked<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.0.next()
+    }
+}
+
+impl<I> Drop for Tracked<I> {
+    fn drop(&mut self) {
+        LIVE_ITERATORS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn live_iterators() -> u32 {
+    LIVE_ITERATORS.load(Ordering::SeqCst)
+}
+
+// `[Iterator]` functions can return anything that can be turned into an iterator over the items.
+fn fibonacci() -> impl Iterator<Item = u64> + Send {
+    Tracked::new(
+        std::iter::successors(Some((0u64, Some(1u64))), |&(a, b)| {
+            b.map(|b| (b, a.checked_add(b)))
+        })
+        .map(|(a, _)| a),
+    )
+}
+
+fn count_to(n: u32) -> impl Iterator<Item = u32> + Send {
+    Tracked::new(1..=n)
+}
+
+fn words(text: String) -> impl Iterator<Item = String> + Send {
+    let words: Vec<String> = text.split_whitespace().map(String::from).collect();
+    Tracked::new(words.into_iter())
+}
+
+#[derive(Debug, Clone)]
+pub struct Book {
+    title: String,
+    year: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Library {
+    books: Vec<Book>,
+}
+
+impl Library {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_book(&mut self, title: String, year: u32) {
+        self.books.push(Book { title, year })
+    }
+
+    // The iterator can't borrow from the library, because the foreign-language code
+    // keeps using it after the method returns, so it takes copies of the books.
+    fn books_before(&self, year: u32) -> impl Iterator<Item = Book> + Send {
+        let books: Vec<Book> = self
+            .books
+            .iter()
+            .filter(|book| book.year < year)
+            .cloned()
+            .collect();
+        Tracked::new(books.into_iter())
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/iterators.uniffi.rs"));
@@ -0,0 +1,36 @@
+import uniffi.iterators.*
+
+assert(fibonacci().asSequence().take(10).toList() == listOf(0uL, 1uL, 1uL, 2uL, 3uL, 5uL, 8uL, 13uL, 21uL, 34uL))
+assert(fibonacci().asSequence().count() == 94)
+assert(words("the quick  brown\tfox").asSequence().toList() == listOf("the", "quick", "brown", "fox"))
+assert(!words("").hasNext())
+
+// Items are taken from rust in batches, which is invisible to the caller.
+assert(countTo(1000u).asSequence().toList() == (1u..1000u).toList())
+
+// Exhausted iterators are freed straight away.
+assert(liveIterators() == 0u)
+
+// Others can be freed before they're exhausted.
+val numbers = countTo(1000u)
+assert(numbers.asSequence().take(3).toList() == listOf(1u, 2u, 3u))
+assert(liveIterators() == 1u)
+numbers.close()
+assert(liveIterators() == 0u)
+
+countTo(1000u).use { numbers ->
+    assert(numbers.next() == 1u)
+}
+assert(liveIterators() == 0u)
+
+val library = Library()
+library.addBook("Frankenstein", 1818u)
+library.addBook("Dracula", 1897u)
+library.addBook("The Time Machine", 1895u)
+val books = library.booksBefore(1896u)
+// The iterator holds its own copies of the books.
+library.addBook("Persuasion", 1817u)
+assert(books.asSequence().map { it.title }.toList() == listOf("Frankenstein", "The Time Machine"))
+assert(library.booksBefore(1818u).asSequence().map { it.title }.toList() == listOf("Persuasion"))
+assert(liveIterators() == 0u)
+library.destroy()
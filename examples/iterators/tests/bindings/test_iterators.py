@@ -0,0 +1,38 @@
+import itertools
+from iterators import *
+
+assert list(itertools.islice(fibonacci(), 10)) == [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+assert len(list(fibonacci())) == 94
+assert list(words("the quick  brown\tfox")) == ["the", "quick", "brown", "fox"]
+assert list(words("")) == []
+
+# Items are taken from rust in batches, which is invisible to the caller.
+assert list(count_to(1000)) == list(range(1, 1001))
+assert sum(count_to(200)) == 20100
+
+# Exhausted iterators are freed straight away.
+assert live_iterators() == 0
+
+# Others can be freed before they're exhausted.
+numbers = count_to(1000)
+assert list(itertools.islice(numbers, 3)) == [1, 2, 3]
+assert live_iterators() == 1
+numbers.close()
+assert live_iterators() == 0
+
+# Or when they're garbage collected.
+numbers = count_to(1000)
+next(numbers)
+del numbers
+assert live_iterators() == 0
+
+library = Library()
+library.add_book("Frankenstein", 1818)
+library.add_book("Dracula", 1897)
+library.add_book("The Time Machine", 1895)
+books = library.books_before(1896)
+# The iterator holds its own copies of the books.
+library.add_book("Persuasion", 1817)
+assert [book.title for book in books] == ["Frankenstein", "The Time Machine"]
+assert [book.title for book in library.books_before(1818)] == ["Persuasion"]
+assert live_iterators() == 0
@@ -0,0 +1,39 @@
+import iterators
+
+assert(Array(fibonacci().prefix(10)) == [0, 1, 1, 2, 3, 5, 8, 13, 21, 34])
+assert(Array(fibonacci()).count == 94)
+assert(Array(words(text: "the quick  brown\tfox")) == ["the", "quick", "brown", "fox"])
+assert(words(text: "").next() == nil)
+
+// Items are taken from rust in batches, which is invisible to the caller.
+assert(Array(countTo(n: 1000)) == Array(1...1000))
+
+// Exhausted iterators are freed straight away.
+assert(liveIterators() == 0)
+
+do {
+    // Others can be freed before they're exhausted.
+    let numbers = countTo(n: 1000)
+    assert(Array(numbers.prefix(3)) == [1, 2, 3])
+    assert(liveIterators() == 1)
+    numbers.close()
+    assert(liveIterators() == 0)
+}
+
+do {
+    // Or when they're deinitialized.
+    let numbers = countTo(n: 1000)
+    assert(numbers.next() == 1)
+}
+assert(liveIterators() == 0)
+
+let library = Library()
+library.addBook(title: "Frankenstein", year: 1818)
+library.addBook(title: "Dracula", year: 1897)
+library.addBook(title: "The Time Machine", year: 1895)
+let books = library.booksBefore(year: 1896)
+// The iterator holds its own copies of the books.
+library.addBook(title: "Persuasion", year: 1817)
+assert(books.map { $0.title } == ["Frankenstein", "The Time Machine"])
+assert(library.booksBefore(year: 1818).map { $0.title } == ["Persuasion"])
+assert(liveIterators() == 0)
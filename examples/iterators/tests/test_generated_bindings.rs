@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/iterators.idl",
+    [
+        "tests/bindings/test_iterators.kts",
+        "tests/bindings/test_iterators.swift",
+        "tests/bindings/test_iterators.py",
+    ]
+);
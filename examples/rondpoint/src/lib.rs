@@ -35,6 +35,13 @@ pub enum Enumeration {
     Trois,
 }
 
+#[derive(Debug, Clone)]
+pub enum EnumerationAvecDonnees {
+    Zero,
+    Un { premier: u32 },
+    Deux { premier: u32, second: String },
+}
+
 #[allow(non_camel_case_types)]
 #[allow(non_snake_case)]
 pub struct minusculeMAJUSCULEDict {
@@ -58,6 +65,14 @@ fn copie_carte(e: HashMap<String, Enumeration>) -> HashMap<String, Enumeration>
     e
 }
 
+fn copie_enumeration_avec_donnees(e: EnumerationAvecDonnees) -> EnumerationAvecDonnees {
+    e
+}
+
+fn copie_enumerations_avec_donnees(e: Vec<EnumerationAvecDonnees>) -> Vec<EnumerationAvecDonnees> {
+    e
+}
+
 fn copie_dictionnaire(d: Dictionnaire) -> Dictionnaire {
     d
 }
@@ -168,4 +183,97 @@ impl Stringifier {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct OptionneurDictionnaire {
+    i8_var: i8,
+    u8_var: u8,
+    i16_var: i16,
+    u16_var: u16,
+    i32_var: i32,
+    u32_var: u32,
+    i64_var: i64,
+    u64_var: u64,
+    float_var: f32,
+    double_var: f64,
+    boolean_var: bool,
+    string_var: String,
+    list_var: Vec<String>,
+    enumeration_var: Enumeration,
+    enumeration_null_var: Option<Enumeration>,
+}
+
+// The default values only exist in the foreign-language bindings, which fill them in
+// for any arguments that aren't given. So all these methods have to do is hand them back.
+#[derive(Debug, Clone)]
+struct Optionneur;
+impl Optionneur {
+    fn new() -> Self {
+        Optionneur
+    }
+
+    fn sinon_boolean(&self, value: bool) -> bool {
+        value
+    }
+
+    fn sinon_string(&self, value: String) -> String {
+        value
+    }
+
+    fn sinon_sequence(&self, value: Vec<String>) -> Vec<String> {
+        value
+    }
+
+    fn sinon_carte(&self, value: HashMap<String, u32>) -> HashMap<String, u32> {
+        value
+    }
+
+    fn sinon_null(&self, value: Option<String>) -> Option<String> {
+        value
+    }
+
+    fn sinon_zero(&self, value: Option<i32>) -> Option<i32> {
+        value
+    }
+
+    fn sinon_u8_dec(&self, value: u8) -> u8 {
+        value
+    }
+
+    fn sinon_i8_dec(&self, value: i8) -> i8 {
+        value
+    }
+
+    fn sinon_u16_hex(&self, value: u16) -> u16 {
+        value
+    }
+
+    fn sinon_i16_hex(&self, value: i16) -> i16 {
+        value
+    }
+
+    fn sinon_u32_oct(&self, value: u32) -> u32 {
+        value
+    }
+
+    fn sinon_i64_dec(&self, value: i64) -> i64 {
+        value
+    }
+
+    fn sinon_f32(&self, value: f32) -> f32 {
+        value
+    }
+
+    fn sinon_f64(&self, value: f64) -> f64 {
+        value
+    }
+
+    fn sinon_enum(&self, value: Enumeration) -> Enumeration {
+        value
+    }
+
+    fn sinon_dictionnaire(&self, value: OptionneurDictionnaire) -> OptionneurDictionnaire {
+        value
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/rondpoint.uniffi.rs"));
@@ -8,6 +8,15 @@ assert(copieEnumeration(Enumeration.DEUX) == Enumeration.DEUX)
 assert(copieEnumerations(listOf(Enumeration.UN, Enumeration.DEUX)) == listOf(Enumeration.UN, Enumeration.DEUX))
 assert(copieCarte(mapOf("1" to Enumeration.UN, "2" to Enumeration.DEUX)) == mapOf("1" to Enumeration.UN, "2" to Enumeration.DEUX))
 
+val variants = listOf(
+    EnumerationAvecDonnees.Zero,
+    EnumerationAvecDonnees.Un(1u),
+    EnumerationAvecDonnees.Deux(2u, "deux")
+)
+assert(copieEnumerationAvecDonnees(variants[1]) == variants[1])
+assert(copieEnumerationsAvecDonnees(variants) == variants)
+assert(EnumerationAvecDonnees.Un(1u) != EnumerationAvecDonnees.Un(2u))
+
 assert(switcheroo(false))
 
 // Test the roundtrip across the FFI.
@@ -119,3 +128,36 @@ listOf(0.0F, 1.0F, -1.0F, Float.MIN_VALUE, Float.MAX_VALUE).affirmEnchaine(st::t
 // Doubles
 // MIN_VALUE is 4.9E-324. Accuracy and formatting get weird at small sizes.
 listOf(0.0, 1.0, -1.0, Double.MIN_VALUE, Double.MAX_VALUE).affirmEnchaine(st::toStringDouble)  { s, n -> s.toDouble() == n }
+
+// Default values are filled in for any arguments that aren't given.
+val op = Optionneur()
+assert(op.sinonBoolean() == false)
+assert(op.sinonBoolean(true) == true)
+assert(op.sinonString() == "default")
+assert(op.sinonString("autre") == "autre")
+assert(op.sinonSequence() == listOf<String>())
+assert(op.sinonCarte() == mapOf<String, UInt>())
+assert(op.sinonNull() == null)
+assert(op.sinonZero() == 0)
+assert(op.sinonU8Dec() == 42.toUByte())
+assert(op.sinonI8Dec() == (-42).toByte())
+assert(op.sinonU16Hex() == 0xffff.toUShort())
+assert(op.sinonI16Hex() == (-0x7f).toShort())
+assert(op.sinonU32Oct() == 493u)
+assert(op.sinonI64Dec() == 42L)
+assert(op.sinonF32() == 42.0f)
+assert(op.sinonF64() == 42.1)
+assert(op.sinonEnum() == Enumeration.TROIS)
+assert(op.sinonEnum(Enumeration.UN) == Enumeration.UN)
+
+// Record fields can have default values too.
+val defaults = OptionneurDictionnaire()
+assert(defaults.i8Var == (-8).toByte())
+assert(defaults.u16Var == 0x10.toUShort())
+assert(defaults.stringVar == "default")
+assert(defaults.listVar == listOf<String>())
+assert(defaults.enumerationVar == Enumeration.DEUX)
+assert(defaults.enumerationNullVar == null)
+assert(op.sinonDictionnaire(defaults) == defaults)
+assert(OptionneurDictionnaire(i32Var = 1).i32Var == 1)
+
@@ -8,6 +8,11 @@ assert(copieEnumeration(e: .deux) == .deux)
 assert(copieEnumerations(e: [.un, .deux]) == [.un, .deux])
 assert(copieCarte(c: ["1": .un, "2": .deux]) == ["1": .un, "2": .deux])
 
+let variants: [EnumerationAvecDonnees] = [.zero, .un(premier: 1), .deux(premier: 2, second: "deux")]
+assert(copieEnumerationAvecDonnees(e: variants[1]) == variants[1])
+assert(copieEnumerationsAvecDonnees(e: variants) == variants)
+assert(EnumerationAvecDonnees.un(premier: 1) != .un(premier: 2))
+
 assert(switcheroo(b: false))
 
 // Test the roundtrip across the FFI.
@@ -90,6 +95,38 @@ assert("uniffi 💚 swift!" == wellKnown, "wellKnownString 'uniffi 💚 swift!'
 // Doubles
 [.zero, 1, -1, .leastNonzeroMagnitude, .greatestFiniteMagnitude].affirmEnchaine(st.toStringDouble) { Double.init($0) == $1 }
 
+// Default values are filled in for any arguments that aren't given.
+let op = Optionneur()
+assert(op.sinonBoolean() == false, "default boolean")
+assert(op.sinonBoolean(value: true) == true, "given boolean")
+assert(op.sinonString() == "default", "default string")
+assert(op.sinonString(value: "autre") == "autre", "given string")
+assert(op.sinonSequence() == [], "default sequence")
+assert(op.sinonCarte() == [:], "default map")
+assert(op.sinonNull() == nil, "default null")
+assert(op.sinonZero() == 0, "default optional")
+assert(op.sinonU8Dec() == 42, "default u8")
+assert(op.sinonI8Dec() == -42, "default i8")
+assert(op.sinonU16Hex() == 0xffff, "default hex u16")
+assert(op.sinonI16Hex() == -0x7f, "default hex i16")
+assert(op.sinonU32Oct() == 0o755, "default octal u32")
+assert(op.sinonI64Dec() == 42, "default i64")
+assert(op.sinonF32() == 42.0, "default float")
+assert(op.sinonF64() == 42.1, "default double")
+assert(op.sinonEnum() == .trois, "default enum")
+assert(op.sinonEnum(value: .un) == .un, "given enum")
+
+// Record fields can have default values too.
+let defaults = OptionneurDictionnaire()
+assert(defaults.i8Var == -8, "default i8 field")
+assert(defaults.u16Var == 0x10, "default hex u16 field")
+assert(defaults.stringVar == "default", "default string field")
+assert(defaults.listVar == [], "default sequence field")
+assert(defaults.enumerationVar == .deux, "default enum field")
+assert(defaults.enumerationNullVar == nil, "default null field")
+assert(op.sinonDictionnaire(value: defaults) == defaults, "record with defaults round-trips")
+assert(OptionneurDictionnaire(i32Var: 1).i32Var == 1, "fields can be given by name")
+
 // Some extension functions for testing the results of roundtripping and stringifying
 extension Array where Element: Equatable {
     static func defaultEquals(_ observed: String, expected: Element) -> Bool {
@@ -12,6 +12,9 @@ assert( s.getPosition() == Point(1.0, 2.0) )
 s.moveBy(Vector(-4.0, 2.0))
 assert( s.getPosition() == Point(-3.0, 4.0) )
 
+val srel = Sprite.newRelativeTo(Point(0.0, 1.0), Vector(1.0, 1.5))
+assert( srel.getPosition() == Point(1.0, 2.5) )
+
 s.destroy()
 try { 
     s.moveBy(Vector(0.0, 0.0))
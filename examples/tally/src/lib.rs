@@ -0,0 +1,55 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+
+// Enums used as map keys need to be hashable.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+pub struct Rota {
+    hours: HashMap<Weekday, u32>,
+}
+
+fn byte_histogram(data: Vec<u8>) -> HashMap<u8, u32> {
+    let mut histogram = HashMap::new();
+    for byte in data {
+        *histogram.entry(byte).or_insert(0) += 1;
+    }
+    histogram
+}
+
+fn add_counts(a: HashMap<Weekday, u32>, b: HashMap<Weekday, u32>) -> HashMap<Weekday, u32> {
+    let mut counts = a;
+    for (day, count) in b {
+        *counts.entry(day).or_insert(0) += count;
+    }
+    counts
+}
+
+fn invert(names: HashMap<String, i64>) -> HashMap<i64, String> {
+    names.into_iter().map(|(name, n)| (n, name)).collect()
+}
+
+fn total_hours(rota: Rota) -> u32 {
+    rota.hours.values().sum()
+}
+
+fn word_counts(text: String) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+include!(concat!(env!("OUT_DIR"), "/tally.uniffi.rs"));
@@ -0,0 +1,20 @@
+import uniffi.tally.*
+
+// Maps can have integer keys.
+val hello = "hello".toByteArray()
+assert(byteHistogram(hello) == mapOf('h'.toByte().toUByte() to 1u, 'e'.toByte().toUByte() to 1u, 'l'.toByte().toUByte() to 2u, 'o'.toByte().toUByte() to 1u))
+assert(byteHistogram(byteArrayOf()).isEmpty())
+assert(invert(mapOf("one" to 1L, "minus one" to -1L)) == mapOf(1L to "one", -1L to "minus one"))
+
+// Maps can have enum keys.
+val a = mapOf(Weekday.MONDAY to 2u, Weekday.FRIDAY to 1u)
+val b = mapOf(Weekday.FRIDAY to 3u, Weekday.SUNDAY to 5u)
+assert(addCounts(a, b) == mapOf(Weekday.MONDAY to 2u, Weekday.FRIDAY to 4u, Weekday.SUNDAY to 5u))
+
+// Maps can be nested in other types.
+val rota = Rota(mapOf(Weekday.MONDAY to 8u, Weekday.TUESDAY to 4u))
+assert(totalHours(rota) == 12u)
+
+// Strings can be passed to rust as arguments.
+assert(wordCounts("the cat and the hat") == mapOf("the" to 2u, "cat" to 1u, "and" to 1u, "hat" to 1u))
+assert(wordCounts("").isEmpty())
@@ -0,0 +1,19 @@
+from tally import *
+
+# Maps can have integer keys.
+assert byte_histogram(b"hello") == {ord("h"): 1, ord("e"): 1, ord("l"): 2, ord("o"): 1}
+assert byte_histogram(b"") == {}
+assert invert({"one": 1, "minus one": -1}) == {1: "one", -1: "minus one"}
+
+# Maps can have enum keys.
+a = {Weekday.MONDAY: 2, Weekday.FRIDAY: 1}
+b = {Weekday.FRIDAY: 3, Weekday.SUNDAY: 5}
+assert add_counts(a, b) == {Weekday.MONDAY: 2, Weekday.FRIDAY: 4, Weekday.SUNDAY: 5}
+
+# Maps can be nested in other types.
+rota = Rota({Weekday.MONDAY: 8, Weekday.TUESDAY: 4})
+assert total_hours(rota) == 12
+
+# Strings can be passed to rust as arguments.
+assert word_counts("the cat and the hat") == {"the": 2, "cat": 1, "and": 1, "hat": 1}
+assert word_counts("") == {}
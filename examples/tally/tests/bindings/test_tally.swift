@@ -0,0 +1,19 @@
+import tally
+import Foundation
+
+// Maps can have integer keys.
+assert(byteHistogram(data: Data("hello".utf8)) == [104: 1, 101: 1, 108: 2, 111: 1], "integer keys work")
+assert(invert(names: ["one": 1, "minus one": -1]) == [1: "one", -1: "minus one"], "keys can be inverted")
+
+// Maps can have enum keys.
+let a: [Weekday: UInt32] = [.monday: 2, .friday: 1]
+let b: [Weekday: UInt32] = [.friday: 3, .sunday: 5]
+assert(addCounts(a: a, b: b) == [.monday: 2, .friday: 4, .sunday: 5], "enum keys work")
+
+// Maps can be nested in other types.
+let rota = Rota(hours: [.monday: 8, .tuesday: 4])
+assert(totalHours(rota: rota) == 12, "nested maps work")
+
+// Strings can be passed to rust as arguments.
+assert(wordCounts(text: "the cat and the hat") == ["the": 2, "cat": 1, "and": 1, "hat": 1], "string arguments work")
+assert(wordCounts(text: "").isEmpty, "empty strings work")
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/tally.idl",
+    [
+        "tests/bindings/test_tally.py",
+        "tests/bindings/test_tally.kts",
+        "tests/bindings/test_tally.swift",
+    ]
+);
@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+// Threadsafe objects are shared between threads, so all their methods take `&self`
+// and they have to take care of their own synchronization, here using atomics.
+#[derive(Debug, Default)]
+pub struct Counter {
+    is_busy: AtomicBool,
+    count: AtomicU64,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn busy_wait(&self, ms: u32) {
+        self.is_busy.store(true, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(ms.into()));
+        self.is_busy.store(false, Ordering::SeqCst);
+    }
+
+    fn increment_if_busy(&self) {
+        if self.is_busy.load(Ordering::SeqCst) {
+            self.count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn get_count(&self) -> u64 {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/threadsafe.uniffi.rs"));
@@ -0,0 +1,22 @@
+import java.util.concurrent.Executors
+import java.util.concurrent.TimeUnit
+import uniffi.threadsafe.*
+
+val counter = Counter()
+assert(counter.getCount() == 0uL)
+
+// Keep the counter busy on another thread. If calling a method locked the counter,
+// then `incrementIfBusy` would have to wait until it wasn't busy any more.
+val executor = Executors.newSingleThreadExecutor()
+val busy = executor.submit { counter.busyWait(1000u) }
+Thread.sleep(100)
+repeat(10) { counter.incrementIfBusy() }
+busy.get()
+assert(counter.getCount() == 10uL)
+
+// Once it's not busy, incrementing does nothing.
+counter.incrementIfBusy()
+assert(counter.getCount() == 10uL)
+
+executor.shutdown()
+executor.awaitTermination(1, TimeUnit.SECONDS)
@@ -0,0 +1,20 @@
+import threading
+import time
+from threadsafe import *
+
+counter = Counter()
+assert counter.get_count() == 0
+
+# Keep the counter busy on another thread. If calling a method locked the counter,
+# then `increment_if_busy` would have to wait until it wasn't busy any more.
+busy = threading.Thread(target=counter.busy_wait, args=(1000,))
+busy.start()
+time.sleep(0.1)
+for _ in range(10):
+    counter.increment_if_busy()
+busy.join()
+assert counter.get_count() == 10
+
+# Once it's not busy, incrementing does nothing.
+counter.increment_if_busy()
+assert counter.get_count() == 10
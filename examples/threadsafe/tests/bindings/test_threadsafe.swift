@@ -0,0 +1,23 @@
+import Foundation
+import threadsafe
+
+let counter = Counter()
+assert(counter.getCount() == 0, "the counter starts at zero")
+
+// Keep the counter busy on another thread. If calling a method locked the counter,
+// then `incrementIfBusy` would have to wait until it wasn't busy any more.
+let done = DispatchSemaphore(value: 0)
+DispatchQueue.global().async {
+    counter.busyWait(ms: 1000)
+    done.signal()
+}
+Thread.sleep(forTimeInterval: 0.1)
+for _ in 0..<10 {
+    counter.incrementIfBusy()
+}
+done.wait()
+assert(counter.getCount() == 10, "the counter was incremented while busy")
+
+// Once it's not busy, incrementing does nothing.
+counter.incrementIfBusy()
+assert(counter.getCount() == 10, "the counter isn't incremented when not busy")
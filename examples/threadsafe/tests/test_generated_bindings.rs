@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/threadsafe.idl",
+    [
+        "tests/bindings/test_threadsafe.kts",
+        "tests/bindings/test_threadsafe.swift",
+        "tests/bindings/test_threadsafe.py",
+    ]
+);
@@ -0,0 +1,120 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+const PROTOCOL_VERSION: u32 = 2;
+
+// A crate of goods, which can be handed back and forth between rust and the foreign-language
+// code. Crates never change, so they can be shared by the two sides without any locking.
+#[derive(Debug)]
+pub struct Crate {
+    weight: u32,
+}
+
+impl Crate {
+    const VOLUME: f64 = 1.5;
+    const STACKABLE: bool = true;
+
+    fn new(weight: u32) -> Crate {
+        Crate { weight }
+    }
+
+    fn empty() -> Arc<Crate> {
+        Arc::new(Crate { weight: 0 })
+    }
+
+    fn weight(&self) -> u32 {
+        self.weight
+    }
+}
+
+pub struct Shipment {
+    destination: String,
+    item: Arc<Crate>,
+}
+
+fn pack(weight: u32) -> Arc<Crate> {
+    Arc::new(Crate::new(weight))
+}
+
+fn weigh(item: &Crate) -> u32 {
+    item.weight
+}
+
+// A truck holds the crates loaded into it, until they're unloaded again.
+#[derive(Debug)]
+pub struct Truck {
+    max_weight: u32,
+    crates: Vec<Arc<Crate>>,
+    destinations: Vec<String>,
+}
+
+impl Truck {
+    const MAX_AXLES: u8 = 6;
+
+    fn new(max_weight: u32) -> Truck {
+        Truck {
+            max_weight,
+            crates: Vec::new(),
+            destinations: Vec::new(),
+        }
+    }
+
+    fn loaded_with(max_weight: u32, shipment: Shipment) -> Truck {
+        let mut truck = Truck::new(max_weight);
+        truck.load_shipment(shipment);
+        truck
+    }
+
+    fn max_weight_for(axles: u8) -> u32 {
+        u32::from(axles) * 50
+    }
+
+    fn get_max_weight(&self) -> u32 {
+        self.max_weight
+    }
+
+    fn set_max_weight(&mut self, value: u32) {
+        self.max_weight = value
+    }
+
+    fn get_crate_count(&self) -> u32 {
+        self.crates.len() as u32
+    }
+
+    fn load(&mut self, item: Arc<Crate>) {
+        if self.total_weight() + item.weight <= self.max_weight {
+            self.crates.push(item);
+        }
+    }
+
+    fn load_shipment(&mut self, shipment: Shipment) {
+        self.destinations.push(shipment.destination);
+        self.load(shipment.item)
+    }
+
+    fn destinations(&self) -> Vec<String> {
+        self.destinations.clone()
+    }
+
+    fn total_weight(&self) -> u32 {
+        self.crates.iter().map(|c| c.weight).sum()
+    }
+
+    fn unload_heaviest(&mut self) -> Option<Arc<Crate>> {
+        let (index, _) = self
+            .crates
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, c)| c.weight)?;
+        Some(self.crates.remove(index))
+    }
+
+    fn unload(&mut self) -> Vec<Arc<Crate>> {
+        std::mem::take(&mut self.crates)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/warehouse.uniffi.rs"));
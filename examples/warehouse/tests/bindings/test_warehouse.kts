@@ -0,0 +1,67 @@
+import uniffi.warehouse.*
+
+// Objects can be returned from functions, and passed to them.
+val crate = pack(10u)
+assert(crate.weight() == 10u)
+assert(weigh(Crate(7u)) == 7u)
+
+// Rust shares objects with the caller, so they can still be used after passing them.
+assert(weigh(crate) == 10u)
+assert(crate.weight() == 10u)
+
+val truck = Truck(100u)
+truck.load(Crate(20u))
+truck.load(Crate(50u))
+assert(truck.totalWeight() == 70u)
+
+// The same object can be passed more than once.
+val twice = Truck(100u)
+twice.load(crate)
+twice.load(crate)
+assert(twice.totalWeight() == 20u)
+assert(crate.weight() == 10u)
+
+// Objects can be nested in records.
+truck.loadShipment(Shipment("Berlin", Crate(25u)))
+assert(truck.totalWeight() == 95u)
+assert(truck.destinations() == listOf("Berlin"))
+
+// Rust hands objects back when returning them, and they can be passed in again.
+val heaviest = truck.unloadHeaviest()!!
+assert(heaviest.weight() == 50u)
+assert(truck.totalWeight() == 45u)
+truck.load(heaviest)
+assert(truck.totalWeight() == 95u)
+
+// Objects can be returned in sequences.
+val crates = truck.unload()
+assert(crates.map { it.weight() }.sorted() == listOf(20u, 25u, 50u))
+assert(truck.totalWeight() == 0u)
+assert(truck.unloadHeaviest() == null)
+
+// Named constructors are functions on the companion object.
+val loaded = Truck.loadedWith(30u, Shipment("Paris", Crate(30u)))
+assert(loaded.totalWeight() == 30u)
+assert(loaded.destinations() == listOf("Paris"))
+
+// Static methods are functions on the companion object.
+assert(Crate.empty().weight() == 0u)
+assert(Truck.maxWeightFor(3u) == 150u)
+
+// Attributes are properties.
+assert(loaded.maxWeight == 30u)
+assert(loaded.crateCount == 1u)
+loaded.load(Crate(10u))
+assert(loaded.crateCount == 1u)
+loaded.maxWeight = 40u
+assert(loaded.maxWeight == 40u)
+loaded.load(Crate(10u))
+assert(loaded.crateCount == 2u)
+
+// Constants are defined on the companion object.
+assert(Crate.VOLUME == 1.5)
+assert(Crate.STACKABLE)
+assert(Truck.MAX_AXLES == 6.toUByte())
+
+// Constants in the namespace are top-level constants.
+assert(PROTOCOL_VERSION == 2.toUInt())
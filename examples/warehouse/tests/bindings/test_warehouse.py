@@ -0,0 +1,72 @@
+from warehouse import *
+
+# Objects can be returned from functions, and passed to them.
+crate = pack(10)
+assert crate.weight() == 10
+assert weigh(Crate(7)) == 7
+
+# Rust shares objects with the caller, so they can still be used after passing them.
+assert weigh(crate) == 10
+assert crate.weight() == 10
+
+truck = Truck(100)
+truck.load(Crate(20))
+truck.load(Crate(50))
+assert truck.total_weight() == 70
+
+# The same object can be passed more than once.
+twice = Truck(100)
+twice.load(crate)
+twice.load(crate)
+assert twice.total_weight() == 20
+assert crate.weight() == 10
+
+# Objects can be nested in records.
+truck.load_shipment(Shipment("Berlin", Crate(25)))
+assert truck.total_weight() == 95
+assert truck.destinations() == ["Berlin"]
+
+# Rust hands objects back when returning them, and they can be passed in again.
+heaviest = truck.unload_heaviest()
+assert heaviest.weight() == 50
+assert truck.total_weight() == 45
+truck.load(heaviest)
+assert truck.total_weight() == 95
+
+# Objects can be returned in sequences.
+crates = truck.unload()
+assert sorted(c.weight() for c in crates) == [20, 25, 50]
+assert truck.total_weight() == 0
+assert truck.unload_heaviest() is None
+
+# Named constructors are classmethods.
+loaded = Truck.loaded_with(30, Shipment("Paris", Crate(30)))
+assert loaded.total_weight() == 30
+assert loaded.destinations() == ["Paris"]
+
+# Static methods are called on the class.
+assert Crate.empty().weight() == 0
+assert Truck.max_weight_for(3) == 150
+
+# Attributes are properties.
+assert loaded.max_weight == 30
+assert loaded.crate_count == 1
+loaded.load(Crate(10))
+assert loaded.crate_count == 1
+loaded.max_weight = 40
+assert loaded.max_weight == 40
+loaded.load(Crate(10))
+assert loaded.crate_count == 2
+try:
+    loaded.crate_count = 0
+    raise RuntimeError("Should not be able to set a readonly attribute")
+except AttributeError:
+    pass
+
+# Constants are class attributes.
+assert Crate.VOLUME == 1.5
+assert Crate.STACKABLE
+assert Truck.MAX_AXLES == 6
+
+# Constants in the namespace are module attributes.
+assert PROTOCOL_VERSION == 2
@@ -0,0 +1,67 @@
+import warehouse
+
+// Objects can be returned from functions, and passed to them.
+let crate = pack(weight: 10)
+assert(crate.weight() == 10, "pack works")
+assert(weigh(item: Crate(weight: 7)) == 7, "weigh works")
+
+// Rust shares objects with the caller, so they can still be used after passing them.
+assert(weigh(item: crate) == 10, "weigh works twice")
+assert(crate.weight() == 10, "the crate is still usable")
+
+let truck = Truck(maxWeight: 100)
+truck.load(item: Crate(weight: 20))
+truck.load(item: Crate(weight: 50))
+assert(truck.totalWeight() == 70, "crates are loaded")
+
+// The same object can be passed more than once.
+let twice = Truck(maxWeight: 100)
+twice.load(item: crate)
+twice.load(item: crate)
+assert(twice.totalWeight() == 20, "the same crate is loaded twice")
+assert(crate.weight() == 10, "the crate is still usable after loading it")
+
+// Objects can be nested in records.
+truck.loadShipment(shipment: Shipment(destination: "Berlin", item: Crate(weight: 25)))
+assert(truck.totalWeight() == 95, "shipments are loaded")
+assert(truck.destinations() == ["Berlin"], "destinations are recorded")
+
+// Rust hands objects back when returning them, and they can be passed in again.
+let heaviest = truck.unloadHeaviest()!
+assert(heaviest.weight() == 50, "the heaviest crate is unloaded")
+assert(truck.totalWeight() == 45, "the heaviest crate is gone")
+truck.load(item: heaviest)
+assert(truck.totalWeight() == 95, "crates can be loaded again")
+
+// Objects can be returned in sequences.
+let crates = truck.unload()
+assert(crates.map { $0.weight() }.sorted() == [20, 25, 50], "all crates are unloaded")
+assert(truck.totalWeight() == 0, "the truck is empty")
+assert(truck.unloadHeaviest() == nil, "there are no more crates")
+
+// Named constructors are static methods.
+let loaded = Truck.loadedWith(maxWeight: 30, shipment: Shipment(destination: "Paris", item: Crate(weight: 30)))
+assert(loaded.totalWeight() == 30, "the named constructor loads the shipment")
+assert(loaded.destinations() == ["Paris"], "the named constructor records the destination")
+
+// Static methods are called on the class.
+assert(Crate.empty().weight() == 0, "static methods can return objects")
+assert(Truck.maxWeightFor(axles: 3) == 150, "static methods can take arguments")
+
+// Attributes are properties.
+assert(loaded.maxWeight == 30, "attributes can be read")
+assert(loaded.crateCount == 1, "readonly attributes can be read")
+loaded.load(item: Crate(weight: 10))
+assert(loaded.crateCount == 1, "the truck is full")
+loaded.maxWeight = 40
+assert(loaded.maxWeight == 40, "attributes can be written")
+loaded.load(item: Crate(weight: 10))
+assert(loaded.crateCount == 2, "the truck can take more now")
+
+// Constants are static properties.
+assert(Crate.volume == 1.5, "double constants")
+assert(Crate.stackable, "boolean constants")
+assert(Truck.maxAxles == 6, "integer constants")
+
+// Constants in the namespace are global constants.
+assert(protocolVersion == 2, "namespace constants")
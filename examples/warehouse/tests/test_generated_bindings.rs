@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/warehouse.idl",
+    [
+        "tests/bindings/test_warehouse.py",
+        "tests/bindings/test_warehouse.kts",
+        "tests/bindings/test_warehouse.swift",
+    ]
+);
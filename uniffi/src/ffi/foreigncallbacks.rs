@@ -0,0 +1,181 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for calling back into foreign-language implementations of callback interfaces.
+//!
+//! Each callback interface declared in the IDL gets a trait in the generated rust scaffolding,
+//! and foreign-language code can implement that trait by providing an object of its own.
+//! The foreign-language bindings keep such objects in a handle map, and pass the handle
+//! over the FFI in place of the object itself.
+//!
+//! For each callback interface the foreign-language code registers a single [`ForeignCallback`]
+//! function with rust, which acts a bit like a vtable: rust calls it with the handle of the
+//! target object, the index of the method to invoke, and the method's arguments serialized
+//! into a [`RustBuffer`]. The foreign-language code is responsible for freeing that buffer,
+//! and writes the serialized return value (if any) into a [`RustBuffer`] that it allocated by
+//! calling the `rustbuffer_alloc` FFI function, which rust then takes ownership of.
+//!
+//! Method index `0` is reserved to tell the foreign-language code that rust has dropped its
+//! reference to the object, and that it can be removed from the handle map. Index `1`
+//! corresponds to the first method declared on the callback interface, `2` to the second,
+//! and so on.
+
+use super::RustBuffer;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The signature of the function that foreign-language code registers to receive callbacks.
+///
+/// It is passed the handle of the foreign-language object, the index of the method to invoke,
+/// the serialized arguments, and a pointer at which to write the serialized return value.
+/// It returns zero on success and a non-zero value if the call could not be completed.
+pub type ForeignCallback = unsafe extern "C" fn(
+    handle: u64,
+    method: u32,
+    args: RustBuffer,
+    ret_buf: *mut RustBuffer,
+) -> i32;
+
+/// The method index reserved for freeing a foreign-language object once rust is done with it.
+pub const IDX_CALLBACK_FREE: u32 = 0;
+
+/// Holds the [`ForeignCallback`] for a single callback interface.
+///
+/// The generated scaffolding declares one static instance of this struct per callback
+/// interface, which is populated when the foreign-language bindings are loaded.
+pub struct ForeignCallbackInternals {
+    callback_ptr: AtomicUsize,
+}
+
+impl ForeignCallbackInternals {
+    pub const fn new() -> Self {
+        ForeignCallbackInternals {
+            callback_ptr: AtomicUsize::new(0),
+        }
+    }
+
+    /// Register the foreign callback.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different callback has already been registered, since any handles
+    /// held by rust would then be dispatched to the wrong handle map.
+    pub fn set_callback(&self, callback: ForeignCallback) {
+        let as_usize = callback as usize;
+        if let Err(existing) =
+            self.callback_ptr
+                .compare_exchange(0, as_usize, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            if existing != as_usize {
+                panic!("Bug: a different foreign callback has already been registered");
+            }
+        }
+    }
+
+    /// Get the registered foreign callback, if any.
+    pub fn get_callback(&self) -> Option<ForeignCallback> {
+        match self.callback_ptr.load(Ordering::SeqCst) {
+            0 => None,
+            // Safety: the only non-zero values we store are `ForeignCallback` pointers.
+            ptr => Some(unsafe { std::mem::transmute::<usize, ForeignCallback>(ptr) }),
+        }
+    }
+
+    /// Invoke a method on the foreign-language object with the given handle.
+    ///
+    /// Returns the buffer containing the serialized return value, which will be empty
+    /// for methods that do not return anything.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no callback has been registered, or if the foreign-language code
+    /// reports that it was unable to complete the call. Callback interface methods
+    /// have no way to report errors to their rust caller, so there's not much else
+    /// we can do in that case.
+    pub fn invoke_callback(&self, handle: u64, method: u32, args: RustBuffer) -> RustBuffer {
+        let callback = self
+            .get_callback()
+            .expect("foreign callback has not been registered");
+        let mut ret_buf = RustBuffer::new();
+        let status = unsafe { callback(handle, method, args, &mut ret_buf) };
+        if status != 0 {
+            panic!(
+                "foreign callback failed with status {} (method {})",
+                status, method
+            );
+        }
+        ret_buf
+    }
+}
+
+impl Default for ForeignCallbackInternals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    unsafe extern "C" fn echo_method(
+        handle: u64,
+        method: u32,
+        args: RustBuffer,
+        ret_buf: *mut RustBuffer,
+    ) -> i32 {
+        let mut bytes = args.destroy_into_vec();
+        bytes.extend_from_slice(&handle.to_be_bytes());
+        bytes.extend_from_slice(&method.to_be_bytes());
+        *ret_buf = RustBuffer::from_vec(bytes);
+        0
+    }
+
+    unsafe extern "C" fn always_fails(_: u64, _: u32, args: RustBuffer, _: *mut RustBuffer) -> i32 {
+        args.destroy();
+        -1
+    }
+
+    #[test]
+    fn test_invoke_callback_passes_args_and_returns_buffer() {
+        let internals = ForeignCallbackInternals::new();
+        assert!(internals.get_callback().is_none());
+        internals.set_callback(echo_method);
+        let ret = internals.invoke_callback(42, 3, RustBuffer::from_vec(vec![7u8]));
+        let mut expected = vec![7u8];
+        expected.extend_from_slice(&42u64.to_be_bytes());
+        expected.extend_from_slice(&3u32.to_be_bytes());
+        assert_eq!(ret.destroy_into_vec(), expected);
+    }
+
+    #[test]
+    fn test_set_callback_is_idempotent() {
+        let internals = ForeignCallbackInternals::new();
+        internals.set_callback(echo_method);
+        internals.set_callback(echo_method);
+        assert!(internals.get_callback().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_callback_rejects_a_different_callback() {
+        let internals = ForeignCallbackInternals::new();
+        internals.set_callback(echo_method);
+        internals.set_callback(always_fails);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invoke_callback_panics_on_failure() {
+        let internals = ForeignCallbackInternals::new();
+        internals.set_callback(always_fails);
+        internals.invoke_callback(1, 1, RustBuffer::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invoke_callback_panics_if_not_registered() {
+        let internals = ForeignCallbackInternals::new();
+        internals.invoke_callback(1, 1, RustBuffer::new());
+    }
+}
@@ -0,0 +1,129 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ffi_support::{Handle, HandleError, HandleMap};
+use std::sync::{Arc, RwLock};
+
+/// A handle map for objects that can be used from several threads at once.
+///
+/// The generated scaffolding keeps most objects in an `ffi_support::ConcurrentHandleMap`,
+/// which puts each object behind its own `Mutex` so that only one method call can use it
+/// at a time. Objects declared as `[Threadsafe]` in the IDL live in one of these instead,
+/// which keeps each object in an `Arc`. A method call clones the `Arc` and releases the map's
+/// lock before calling into the object, so calls on the same object can run in parallel and
+/// the object is responsible for its own synchronization.
+pub struct ArcHandleMap<T: Send + Sync> {
+    map: RwLock<HandleMap<Arc<T>>>,
+}
+
+impl<T: Send + Sync> ArcHandleMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HandleMap::new()),
+        }
+    }
+
+    /// Insert an object into the map, returning the newly allocated handle for it.
+    pub fn insert(&self, v: T) -> Handle {
+        self.insert_arc(Arc::new(v))
+    }
+
+    /// Insert a reference to an object into the map, returning the newly allocated handle for it.
+    ///
+    /// The same object can be in the map under several handles, each of which keeps it alive.
+    pub fn insert_arc(&self, v: Arc<T>) -> Handle {
+        let mut map = self.map.write().unwrap();
+        map.insert(v)
+    }
+
+    /// Get a reference to the object for a handle, for calling methods on.
+    ///
+    /// The map's lock is only held while cloning the `Arc`, not for as long as the caller
+    /// holds on to the result.
+    pub fn get_u64(&self, h: u64) -> Result<Arc<T>, HandleError> {
+        let map = self.map.read().unwrap();
+        Ok(Arc::clone(map.get(Handle::from_u64(h)?)?))
+    }
+
+    /// Remove the object for a handle from the map.
+    ///
+    /// Method calls that are still running on other threads keep their own reference to the
+    /// object, so the result is only the sole owner of the object once they've finished.
+    pub fn remove_u64(&self, h: u64) -> Result<Arc<T>, HandleError> {
+        let mut map = self.map.write().unwrap();
+        map.remove(Handle::from_u64(h)?)
+    }
+
+    /// Remove the object for a handle from the map and drop it.
+    pub fn delete_u64(&self, h: u64) -> Result<(), HandleError> {
+        // Drop the object after releasing the lock, so that a panic in its destructor
+        // can't poison the map.
+        self.remove_u64(h).map(drop)
+    }
+}
+
+impl<T: Send + Sync> Default for ArcHandleMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Trait for the `[Threadsafe]` objects declared in the component IDL, which can be passed
+/// over the FFI as arguments, return values and fields.
+///
+/// The generated scaffolding implements this to give access to the handle map that it keeps
+/// the objects in, which the `ViaFfi` implementation for `Arc<T>` uses to pass them by handle.
+#[doc(hidden)]
+pub trait FfiObject: Send + Sync + Sized + 'static {
+    fn handle_map() -> &'static ArcHandleMap<Self>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct Counter(AtomicU32);
+
+    #[test]
+    fn test_objects_can_be_shared_while_in_the_map() {
+        let map = ArcHandleMap::<Counter>::new();
+        let h = map.insert(Counter::default()).into_u64();
+        let first = map.get_u64(h).unwrap();
+        let second = map.get_u64(h).unwrap();
+        first.0.fetch_add(1, Ordering::SeqCst);
+        second.0.fetch_add(1, Ordering::SeqCst);
+        assert_eq!(map.get_u64(h).unwrap().0.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_objects_can_be_in_the_map_under_several_handles() {
+        let map = ArcHandleMap::<Counter>::new();
+        let obj = Arc::new(Counter::default());
+        let first = map.insert_arc(Arc::clone(&obj)).into_u64();
+        let second = map.insert_arc(Arc::clone(&obj)).into_u64();
+        assert_ne!(first, second);
+        map.delete_u64(first).unwrap();
+        map.get_u64(second)
+            .unwrap()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        map.delete_u64(second).unwrap();
+        assert_eq!(Arc::try_unwrap(obj).ok().unwrap().0.into_inner(), 1);
+    }
+
+    #[test]
+    fn test_removed_objects_outlive_running_calls() {
+        let map = ArcHandleMap::<Counter>::new();
+        let h = map.insert(Counter::default()).into_u64();
+        let running = map.get_u64(h).unwrap();
+        let removed = map.remove_u64(h).unwrap();
+        assert!(Arc::try_unwrap(removed).is_err());
+        running.0.fetch_add(1, Ordering::SeqCst);
+        assert!(map.get_u64(h).is_err());
+        assert!(map.delete_u64(h).is_err());
+        assert_eq!(Arc::try_unwrap(running).ok().unwrap().0.into_inner(), 1);
+    }
+}
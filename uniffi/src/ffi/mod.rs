@@ -3,7 +3,17 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 pub mod foreignbytes;
+pub mod foreigncallbacks;
+pub mod handle_maps;
 pub mod rustbuffer;
+pub mod rustcalls;
+pub mod rustfutures;
+pub mod rustiterators;
 
 pub use foreignbytes::*;
+pub use foreigncallbacks::*;
+pub use handle_maps::*;
 pub use rustbuffer::*;
+pub use rustcalls::*;
+pub use rustfutures::*;
+pub use rustiterators::*;
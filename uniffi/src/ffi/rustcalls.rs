@@ -0,0 +1,185 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for calling rust functions from the generated scaffolding and reporting the outcome.
+//!
+//! Every function in the generated FFI takes a trailing `&mut RustCallStatus` argument,
+//! which the foreign-language bindings allocate and inspect after the call returns:
+//!
+//!  * A `code` of [`CALL_SUCCESS`] means the call completed normally, and the return value
+//!    of the function is valid.
+//!  * A `code` of [`CALL_ERROR`] means the rust function returned one of the errors declared
+//!    in the IDL. The error is serialized into `error_buf`, and should be lifted by the foreign
+//!    language into a corresponding exception.
+//!  * A `code` of [`CALL_PANIC`] means something went unexpectedly wrong, such as a panic in
+//!    the rust code. `error_buf` holds the panic message as a string.
+//!
+//! In both error cases the foreign-language code takes ownership of `error_buf` and must free
+//! it. The return value of the function is a meaningless default and must be ignored.
+
+use super::RustBuffer;
+use crate::ViaFfi;
+use ffi_support::IntoFfi;
+use std::any::Any;
+use std::panic;
+
+/// The call completed successfully.
+pub const CALL_SUCCESS: i8 = 0;
+/// The call returned an error declared in the IDL, serialized into `error_buf`.
+pub const CALL_ERROR: i8 = 1;
+/// The call panicked, and `error_buf` contains the panic message.
+pub const CALL_PANIC: i8 = 2;
+
+/// Describes the outcome of a call into the generated FFI.
+///
+/// The foreign-language code should initialize this with a `code` of [`CALL_SUCCESS`] and an
+/// empty `error_buf`, and pass a pointer to it as the final argument of each FFI call.
+#[repr(C)]
+pub struct RustCallStatus {
+    pub code: i8,
+    pub error_buf: RustBuffer,
+}
+
+impl RustCallStatus {
+    pub fn is_success(&self) -> bool {
+        self.code == CALL_SUCCESS
+    }
+}
+
+impl Default for RustCallStatus {
+    fn default() -> Self {
+        Self {
+            code: CALL_SUCCESS,
+            error_buf: RustBuffer::new(),
+        }
+    }
+}
+
+/// Call a rust function that cannot fail, and report any panics via the given `RustCallStatus`.
+pub fn call_with_output<R, F>(out_status: &mut RustCallStatus, callback: F) -> R::Value
+where
+    F: panic::UnwindSafe + FnOnce() -> R,
+    R: IntoFfi,
+{
+    make_call(out_status, || Ok(callback()))
+}
+
+/// Call a rust function that returns a `Result`, and report the outcome via the given
+/// `RustCallStatus`.
+///
+/// The error type must be one that can be serialized for transfer over the FFI, which in
+/// practice means one of the `[Error]` types declared in the IDL.
+pub fn call_with_result<R, E, F>(out_status: &mut RustCallStatus, callback: F) -> R::Value
+where
+    F: panic::UnwindSafe + FnOnce() -> Result<R, E>,
+    E: ViaFfi<FfiType = RustBuffer>,
+    R: IntoFfi,
+{
+    make_call(out_status, || callback().map_err(ViaFfi::lower))
+}
+
+fn make_call<R, F>(out_status: &mut RustCallStatus, callback: F) -> R::Value
+where
+    F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
+    R: IntoFfi,
+{
+    match panic::catch_unwind(callback) {
+        Ok(Ok(v)) => {
+            out_status.code = CALL_SUCCESS;
+            v.into_ffi_value()
+        }
+        Ok(Err(buf)) => {
+            out_status.code = CALL_ERROR;
+            out_status.error_buf = buf;
+            R::ffi_default()
+        }
+        Err(cause) => {
+            let message = panic_message(cause);
+            log::error!("Caught a panic calling rust code: {:?}", message);
+            out_status.code = CALL_PANIC;
+            out_status.error_buf = message.lower();
+            R::ffi_default()
+        }
+    }
+}
+
+/// Get the message from the payload of a panic caught by `catch_unwind`.
+pub(crate) fn panic_message(cause: Box<dyn Any + Send>) -> String {
+    if let Some(s) = cause.downcast_ref::<&'static str>() {
+        (*s).to_string()
+    } else if let Some(s) = cause.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic!".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::try_lift_from_buffer;
+
+    #[derive(Debug, PartialEq)]
+    struct TestError(u32);
+
+    unsafe impl ViaFfi for TestError {
+        type FfiType = RustBuffer;
+
+        fn lower(self) -> Self::FfiType {
+            crate::lower_into_buffer(self)
+        }
+
+        fn try_lift(v: Self::FfiType) -> anyhow::Result<Self> {
+            try_lift_from_buffer(v)
+        }
+
+        fn write<B: bytes::BufMut>(&self, buf: &mut B) {
+            buf.put_u32(self.0);
+        }
+
+        fn try_read<B: bytes::Buf>(buf: &mut B) -> anyhow::Result<Self> {
+            crate::check_remaining(buf, 4)?;
+            Ok(TestError(buf.get_u32()))
+        }
+    }
+
+    fn might_fail(fail: bool) -> Result<u8, TestError> {
+        if fail {
+            Err(TestError(42))
+        } else {
+            Ok(7)
+        }
+    }
+
+    #[test]
+    fn test_call_with_output_returns_value() {
+        let mut status = RustCallStatus::default();
+        assert_eq!(call_with_output(&mut status, || 3u32), 3);
+        assert!(status.is_success());
+    }
+
+    #[test]
+    fn test_call_with_result_serializes_the_error() {
+        let mut status = RustCallStatus::default();
+        assert_eq!(call_with_result(&mut status, || might_fail(false)), 7);
+        assert_eq!(status.code, CALL_SUCCESS);
+
+        let mut status = RustCallStatus::default();
+        assert_eq!(call_with_result(&mut status, || might_fail(true)), 0);
+        assert_eq!(status.code, CALL_ERROR);
+        assert_eq!(
+            TestError::try_lift(status.error_buf).unwrap(),
+            TestError(42)
+        );
+    }
+
+    #[test]
+    fn test_panics_are_reported_with_their_message() {
+        let mut status = RustCallStatus::default();
+        let v = call_with_output(&mut status, || -> u64 { panic!("oh no") });
+        assert_eq!(v, 0);
+        assert_eq!(status.code, CALL_PANIC);
+        assert_eq!(String::try_lift(status.error_buf).unwrap(), "oh no");
+    }
+}
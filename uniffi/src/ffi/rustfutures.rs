@@ -0,0 +1,264 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for running rust futures on behalf of `[Async]` functions, and reporting the outcome.
+//!
+//! The FFI function for an async function or method takes two extra arguments just before the
+//! trailing `&mut RustCallStatus`: a [`FutureCallback`], and an opaque `u64` of callback data
+//! that the foreign-language code uses to find the call that's waiting for the result.
+//! The FFI function lifts its arguments and starts the rust future running, then returns
+//! straight away without waiting for it. If something goes wrong before the future has started,
+//! such as a panic while lifting the arguments, it's reported through the `RustCallStatus` as
+//! usual and the callback is never called. Otherwise, the callback is called exactly once when
+//! the future completes, with the callback data, a status code and a buffer:
+//!
+//!  * A code of [`CALL_SUCCESS`] means the future completed normally, and the buffer holds
+//!    its serialized return value. The buffer is empty for functions that don't return anything.
+//!  * A code of [`CALL_ERROR`] means the future resolved to one of the errors declared in the IDL,
+//!    and the buffer holds the serialized error.
+//!  * A code of [`CALL_PANIC`] means the future panicked, and the buffer holds the panic message.
+//!
+//! In every case the foreign-language code takes ownership of the buffer and must free it.
+//!
+//! Futures are polled on a single background thread owned by this module, which is also
+//! the thread the callback is called from. That means they must be `Send + 'static`, and they
+//! shouldn't block, since that would hold up every other async call in the meantime.
+
+use super::rustcalls::{panic_message, CALL_ERROR, CALL_PANIC, CALL_SUCCESS};
+use super::RustBuffer;
+use crate::ViaFfi;
+use std::{
+    future::Future,
+    panic,
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread,
+};
+
+/// The signature of the function that rust calls when the future for an async call completes.
+///
+/// It is passed the callback data that was given when making the call, a status code and a
+/// buffer holding the result, as described in the [module docs](self).
+pub type FutureCallback = unsafe extern "C" fn(callback_data: u64, code: i8, buf: RustBuffer);
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A future that has been handed to the executor, along with what's needed to wake it up.
+///
+/// Waking the task sends it to the executor thread to be polled again. The future is taken
+/// out once it has completed, so any stray wakeups after that are harmless.
+struct Task {
+    future: Mutex<Option<BoxFuture>>,
+}
+
+impl Task {
+    fn poll(self: Arc<Self>) {
+        let mut slot = self.future.lock().unwrap();
+        if let Some(future) = slot.as_mut() {
+            let waker = Waker::from(Arc::clone(&self));
+            if future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                *slot = None;
+            }
+        }
+    }
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        schedule(self);
+    }
+}
+
+lazy_static::lazy_static! {
+    // Tasks that are ready to be polled are sent to the executor thread through this channel.
+    static ref EXECUTOR: Mutex<mpsc::Sender<Arc<Task>>> = {
+        let (sender, receiver) = mpsc::channel::<Arc<Task>>();
+        thread::Builder::new()
+            .name("uniffi-executor".to_string())
+            .spawn(move || {
+                for task in receiver {
+                    task.poll();
+                }
+            })
+            .expect("failed to start the thread for running async calls");
+        Mutex::new(sender)
+    };
+}
+
+fn schedule(task: Arc<Task>) {
+    EXECUTOR
+        .lock()
+        .unwrap()
+        .send(task)
+        .expect("the thread for running async calls has stopped");
+}
+
+/// Start running a future that cannot fail, and report its outcome via the given callback.
+///
+/// The future resolves to the serialized return value of the async function.
+pub fn run_future_with_output<F>(callback: FutureCallback, callback_data: u64, future: F)
+where
+    F: Future<Output = RustBuffer> + Send + 'static,
+{
+    spawn(callback, callback_data, async move { Ok(future.await) })
+}
+
+/// Start running a future that resolves to a `Result`, and report its outcome via the given
+/// callback.
+///
+/// Like [`call_with_result`](super::call_with_result), the error type must be one that can be
+/// serialized for transfer over the FFI, which in practice means one of the `[Error]` types
+/// declared in the IDL.
+pub fn run_future_with_result<F, E>(callback: FutureCallback, callback_data: u64, future: F)
+where
+    F: Future<Output = Result<RustBuffer, E>> + Send + 'static,
+    E: ViaFfi<FfiType = RustBuffer>,
+{
+    spawn(callback, callback_data, async move {
+        future.await.map_err(ViaFfi::lower)
+    })
+}
+
+fn spawn<F>(callback: FutureCallback, callback_data: u64, future: F)
+where
+    F: Future<Output = Result<RustBuffer, RustBuffer>> + Send + 'static,
+{
+    let mut future = Box::pin(future);
+    // Each poll of the future catches panics, so that they're reported to the foreign-language
+    // code like they would be for a synchronous call instead of taking down the executor.
+    let outcome = std::future::poll_fn(move |cx| {
+        match panic::catch_unwind(panic::AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(result)) => Poll::Ready(Ok(result)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(cause) => Poll::Ready(Err(cause)),
+        }
+    });
+    let task = async move {
+        let (code, buf) = match outcome.await {
+            Ok(Ok(buf)) => (CALL_SUCCESS, buf),
+            Ok(Err(buf)) => (CALL_ERROR, buf),
+            Err(cause) => {
+                let message = panic_message(cause);
+                log::error!("Caught a panic running an async call: {:?}", message);
+                (CALL_PANIC, message.lower())
+            }
+        };
+        unsafe { callback(callback_data, code, buf) };
+    };
+    schedule(Arc::new(Task {
+        future: Mutex::new(Some(Box::pin(task))),
+    }));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{sync::mpsc::Sender, time::Duration};
+
+    // The code and serialized result that a future completes with.
+    type Outcome = (i8, Vec<u8>);
+
+    lazy_static::lazy_static! {
+        // The callback can't capture anything, so it uses the callback data to find the channel
+        // on which to send the outcome back to the test.
+        static ref OUTCOMES: Mutex<Vec<Sender<Outcome>>> = Mutex::new(Vec::new());
+    }
+
+    unsafe extern "C" fn send_outcome(callback_data: u64, code: i8, buf: RustBuffer) {
+        let outcomes = OUTCOMES.lock().unwrap();
+        outcomes[callback_data as usize]
+            .send((code, buf.destroy_into_vec()))
+            .unwrap();
+    }
+
+    fn outcome_channel() -> (u64, mpsc::Receiver<Outcome>) {
+        let (sender, receiver) = mpsc::channel();
+        let mut outcomes = OUTCOMES.lock().unwrap();
+        outcomes.push(sender);
+        ((outcomes.len() - 1) as u64, receiver)
+    }
+
+    // A future that is only ready once another thread has woken it up.
+    struct Delay {
+        started: bool,
+        done: Arc<Mutex<bool>>,
+    }
+
+    impl Future for Delay {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if *self.done.lock().unwrap() {
+                return Poll::Ready(());
+            }
+            if !self.started {
+                self.started = true;
+                let done = Arc::clone(&self.done);
+                let waker = cx.waker().clone();
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(10));
+                    *done.lock().unwrap() = true;
+                    waker.wake();
+                });
+            }
+            Poll::Pending
+        }
+    }
+
+    fn delay() -> Delay {
+        Delay {
+            started: false,
+            done: Arc::new(Mutex::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_run_future_with_output_reports_the_value() {
+        let (data, outcome) = outcome_channel();
+        run_future_with_output(send_outcome, data, async {
+            delay().await;
+            crate::lower_into_buffer(42u32)
+        });
+        let (code, bytes) = outcome.recv().unwrap();
+        assert_eq!(code, CALL_SUCCESS);
+        assert_eq!(bytes, 42u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_run_future_with_result_serializes_the_error() {
+        let (data, outcome) = outcome_channel();
+        run_future_with_result(send_outcome, data, async {
+            delay().await;
+            Err::<RustBuffer, _>("oops".to_string())
+        });
+        let (code, bytes) = outcome.recv().unwrap();
+        assert_eq!(code, CALL_ERROR);
+        assert_eq!(
+            String::try_lift(RustBuffer::from_vec(bytes)).unwrap(),
+            "oops"
+        );
+    }
+
+    #[test]
+    fn test_panics_in_futures_are_reported_with_their_message() {
+        let (data, outcome) = outcome_channel();
+        run_future_with_output(send_outcome, data, async {
+            delay().await;
+            panic!("oh no")
+        });
+        let (code, bytes) = outcome.recv().unwrap();
+        assert_eq!(code, CALL_PANIC);
+        assert_eq!(bytes, b"oh no");
+
+        // The executor carries on running other futures afterwards.
+        let (data, outcome) = outcome_channel();
+        run_future_with_output(send_outcome, data, async { RustBuffer::new() });
+        assert_eq!(outcome.recv().unwrap(), (CALL_SUCCESS, vec![]));
+    }
+}
@@ -0,0 +1,107 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for handing rust iterators to the foreign-language code.
+//!
+//! A function declared as `[Iterator]` in the IDL returns anything that can be turned into an
+//! iterator, rather than a `Vec`, so that its items don't all need to be produced up front and
+//! serialized into a single buffer. The scaffolding moves the iterator into an [`IteratorHandleMap`]
+//! for its item type and passes the handle to the foreign-language code, which then pulls out the
+//! items in batches: each call to the `next` FFI function for the item type serializes up to the
+//! requested number of items like a `Vec`, and an empty batch means the iterator is exhausted.
+//! Once it's done with the iterator, the foreign-language code frees it with the matching `free`
+//! FFI function, which it can also do early if it doesn't need any more items.
+
+use super::RustBuffer;
+use crate::ViaFfi;
+use ffi_support::{ConcurrentHandleMap, HandleError};
+
+/// An iterator that has been handed to the foreign-language code.
+pub type BoxedIterator<T> = Box<dyn Iterator<Item = T> + Send>;
+
+/// A handle map for the iterators with a particular item type.
+pub struct IteratorHandleMap<T> {
+    map: ConcurrentHandleMap<BoxedIterator<T>>,
+}
+
+impl<T: ViaFfi + 'static> IteratorHandleMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: ConcurrentHandleMap::new(),
+        }
+    }
+
+    /// Move an iterator into the map, returning the handle to pass to the foreign-language code.
+    pub fn insert<I>(&self, items: I) -> u64
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        self.map.insert(Box::new(items.into_iter())).into_u64()
+    }
+
+    /// Take up to `max_items` items from the iterator for a handle, serialized like a `Vec`.
+    ///
+    /// This panics if the handle isn't valid, or if the iterator panicked in an earlier call.
+    pub fn next_batch(&self, handle: u64, max_items: u32) -> RustBuffer {
+        self.map
+            .get_mut_u64(handle, |iter| {
+                let batch: Vec<T> = iter.take(max_items as usize).collect();
+                Ok::<_, HandleError>(crate::lower_into_buffer(batch))
+            })
+            .expect("can't take items from an invalid iterator")
+    }
+
+    /// Remove the iterator for a handle from the map and drop it.
+    pub fn delete_u64(&self, handle: u64) -> Result<(), HandleError> {
+        self.map.delete_u64(handle)
+    }
+}
+
+impl<T: ViaFfi + 'static> Default for IteratorHandleMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn batch(map: &IteratorHandleMap<u32>, handle: u64, max_items: u32) -> Vec<u32> {
+        Vec::<u32>::try_lift(map.next_batch(handle, max_items)).unwrap()
+    }
+
+    #[test]
+    fn test_items_are_taken_in_batches_until_exhausted() {
+        let map = IteratorHandleMap::new();
+        let handle = map.insert(1..=5u32);
+        assert_eq!(batch(&map, handle, 2), vec![1, 2]);
+        assert_eq!(batch(&map, handle, 2), vec![3, 4]);
+        assert_eq!(batch(&map, handle, 2), vec![5]);
+        assert_eq!(batch(&map, handle, 2), Vec::<u32>::new());
+        map.delete_u64(handle).unwrap();
+    }
+
+    #[test]
+    fn test_iterators_are_independent() {
+        let map = IteratorHandleMap::new();
+        let first = map.insert(vec![1u32, 2]);
+        let second = map.insert((10..).step_by(10));
+        assert_eq!(batch(&map, second, 3), vec![10, 20, 30]);
+        assert_eq!(batch(&map, first, 3), vec![1, 2]);
+        // Freeing an iterator early is fine, and leaves the others alone.
+        map.delete_u64(second).unwrap();
+        assert_eq!(batch(&map, first, 3), Vec::<u32>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_freed_iterators_cannot_be_used() {
+        let map = IteratorHandleMap::new();
+        let handle = map.insert(vec![1u32]);
+        map.delete_u64(handle).unwrap();
+        map.next_batch(handle, 1);
+    }
+}
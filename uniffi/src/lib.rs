@@ -30,7 +30,13 @@
 use anyhow::{bail, Result};
 use bytes::buf::{Buf, BufMut};
 use paste::paste;
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 pub mod ffi;
 pub use ffi::*;
@@ -117,6 +123,50 @@ pub unsafe trait ViaFfi: Sized {
     /// Since we cannot statically guarantee that the foreign-language code will send valid
     /// serialized bytes for the target type, this method is fallible.
     fn try_read<B: Buf>(buf: &mut B) -> Result<Self>;
+
+    /// Write a slice of values into a buffer, as the items of a serialized `Vec`.
+    ///
+    /// By default this writes each item in turn, but a type can override it to write all the
+    /// items in one go, as `u8` does so that `Vec<u8>` is not serialized one byte at a time.
+    #[doc(hidden)]
+    fn write_items<B: BufMut>(items: &[Self], buf: &mut B) {
+        for item in items.iter() {
+            ViaFfi::write(item, buf);
+        }
+    }
+
+    /// Read the given number of values from a buffer, as the items of a serialized `Vec`.
+    ///
+    /// This is the counterpart of [`ViaFfi::write_items`].
+    #[doc(hidden)]
+    fn try_read_items<B: Buf>(buf: &mut B, len: usize) -> Result<Vec<Self>> {
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(<Self as ViaFfi>::try_read(buf)?)
+        }
+        Ok(vec)
+    }
+}
+
+/// Trait for rust types that are exposed to foreign-language code as a "custom type".
+///
+/// A custom type is declared in the component IDL as a typedef of one of the builtin types,
+/// like `[Custom] typedef string Guid;`. It is passed over the FFI in exactly the same way as
+/// that builtin type, and the generated scaffolding uses this trait to convert between the
+/// builtin type and the rust type of the same name. Unlike `ViaFfi`, this trait is safe to
+/// implement by hand, and is expected to be implemented by the component author.
+///
+/// Since foreign-language code can send any value of the builtin type, wrapping a value
+/// is fallible. Unwrapping must always succeed.
+pub trait CustomTypeWrapper: Sized {
+    /// The builtin type wrapped by this custom type, e.g. `String` for the example above.
+    type Wrapped;
+
+    /// Convert a value of the wrapped builtin type into the custom type.
+    fn wrap(val: Self::Wrapped) -> Result<Self>;
+
+    /// Convert the custom type into a value of the wrapped builtin type.
+    fn unwrap(&self) -> Self::Wrapped;
 }
 
 /// A helper function to lower a type by serializing it into a buffer.
@@ -192,7 +242,43 @@ macro_rules! impl_via_ffi_for_num_primitive {
 }
 
 impl_via_ffi_for_num_primitive! {
-    i8, u8, i16, u16, i32, u32, i64, u64, f32, f64
+    i8, i16, u16, i32, u32, i64, u64, f32, f64
+}
+
+/// Support for passing `u8` values via the FFI.
+///
+/// These are passed just like the other numeric primitives, except that a `Vec<u8>`
+/// reads and writes all of its items at once rather than one byte at a time.
+unsafe impl ViaFfi for u8 {
+    type FfiType = Self;
+
+    fn lower(self) -> Self::FfiType {
+        self
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        Ok(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(*self);
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 1)?;
+        Ok(buf.get_u8())
+    }
+
+    fn write_items<B: BufMut>(items: &[Self], buf: &mut B) {
+        buf.put_slice(items);
+    }
+
+    fn try_read_items<B: Buf>(buf: &mut B, len: usize) -> Result<Vec<Self>> {
+        check_remaining(buf, len)?;
+        let items = buf.bytes()[..len].to_vec();
+        buf.advance(len);
+        Ok(items)
+    }
 }
 
 /// Support for passing boolean values via the FFI.
@@ -280,6 +366,101 @@ unsafe impl ViaFfi for String {
     }
 }
 
+/// The number of nanoseconds in a second, which sub-second parts of times must be less than.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Support for passing timestamps via the FFI.
+///
+/// Timestamps are passed by serializing to a buffer, as a `i64` count of whole seconds since
+/// the unix epoch followed by a `u32` count of nanoseconds after that second. The seconds are
+/// rounded down, so for timestamps before the epoch they are negative and the nanoseconds count
+/// forwards from there; e.g. half a second before the epoch is `-1` seconds and `500_000_000`
+/// nanoseconds. This matches the representation used by `java.time.Instant`.
+unsafe impl ViaFfi for SystemTime {
+    type FfiType = RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        try_lift_from_buffer(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        // TODO: would be nice not to panic here on timestamps that are out of range.
+        let (seconds, nanos) = match self.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => (
+                i64::try_from(after.as_secs()).unwrap(),
+                after.subsec_nanos(),
+            ),
+            Err(e) => {
+                let before = e.duration();
+                let seconds = -i64::try_from(before.as_secs()).unwrap();
+                match before.subsec_nanos() {
+                    0 => (seconds, 0),
+                    nanos => (seconds - 1, NANOS_PER_SEC - nanos),
+                }
+            }
+        };
+        buf.put_i64(seconds);
+        buf.put_u32(nanos);
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= NANOS_PER_SEC {
+            bail!("invalid nanoseconds in timestamp: {}", nanos);
+        }
+        let whole_seconds = Duration::from_secs(seconds.unsigned_abs());
+        let epoch_second = if seconds >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(whole_seconds)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(whole_seconds)
+        };
+        match epoch_second.and_then(|t| t.checked_add(Duration::from_nanos(nanos.into()))) {
+            Some(t) => Ok(t),
+            None => bail!(
+                "timestamp out of range: {} seconds since the epoch",
+                seconds
+            ),
+        }
+    }
+}
+
+/// Support for passing durations via the FFI.
+///
+/// Durations are passed by serializing to a buffer, as a `u64` count of whole seconds
+/// followed by a `u32` count of the remaining nanoseconds.
+unsafe impl ViaFfi for Duration {
+    type FfiType = RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        try_lift_from_buffer(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u64(self.as_secs());
+        buf.put_u32(self.subsec_nanos());
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_u64();
+        let nanos = buf.get_u32();
+        if nanos >= NANOS_PER_SEC {
+            bail!("invalid nanoseconds in duration: {}", nanos);
+        }
+        Ok(Duration::new(seconds, nanos))
+    }
+}
+
 /// Support for passing optional values via the FFI.
 ///
 /// Optional values are currently always passed by serializing to a buffer.
@@ -326,9 +507,8 @@ unsafe impl<T: ViaFfi> ViaFfi for Option<T> {
 /// We write a `i32` item count followed by each item in turn.
 /// (It's a signed type due to limits of the JVM).
 ///
-/// Ideally we would pass `Vec<u8>` directly as a `RustBuffer` rather
-/// than serializing, and perhaps even pass other vector types using a
-/// similar struct. But that's for future work.
+/// A `Vec<u8>` declared as `bytes` in the IDL is instead passed directly
+/// as a `RustBuffer`, using [`lower_bytes`] and [`try_lift_bytes`].
 unsafe impl<T: ViaFfi> ViaFfi for Vec<T> {
     type FfiType = RustBuffer;
 
@@ -344,31 +524,43 @@ unsafe impl<T: ViaFfi> ViaFfi for Vec<T> {
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(self.len()).unwrap();
         buf.put_i32(len); // We limit arrays to i32::MAX items
-        for item in self.iter() {
-            ViaFfi::write(item, buf);
-        }
+        <T as ViaFfi>::write_items(self, buf);
     }
 
     fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut vec = Vec::with_capacity(len);
-        for _ in 0..len {
-            vec.push(<T as ViaFfi>::try_read(buf)?)
-        }
-        Ok(vec)
+        <T as ViaFfi>::try_read_items(buf, len)
     }
 }
 
+/// Lower a `bytes` value, for passing via the FFI.
+///
+/// In rust, `bytes` is a `Vec<u8>` and so it can't have a `ViaFfi` implementation of its own
+/// that is distinct from `sequence<u8>`. The generated scaffolding uses this helper to pass
+/// the bytes as the raw contents of a `RustBuffer`, without copying them and without a length
+/// prefix. When serialized in a buffer, bytes are written like any other `Vec<u8>`, as an `i32`
+/// length followed by the bytes themselves.
+pub fn lower_bytes(v: Vec<u8>) -> RustBuffer {
+    RustBuffer::from_vec(v)
+}
+
+/// Lift a `bytes` value that was passed via the FFI, as the raw contents of a `RustBuffer`.
+///
+/// This is the counterpart of [`lower_bytes`]. It can't actually fail, but is fallible
+/// for consistency with [`ViaFfi::try_lift`].
+pub fn try_lift_bytes(v: RustBuffer) -> Result<Vec<u8>> {
+    Ok(v.destroy_into_vec())
+}
+
 /// Support for associative arrays via the FFI.
-/// Note that because of webidl limitations,
-/// the key must always be of the String type.
+/// The keys can be strings, integers or enums without associated data.
 ///
 /// HashMaps are currently always passed by serializing to a buffer.
-/// We write a `i32` entries count followed by each entry (string
-/// key followed by the value) in turn.
+/// We write a `i32` entries count followed by each entry (key
+/// followed by the value) in turn.
 /// (It's a signed type due to limits of the JVM).
-unsafe impl<V: ViaFfi> ViaFfi for HashMap<String, V> {
+unsafe impl<K: ViaFfi + Hash + Eq, V: ViaFfi> ViaFfi for HashMap<K, V> {
     type FfiType = RustBuffer;
 
     fn lower(self) -> Self::FfiType {
@@ -380,24 +572,181 @@ unsafe impl<V: ViaFfi> ViaFfi for HashMap<String, V> {
     }
 
     fn write<B: BufMut>(&self, buf: &mut B) {
-        // TODO: would be nice not to panic here :-/
-        let len = i32::try_from(self.len()).unwrap();
-        buf.put_i32(len); // We limit HashMaps to i32::MAX entries
-        for (key, value) in self.iter() {
-            ViaFfi::write(key, buf);
-            ViaFfi::write(value, buf);
-        }
+        write_map_entries(self.len(), self.iter(), buf)
     }
 
     fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
-        check_remaining(buf, 4)?;
-        let len = usize::try_from(buf.get_i32())?;
+        let len = read_map_len(buf)?;
         let mut map = HashMap::with_capacity(len);
         for _ in 0..len {
-            let key = String::try_read(buf)?;
+            let key = <K as ViaFfi>::try_read(buf)?;
             let value = <V as ViaFfi>::try_read(buf)?;
             map.insert(key, value);
         }
         Ok(map)
     }
 }
+
+/// BTreeMaps are passed exactly like HashMaps, but their entries are written in order of their keys.
+/// Use one where the order matters, since the foreign-language bindings preserve it where they can.
+unsafe impl<K: ViaFfi + Ord, V: ViaFfi> ViaFfi for BTreeMap<K, V> {
+    type FfiType = RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        try_lift_from_buffer(v)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        write_map_entries(self.len(), self.iter(), buf)
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        let len = read_map_len(buf)?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            let key = <K as ViaFfi>::try_read(buf)?;
+            let value = <V as ViaFfi>::try_read(buf)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+fn write_map_entries<'a, K: ViaFfi + 'a, V: ViaFfi + 'a, B: BufMut>(
+    len: usize,
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    buf: &mut B,
+) {
+    // TODO: would be nice not to panic here :-/
+    let len = i32::try_from(len).unwrap();
+    buf.put_i32(len); // We limit maps to i32::MAX entries
+    for (key, value) in entries {
+        ViaFfi::write(key, buf);
+        ViaFfi::write(value, buf);
+    }
+}
+
+fn read_map_len<B: Buf>(buf: &mut B) -> Result<usize> {
+    check_remaining(buf, 4)?;
+    Ok(usize::try_from(buf.get_i32())?)
+}
+
+/// Support for passing objects via the FFI.
+///
+/// Objects are passed as a `u64` handle into the handle map that the generated scaffolding keeps
+/// them in. Lowering an object inserts a new reference to it into the map, which belongs to the
+/// foreign-language code until it frees the handle. Lifting an object looks it up in the map
+/// without removing it, so rust gets its own reference to the same object and the foreign-language
+/// code can keep using it.
+unsafe impl<T: FfiObject> ViaFfi for Arc<T> {
+    type FfiType = u64;
+
+    fn lower(self) -> Self::FfiType {
+        T::handle_map().insert_arc(self).into_u64()
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self> {
+        Ok(T::handle_map().get_u64(v)?)
+    }
+
+    fn write<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u64(ViaFfi::lower(Arc::clone(self)));
+    }
+
+    fn try_read<B: Buf>(buf: &mut B) -> Result<Self> {
+        check_remaining(buf, 8)?;
+        <Self as ViaFfi>::try_lift(buf.get_u64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_wire_format() {
+        let half_second = Duration::from_millis(500);
+        let cases = vec![
+            (SystemTime::UNIX_EPOCH, 0i64, 0u32),
+            (SystemTime::UNIX_EPOCH + half_second, 0, 500_000_000),
+            (SystemTime::UNIX_EPOCH - half_second, -1, 500_000_000),
+            (SystemTime::UNIX_EPOCH - Duration::from_secs(2), -2, 0),
+        ];
+        for (time, seconds, nanos) in cases {
+            let mut buf = Vec::new();
+            ViaFfi::write(&time, &mut buf);
+            let mut expected = Vec::new();
+            expected.put_i64(seconds);
+            expected.put_u32(nanos);
+            assert_eq!(buf, expected);
+            assert_eq!(SystemTime::try_read(&mut buf.as_slice()).unwrap(), time);
+        }
+    }
+
+    #[test]
+    fn test_timestamp_nanos_must_be_less_than_a_second() {
+        let mut buf = Vec::new();
+        buf.put_i64(0);
+        buf.put_u32(NANOS_PER_SEC);
+        assert!(SystemTime::try_read(&mut buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_byte_vecs_are_written_in_one_go() {
+        let mut buf = Vec::new();
+        ViaFfi::write(&vec![1u8, 2, 3], &mut buf);
+        assert_eq!(buf, vec![0, 0, 0, 3, 1, 2, 3]);
+        let v: Vec<u8> = try_lift_from_buffer(RustBuffer::from_vec(buf)).unwrap();
+        assert_eq!(v, vec![1, 2, 3]);
+        // The bytes must all be present.
+        let mut short: &[u8] = &[0, 0, 0, 3, 1, 2];
+        assert!(<Vec<u8> as ViaFfi>::try_read(&mut short).is_err());
+    }
+
+    #[test]
+    fn test_bytes_are_passed_as_raw_buffer() {
+        let rbuf = lower_bytes(vec![1u8, 2, 3]);
+        assert_eq!(rbuf.len(), 3);
+        assert_eq!(try_lift_bytes(rbuf).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_duration_round_trip() {
+        let duration = Duration::new(3, 250);
+        let mut buf = Vec::new();
+        ViaFfi::write(&duration, &mut buf);
+        assert_eq!(buf.len(), 12);
+        assert_eq!(Duration::try_read(&mut buf.as_slice()).unwrap(), duration);
+    }
+
+    #[test]
+    fn test_maps_with_integer_keys() {
+        let map: HashMap<u32, String> = vec![(1, "one".to_string()), (2, "two".to_string())]
+            .into_iter()
+            .collect();
+        let lifted: HashMap<u32, String> =
+            try_lift_from_buffer(lower_into_buffer(map.clone())).unwrap();
+        assert_eq!(lifted, map);
+    }
+
+    #[test]
+    fn test_btreemaps_are_written_in_key_order() {
+        let map: BTreeMap<i8, bool> = vec![(3, true), (-1, false), (2, true)]
+            .into_iter()
+            .collect();
+        let mut buf = Vec::new();
+        ViaFfi::write(&map, &mut buf);
+        assert_eq!(buf, vec![0, 0, 0, 3, 0xff, 0, 2, 1, 3, 1]);
+        assert_eq!(
+            BTreeMap::<i8, bool>::try_read(&mut buf.as_slice()).unwrap(),
+            map
+        );
+        // They're interchangeable with hashmaps.
+        let hashed = HashMap::<i8, bool>::try_read(&mut buf.as_slice()).unwrap();
+        assert_eq!(hashed.into_iter().collect::<BTreeMap<_, _>>(), map);
+    }
+}
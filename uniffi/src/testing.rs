@@ -36,14 +36,43 @@ lazy_static! {
 /// component is compiled and available for use and then executes the foreign language script,
 /// returning successfully iff the script exits successfully.
 pub fn run_foreign_language_testcase(pkg_dir: &str, idl_file: &str, test_file: &str) -> Result<()> {
+    run_foreign_language_testcase_with_idls(pkg_dir, &[idl_file], test_file)
+}
+
+/// Execute the given foreign-language script as part of a rust test suite, for a component
+/// that uses external types from other components.
+///
+/// The first of the given idl files is the component's own. Any others belong to components
+/// that define external types used by this one, which are also compiled and made available.
+pub fn run_foreign_language_testcase_with_idls(
+    pkg_dir: &str,
+    idl_files: &[&str],
+    test_file: &str,
+) -> Result<()> {
+    let (idl_file, external_idl_files) = match idl_files.split_first() {
+        Some(files) => files,
+        None => bail!("No idl file given for the component"),
+    };
     let cdylib_file = ensure_compiled_cdylib(pkg_dir)?;
+    for external_idl_file in external_idl_files {
+        // By convention the idl file lives in the `src` directory of its component crate.
+        let external_pkg_dir = Path::new(pkg_dir)
+            .join(external_idl_file)
+            .parent()
+            .and_then(Path::parent)
+            .ok_or_else(|| anyhow::anyhow!("IDL file has no crate directory"))?
+            .canonicalize()?
+            .to_string_lossy()
+            .into_owned();
+        ensure_compiled_cdylib(&external_pkg_dir)?;
+    }
     let out_dir = Path::new(cdylib_file.as_str())
         .parent()
         .ok_or_else(|| anyhow::anyhow!("Generated cdylib has no parent directory"))?
         .to_str()
         .unwrap();
     let _lock = UNIFFI_BINDGEN.lock();
-    run_uniffi_bindgen_test(out_dir, idl_file, test_file)?;
+    run_uniffi_bindgen_test(out_dir, idl_file, external_idl_files, test_file)?;
     Ok(())
 }
 
@@ -68,12 +97,17 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
     cmd.stdout(Stdio::piped());
     let mut child = cmd.spawn()?;
     let output = std::io::BufReader::new(child.stdout.take().unwrap());
-    // Build the crate, looking for any cdylibs that it might produce.
+    // Build the crate, looking for any cdylibs that it might produce. Components that use external
+    // types depend on the crates of other components, whose cdylibs get built too, so we only
+    // look at the ones from this crate.
+    let pkg_path = Path::new(pkg_dir).canonicalize()?;
     let cdylibs = Message::parse_stream(output)
         .filter_map(|message| match message {
             Err(e) => Some(Err(e.into())),
             Ok(Message::CompilerArtifact(artifact)) => {
-                if artifact.target.kind.iter().any(|item| item == "cdylib") {
+                if artifact.target.kind.iter().any(|item| item == "cdylib")
+                    && artifact.target.src_path.starts_with(&pkg_path)
+                {
                     Some(Ok(artifact))
                 } else {
                     None
@@ -117,10 +151,18 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
 /// on the `uniffi_bindgen` crate and execute its methods in-process. This is useful for folks
 /// who are working on uniffi itself and want to test out their changes to the bindings generator.
 #[cfg(not(feature = "builtin-bindgen"))]
-fn run_uniffi_bindgen_test(out_dir: &str, idl_file: &str, test_file: &str) -> Result<()> {
-    let status = Command::new("uniffi-bindgen")
-        .args(&["test", out_dir, idl_file, test_file])
-        .status()?;
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    idl_file: &str,
+    external_idl_files: &[&str],
+    test_file: &str,
+) -> Result<()> {
+    let mut cmd = Command::new("uniffi-bindgen");
+    cmd.args(&["test", out_dir, idl_file, test_file]);
+    for external_idl_file in external_idl_files {
+        cmd.args(&["--external-idl", external_idl_file]);
+    }
+    let status = cmd.status()?;
     if !status.success() {
         bail!("Error while running tests: {}",);
     }
@@ -128,6 +170,16 @@ fn run_uniffi_bindgen_test(out_dir: &str, idl_file: &str, test_file: &str) -> Re
 }
 
 #[cfg(feature = "builtin-bindgen")]
-fn run_uniffi_bindgen_test(out_dir: &str, idl_file: &str, test_file: &str) -> Result<()> {
-    uniffi_bindgen::run_tests(out_dir, idl_file, vec![test_file])
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    idl_file: &str,
+    external_idl_files: &[&str],
+    test_file: &str,
+) -> Result<()> {
+    uniffi_bindgen::run_tests(
+        out_dir,
+        idl_file,
+        external_idl_files.to_vec(),
+        vec![test_file],
+    )
 }
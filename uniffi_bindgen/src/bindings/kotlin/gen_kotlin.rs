@@ -6,6 +6,7 @@ use anyhow::Result;
 use askama::Template;
 use heck::{CamelCase, MixedCase, ShoutySnakeCase};
 
+use crate::bindings::LanguageConfig;
 use crate::interface::*;
 
 // Some config options for it the caller wants to customize the generated Kotlin.
@@ -13,12 +14,14 @@ use crate::interface::*;
 // sine the details of the underlying component are entirely determined by the `ComponentInterface`.
 pub struct Config {
     pub package_name: String,
+    pub bindings: LanguageConfig,
 }
 
 impl Config {
-    pub fn from(ci: &ComponentInterface) -> Self {
+    pub fn from(ci: &ComponentInterface, bindings: &LanguageConfig) -> Self {
         Config {
             package_name: format!("uniffi.{}", ci.namespace()),
+            bindings: bindings.clone(),
         }
     }
 }
@@ -56,15 +59,29 @@ mod filters {
             // These types need conversion, and special handling for lifting/lowering.
             Type::Boolean => "Boolean".to_string(),
             Type::String => "String".to_string(),
-            Type::Enum(name) | Type::Record(name) | Type::Object(name) | Type::Error(name) => {
-                class_name_kt(name)?
-            }
+            Type::Bytes => "ByteArray".to_string(),
+            Type::Timestamp => "java.time.Instant".to_string(),
+            Type::Duration => "java.time.Duration".to_string(),
+            Type::Enum(name)
+            | Type::Record(name)
+            | Type::Object(name)
+            | Type::Error(name)
+            | Type::CallbackInterface(name)
+            | Type::Union(name, _)
+            | Type::Custom(name, _)
+            | Type::External(name, _) => class_name_kt(name)?,
             Type::Optional(t) => format!("{}?", type_kt(t)?),
             Type::Sequence(t) => format!("List<{}>", type_kt(t)?),
-            Type::Map(t) => format!("Map<String, {}>", type_kt(t)?),
+            Type::Iterator(t) => format!("RustIterator<{}>", type_kt(t)?),
+            Type::Map(k, v) => format!("Map<{}, {}>", type_kt(k)?, type_kt(v)?),
         })
     }
 
+    /// Get the Kotlin syntax for the low-level `FFIType` that a given api-level `Type` lowers into.
+    pub fn ffi_type_kt(type_: &Type) -> Result<String, askama::Error> {
+        type_ffi(&FFIType::from(type_))
+    }
+
     /// Get the Kotlin syntax for representing a given low-level `FFIType`.
     pub fn type_ffi(type_: &FFIType) -> Result<String, askama::Error> {
         Ok(match type_ {
@@ -77,10 +94,10 @@ mod filters {
             FFIType::Int64 | FFIType::UInt64 => "Long".to_string(),
             FFIType::Float32 => "Float".to_string(),
             FFIType::Float64 => "Double".to_string(),
-            FFIType::RustCString => "Pointer".to_string(),
             FFIType::RustBuffer => "RustBuffer.ByValue".to_string(),
-            FFIType::RustError => "RustError".to_string(),
             FFIType::ForeignBytes => "ForeignBytes.ByValue".to_string(),
+            FFIType::ForeignCallback => "ForeignCallback".to_string(),
+            FFIType::FutureCallback => "FutureCallback".to_string(),
         })
     }
 
@@ -104,6 +121,28 @@ mod filters {
         Ok(nm.to_string().to_shouty_snake_case())
     }
 
+    /// Get the Kotlin syntax for a literal value of the given type, e.g. a default argument value.
+    pub fn literal_kt(literal: &Literal, type_: &Type) -> Result<String, askama::Error> {
+        Ok(match (literal, type_) {
+            (_, Type::Optional(t)) if !matches!(literal, Literal::Null) => literal_kt(literal, t)?,
+            (Literal::Boolean(v), _) => v.to_string(),
+            // Escape anything that Kotlin would treat as an escape sequence or a string template.
+            (Literal::String(s), _) => {
+                format!("\"{}\"", s.replace('\\', "\\\\").replace('$', "\\$"))
+            }
+            (Literal::Int(v), _) => v.to_string(),
+            (Literal::UInt(v), _) => format!("{}u", v),
+            (Literal::Float(v), Type::Float32) => format!("{}f", v),
+            (Literal::Float(v), _) => v.clone(),
+            (Literal::Enum(v), _) => {
+                format!("{}.{}", type_kt(type_)?, enum_variant_kt(v)?)
+            }
+            (Literal::EmptySequence, _) => "listOf()".into(),
+            (Literal::EmptyMap, _) => "mapOf()".into(),
+            (Literal::Null, _) => "null".into(),
+        })
+    }
+
     /// Get a Kotlin expression for lowering a value into something we can pass over the FFI.
     ///
     /// Where possible, this delegates to a `lower()` method on the type itself, but special
@@ -111,9 +150,21 @@ mod filters {
     pub fn lower_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         let nm = var_name_kt(nm)?;
         Ok(match type_ {
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(..)
+            | Type::Custom(..)
+            | Type::External(..) => {
                 format!("lower{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
+            Type::CallbackInterface(name) => format!(
+                "CallbackInterface{}Internals.lower({})",
+                class_name_kt(name)?,
+                nm,
+            ),
             _ => format!("{}.lower()", nm),
         })
     }
@@ -129,12 +180,25 @@ mod filters {
     ) -> Result<String, askama::Error> {
         let nm = var_name_kt(nm)?;
         Ok(match type_ {
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => format!(
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(..)
+            | Type::Custom(..)
+            | Type::External(..) => format!(
                 "write{}({}, {})",
                 class_name_kt(&type_.canonical_name())?,
                 nm,
                 target,
             ),
+            Type::CallbackInterface(name) => format!(
+                "CallbackInterface{}Internals.write({}, {})",
+                class_name_kt(name)?,
+                nm,
+                target,
+            ),
             _ => format!("{}.write({})", nm, target),
         })
     }
@@ -146,7 +210,15 @@ mod filters {
     pub fn lift_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         let nm = nm.to_string();
         Ok(match type_ {
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(..)
+            | Type::Custom(..)
+            | Type::External(..)
+            | Type::Iterator(_) => {
                 format!("lift{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
             _ => format!("{}.lift({})", type_kt(type_)?, nm),
@@ -160,10 +232,44 @@ mod filters {
     pub fn read_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         let nm = nm.to_string();
         Ok(match type_ {
-            Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
+            Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(..)
+            | Type::Custom(..)
+            | Type::External(..) => {
                 format!("read{}({})", class_name_kt(&type_.canonical_name())?, nm,)
             }
             _ => format!("{}.read({})", type_kt(type_)?, nm),
         })
     }
+
+    /// Format a doc comment from the IDL as a KDoc comment, indented by the given number of spaces.
+    pub fn docstring_kt(docstring: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let indent = " ".repeat(*spaces);
+        // A stray `*/` would end the comment early.
+        let docstring = docstring.replace("*/", "*&#47;");
+        let mut kdoc = format!("{}/**\n", indent);
+        for line in docstring.lines() {
+            kdoc.push_str(format!("{} * {}", indent, line).trim_end());
+            kdoc.push('\n');
+        }
+        kdoc.push_str(&format!("{} */", indent));
+        Ok(kdoc)
+    }
+
+    /// Get the annotation for a `[Deprecated]` definition, indented by the given number of spaces.
+    pub fn deprecated_kt(message: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$");
+        Ok(format!(
+            "{}@Deprecated(\"{}\")",
+            " ".repeat(*spaces),
+            message
+        ))
+    }
 }
@@ -16,18 +16,20 @@ pub mod gen_kotlin;
 pub use gen_kotlin::{Config, KotlinWrapper};
 
 use super::super::interface::ComponentInterface;
+use super::LanguageConfig;
 
 pub fn write_bindings(
     ci: &ComponentInterface,
+    bindings_config: &LanguageConfig,
     out_dir: &Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let config = Config::from(&ci);
+    let config = Config::from(ci, bindings_config);
     let mut kt_file = full_bindings_path(&config, out_dir)?;
     std::fs::create_dir_all(&kt_file)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
     let mut f = File::create(&kt_file).context("Failed to create .kt file for bindings")?;
-    write!(f, "{}", generate_bindings(config, &ci)?)?;
+    write!(f, "{}", generate_bindings(config, ci)?)?;
     if try_format_code {
         if let Err(e) = Command::new("ktlint")
             .arg("-F")
@@ -52,7 +54,7 @@ fn full_bindings_path(config: &Config, out_dir: &Path) -> Result<PathBuf> {
 // Generate kotlin bindings for the given ComponentInterface, as a string.
 pub fn generate_bindings(config: Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
-    KotlinWrapper::new(config, &ci)
+    KotlinWrapper::new(config, ci)
         .render()
         .map_err(|_| anyhow::anyhow!("failed to render kotlin bindings"))
 }
@@ -60,16 +62,26 @@ pub fn generate_bindings(config: Config, ci: &ComponentInterface) -> Result<Stri
 /// Generate kotlin bindings for the given namespace, then use the kotlin
 /// command-line tools to compile them into a .jar file.
 pub fn compile_bindings(ci: &ComponentInterface, out_dir: &Path) -> Result<()> {
-    let config = Config::from(&ci);
+    // We only need the package name here, which doesn't depend on the bindings config.
+    let config = Config::from(ci, &LanguageConfig::default());
     let mut kt_file = full_bindings_path(&config, out_dir)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
     let mut jar_file = PathBuf::from(out_dir);
     jar_file.push(format!("{}.jar", ci.namespace()));
+    // Bindings that use external types call the `internal` helpers from the bindings of the
+    // components that define them, so they all have to be compiled together.
+    let mut external_kt_files = Vec::new();
+    for namespace in ci.iter_external_namespaces() {
+        let mut external_kt_file = PathBuf::from(out_dir);
+        external_kt_file.push(format!("uniffi/{}/{}.kt", namespace, namespace));
+        external_kt_files.push(external_kt_file);
+    }
     let status = Command::new("kotlinc")
         .arg("-Xopt-in=kotlin.ExperimentalUnsignedTypes")
         .arg("-classpath")
         .arg(env::var("CLASSPATH").unwrap_or_else(|_| "".to_string()))
         .arg(&kt_file)
+        .args(&external_kt_files)
         .arg("-d")
         .arg(jar_file)
         .spawn()
@@ -0,0 +1,56 @@
+// Helpers for calling `[Async]` functions.
+//
+// The FFI function for an async function starts a rust future and returns straight away. When the future
+// completes, rust calls a single `FutureCallback` from one of its own threads, passing back the handle
+// under which we stored the suspended call, which is then resumed with the result.
+
+internal interface FutureCallback : com.sun.jna.Callback {
+    public fun invoke(callbackData: Long, code: Byte, buf: RustBuffer.ByValue)
+}
+
+// An async call that's waiting for rust to report its outcome.
+internal class PendingAsyncCall<E: Exception>(
+    private val continuation: Continuation<RustBuffer.ByValue>,
+    private val errorHandler: CallStatusErrorHandler<E>
+) {
+    fun complete(code: Byte, buf: RustBuffer.ByValue) {
+        // JNA only lends us the struct for the duration of the callback, so take a copy.
+        val status = RustCallStatus()
+        status.code = code
+        status.error_buf.capacity = buf.capacity
+        status.error_buf.len = buf.len
+        status.error_buf.data = buf.data
+        continuation.resumeWith(runCatching {
+            checkCallStatus(errorHandler, status)
+            status.error_buf
+        })
+    }
+}
+
+private val pendingAsyncCalls = ConcurrentHandleMap<PendingAsyncCall<*>>()
+
+internal object AsyncCallCompletion : FutureCallback {
+    override fun invoke(callbackData: Long, code: Byte, buf: RustBuffer.ByValue) {
+        pendingAsyncCalls.remove(callbackData)?.complete(code, buf)
+    }
+}
+
+// Call an async rust function, suspending until it completes. On success this gives the buffer
+// holding the serialized return value, which the caller must lift or free.
+private suspend inline fun <E: Exception> rustCallAsyncWithError(
+    errorHandler: CallStatusErrorHandler<E>,
+    crossinline callback: (FutureCallback, Long, RustCallStatus) -> Unit
+): RustBuffer.ByValue = suspendCoroutine { continuation ->
+    val callbackData = pendingAsyncCalls.insert(PendingAsyncCall(continuation, errorHandler))
+    try {
+        rustCall { status -> callback(AsyncCallCompletion, callbackData, status) }
+    } catch (e: Exception) {
+        // Rust never got as far as starting the future, so it won't call us back.
+        pendingAsyncCalls.remove(callbackData)
+        throw e
+    }
+}
+
+private suspend inline fun rustCallAsync(
+    crossinline callback: (FutureCallback, Long, RustCallStatus) -> Unit
+): RustBuffer.ByValue = rustCallAsyncWithError(NullCallStatusErrorHandler, callback)
@@ -0,0 +1,16 @@
+{#
+// Interfaces that other interfaces inherit from are abstract classes, whose methods are implemented
+// by the classes for the inheriting interfaces.
+#}
+{%- call kt::docstring(obj, 0) %}
+abstract class {{ obj.name()|class_name_kt }}(
+    handle: Long
+) : {% match obj.parent() %}{% when Some with (parent) %}{{ parent|class_name_kt }}(handle){% else %}FFIObject(AtomicLong(handle)){% endmatch %} {
+    {%- for meth in obj.methods() %}
+    {%- if meth.inherited_from().is_none() %}
+    {%- call kt::docstring(meth, 4) %}
+    {%- call kt::deprecated(meth, 4) %}
+    abstract fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}){% match meth.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt }}{% else %}{% endmatch %}
+    {%- endif %}
+    {%- endfor %}
+}
@@ -0,0 +1,32 @@
+// Helpers for foreign-language implementations of callback interfaces.
+//
+// Objects implementing a callback interface are kept in a handle map, and only the handle is passed to rust.
+// When rust wants to call a method on the object, it calls a single `ForeignCallback` registered for the
+// callback interface, which looks up the object by handle and dispatches on the given method index.
+
+internal interface ForeignCallback : com.sun.jna.Callback {
+    public fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue, outBuf: RustBuffer.ByReference): Int
+}
+
+// Magic number for the Rust proxy to call using the same mechanism as every other method,
+// to free the callback once it's dropped by Rust.
+internal const val IDX_CALLBACK_FREE = 0
+
+internal abstract class CallbackInternals<CallbackInterface>(
+    val foreignCallback: ForeignCallback
+) {
+    val handleMap = ConcurrentHandleMap<CallbackInterface>()
+
+    // Registers the foreign callback with the Rust side.
+    // This method is generated for each callback interface.
+    abstract fun register(lib: _UniFFILib)
+
+    fun drop(handle: Long) {
+        handleMap.remove(handle)
+    }
+
+    fun lower(v: CallbackInterface): Long = handleMap.insert(v)
+
+    fun write(v: CallbackInterface, buf: RustBufferBuilder) =
+        buf.putLong(lower(v))
+}
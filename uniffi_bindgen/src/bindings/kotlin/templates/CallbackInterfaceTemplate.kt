@@ -0,0 +1,78 @@
+{% let type_name = cbi.name()|class_name_kt %}
+{% let foreign_callback = format!("ForeignCallback{}", type_name) %}
+{% let internals = format!("CallbackInterface{}Internals", type_name) %}
+
+// Declaration and helpers for the {{ type_name }} callback interface.
+
+public interface {{ type_name }} {
+    {% for meth in cbi.methods() -%}
+    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %})
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %}: {{ return_type|type_kt -}}
+    {%- else -%}
+    {%- endmatch %}
+    {% endfor %}
+}
+
+// The ForeignCallback that is passed to Rust.
+internal class {{ foreign_callback }} : ForeignCallback {
+    @Suppress("TooGenericExceptionCaught")
+    override fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue, outBuf: RustBuffer.ByReference): Int {
+        return try {
+            val cb = {{ internals }}.handleMap.get(handle) ?: throw InternalException("No callback in handlemap; this is a uniffi bug")
+            when (method) {
+                IDX_CALLBACK_FREE -> {
+                    {{ internals }}.drop(handle)
+                    RustBuffer.free(args)
+                }
+                {% for meth in cbi.methods() -%}
+                {% let method_name = format!("invoke_{}", meth.name())|fn_name_kt -%}
+                {{ loop.index }} -> outBuf.setValue(this.{{ method_name }}(cb, args))
+                {% endfor %}
+                // This should never happen, because rust only ever calls the methods declared in the IDL.
+                else -> throw InternalException("Unknown method index $method on callback interface {{ type_name }}")
+            }
+            0
+        } catch (e: Throwable) {
+            // There is no way to surface the exception to the Rust caller, which will panic.
+            -1
+        }
+    }
+
+    {% for meth in cbi.methods() -%}
+    {% let method_name = format!("invoke_{}", meth.name())|fn_name_kt -%}
+    private fun {{ method_name }}(kotlinCallbackInterface: {{ type_name }}, args: RustBuffer.ByValue): RustBuffer.ByValue =
+        try {
+            {%- if meth.arguments().len() != 0 %}
+            val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a uniffi bug")
+            {%- endif %}
+            kotlinCallbackInterface.{{ meth.name()|fn_name_kt }}(
+                {%- for arg in meth.arguments() %}
+                {{ "buf"|read_kt(arg.type_()) }}{% if !loop.last %},{% endif %}
+                {%- endfor %}
+            )
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            .let { rval ->
+                lowerIntoRustBuffer(rval) { v, buf -> {{ "v"|write_kt("buf", return_type) }} }
+            }
+            {%- else %}
+            .let { RustBuffer.ByValue() }
+            {%- endmatch %}
+        } finally {
+            RustBuffer.free(args)
+        }
+
+    {% endfor %}
+}
+
+// Holds the handle map for {{ type_name }} implementations, and registers the ForeignCallback with Rust.
+internal object {{ internals }}: CallbackInternals<{{ type_name }}>(
+    foreignCallback = {{ foreign_callback }}()
+) {
+    override fun register(lib: _UniFFILib) {
+        rustCall { status ->
+            lib.{{ cbi.ffi_init_callback().name() }}(this.foreignCallback, status)
+        }
+    }
+}
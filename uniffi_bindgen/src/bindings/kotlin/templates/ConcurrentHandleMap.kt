@@ -0,0 +1,24 @@
+// A map where inserting, getting and removing data is synchronized with a lock.
+internal class ConcurrentHandleMap<T>(
+    private val leftMap: MutableMap<Long, T> = mutableMapOf()
+) {
+    private val lock = ReentrantLock()
+    private val currentHandle = AtomicLong(0L)
+    private val stride = 1L
+
+    fun insert(obj: T): Long =
+        lock.withLock {
+            currentHandle.getAndAdd(stride).also { handle ->
+                leftMap[handle] = obj
+            }
+        }
+
+    fun get(handle: Long) = lock.withLock {
+        leftMap[handle]
+    }
+
+    fun remove(handle: Long): T? =
+        lock.withLock {
+            leftMap.remove(handle)
+        }
+}
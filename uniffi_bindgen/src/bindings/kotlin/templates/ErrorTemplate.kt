@@ -1,148 +1,121 @@
 {#-
-// Here we define error conversions from native references to Kotlin exceptions.
-// This is done by defining a RustErrorReference interface, where any implementers of the interface
-// can be converted into a native reference for an error to flow across the FFI.
-
-// A generic RustError is definied as a super class for other errors. This includes some common behaviour
-// across the errors, can also serve as an error in case the cause of the error is unknown
+// Here we define the status of a call into rust, and the exceptions that it can be converted into.
+// Every FFI function takes a `RustCallStatus` as its final argument; if the call fails then the status
+// holds a serialized error in `error_buf`, which we lift into the corresponding Kotlin exception.
 -#}
 
-interface RustErrorReference : Structure.ByReference {
-    fun isFailure(): Boolean
-    fun<E: Exception> intoException(): E
-    fun ensureConsumed()
-    fun getMessage(): String?
-    fun consumeErrorMessage(): String
-}
-
-@Structure.FieldOrder("code", "message")
-internal open class RustError : Structure() {
-   open class ByReference: RustError(), RustErrorReference
-
-    @JvmField var code: Int = 0
-    @JvmField var message: Pointer? = null
+@Structure.FieldOrder("code", "error_buf")
+internal open class RustCallStatus : Structure() {
+    @JvmField var code: Byte = 0
+    @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
 
-    /**
-     * Does this represent success?
-     */
     fun isSuccess(): Boolean {
-        return code == 0
-    }
-
-    /**
-     * Does this represent failure?
-     */
-    fun isFailure(): Boolean {
-        return code != 0
-    }
-
-    @Synchronized
-    fun ensureConsumed() {
-        if (this.message != null) {
-            rustCall(InternalError.ByReference()) { err ->
-                _UniFFILib.INSTANCE.{{ ci.ffi_string_free().name() }}(this.message!!, err)
-             }
-            this.message = null
-        }
-    }
-
-    /**
-     * Get the error message or null if there is none.
-     */
-    fun getMessage(): String? {
-        return this.message?.getString(0, "utf8")
+        return code == 0.toByte()
     }
 
-    /**
-     * Get and consume the error message, or null if there is none.
-     */
-    @Synchronized
-    fun consumeErrorMessage(): String {
-        val result = this.getMessage()
-        if (this.message != null) {
-            this.ensureConsumed()
-        }
-        if (result == null) {
-            throw NullPointerException("consumeErrorMessage called with null message!")
-        }
-        return result
+    fun isError(): Boolean {
+        return code == 1.toByte()
     }
 
-    @Suppress("ReturnCount", "TooGenericExceptionThrown")
-    open fun<E: Exception> intoException(): E {
-        if (!isFailure()) {
-            // It's probably a bad idea to throw here! We're probably leaking something if this is
-            // ever hit! (But we shouldn't ever hit it?)
-            throw RuntimeException("[Bug] intoException called on non-failure!")
-        }
-        this.consumeErrorMessage()
-        throw RuntimeException("Generic errors are not implemented yet")
+    fun isPanic(): Boolean {
+        return code == 2.toByte()
     }
 }
 
-internal open class InternalError : RustError() {
-    class ByReference: InternalError(), RustErrorReference
+class InternalException(message: String) : Exception(message)
 
-    @Suppress("ReturnCount", "TooGenericExceptionThrown", "UNCHECKED_CAST")
-    override fun<E: Exception> intoException(): E {
-        if (!isFailure()) {
-            // It's probably a bad idea to throw here! We're probably leaking something if this is
-            // ever hit! (But we shouldn't ever hit it?)
-            throw RuntimeException("[Bug] intoException called on non-failure!")
-        }
-        val message = this.consumeErrorMessage()
-        return InternalException(message) as E
-    }
+// Each top-level error class has a companion object that can lift the error from the call status's rust buffer
+interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: RustBuffer.ByValue): E
 }
 
-class InternalException(message: String) : Exception(message)
-
 {%- for e in ci.iter_error_definitions() %}
-internal open class {{e.name()}} : RustError() {
-    class ByReference: {{e.name()}}(), RustErrorReference
 
-    @Suppress("ReturnCount", "TooGenericExceptionThrown", "UNCHECKED_CAST")
-    override fun<E: Exception> intoException(): E {
-        if (!isFailure()) {
-            // It's probably a bad idea to throw here! We're probably leaking something if this is
-            // ever hit! (But we shouldn't ever hit it?)
-            throw RuntimeException("[Bug] intoException called on non-failure!")
+open class {{e.name()|class_name_kt}}Exception(message: String) : Exception(message) {
+    // Each variant is a nested class
+    {% for variant in e.variants() -%}
+    {% if e.is_flat() -%}
+    class {{ variant.name()|class_name_kt }}(message: String) : {{e.name()|class_name_kt}}Exception(message)
+    {% else if variant.has_fields() -%}
+    class {{ variant.name()|class_name_kt }}(
+        {% for field in variant.fields() -%}
+        val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt}}{% if loop.last %}{% else %}, {% endif %}
+        {% endfor -%}
+    ) : {{e.name()|class_name_kt}}Exception("{% for field in variant.fields() %}{{ field.name()|var_name_kt }}=${{ field.name()|var_name_kt }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %}")
+    {% else -%}
+    class {{ variant.name()|class_name_kt }}() : {{e.name()|class_name_kt}}Exception("")
+    {% endif -%}
+    {% endfor %}
+
+    companion object ErrorHandler : CallStatusErrorHandler<{{ e.name()|class_name_kt }}Exception> {
+        override fun lift(error_buf: RustBuffer.ByValue): {{ e.name()|class_name_kt }}Exception {
+            return liftFromRustBuffer(error_buf) { buf -> read(buf) }
         }
-        val message = this.consumeErrorMessage()
-        when (code) {
-            {% for value in e.values() -%}
-            {{loop.index}} -> return {{e.name()}}Exception.{{value}}(message) as E
-            {% endfor -%}
-            else -> throw RuntimeException("Invalid error received...")
+
+        fun read(error_buf: ByteBuffer): {{ e.name()|class_name_kt }}Exception {
+            return when(error_buf.getInt()) {
+                {%- for variant in e.variants() %}
+                {%- if e.is_flat() %}
+                {{ loop.index }} -> {{ e.name()|class_name_kt }}Exception.{{ variant.name()|class_name_kt }}(String.read(error_buf))
+                {%- else if variant.has_fields() %}
+                {{ loop.index }} -> {{ e.name()|class_name_kt }}Exception.{{ variant.name()|class_name_kt }}(
+                    {% for field in variant.fields() -%}
+                    {{ "error_buf"|read_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+                    {% endfor -%}
+                )
+                {%- else %}
+                {{ loop.index }} -> {{ e.name()|class_name_kt }}Exception.{{ variant.name()|class_name_kt }}()
+                {%- endif %}
+                {%- endfor %}
+                else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+            }
         }
     }
 }
-
-open class {{e.name()}}Exception(message: String) : Exception(message) {
-    {% for value in e.values() -%}
-    class {{value}}(msg: String) : {{e.name()}}Exception(msg)
-    {% endfor %}
-}
-
 {% endfor %}
 
-// Helpers for calling Rust with errors:
+// Helpers for calling Rust
 // In practice we usually need to be synchronized to call this safely, so it doesn't
 // synchronize itself
-private inline fun <U, E: RustErrorReference> nullableRustCall(callback: (E) -> U?, err: E): U? {
-    try {
-        val ret = callback(err)
-        if (err.isFailure()) {
-            throw err.intoException()
+
+// Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
+private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+    val status = RustCallStatus()
+    val return_value = callback(status)
+    checkCallStatus(errorHandler, status)
+    return return_value
+}
+
+// Throw the appropriate exception if a call into rust didn't succeed.
+private fun <E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: RustCallStatus) {
+    if (status.isSuccess()) {
+        return
+    } else if (status.isError()) {
+        throw errorHandler.lift(status.error_buf)
+    } else if (status.isPanic()) {
+        // When the rust code sees a panic, it sends back the message as the raw
+        // UTF-8 bytes of the error buffer.
+        try {
+            val byteArr = ByteArray(status.error_buf.len)
+            status.error_buf.asByteBuffer()?.get(byteArr)
+            throw InternalException(byteArr.toString(Charsets.UTF_8))
+        } finally {
+            RustBuffer.free(status.error_buf)
         }
-        return ret
-    } finally {
-        // This only matters if `callback` throws (or does a non-local return, which
-        // we currently don't do)
-        err.ensureConsumed()
+    } else {
+        throw InternalException("Unknown rust call status: ${status.code}")
+    }
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: RustBuffer.ByValue): InternalException {
+        RustBuffer.free(error_buf)
+        return InternalException("Unexpected CALL_ERROR")
     }
 }
 
-private inline fun <U, E: RustErrorReference> rustCall(err: E, callback: (E) -> U?): U {
-    return nullableRustCall(callback, err)!!
+// Call a rust function that returns a plain value
+private inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback)
 }
@@ -18,7 +18,12 @@ inline fun <reified Lib : Library> loadIndirect(
 
 internal interface _UniFFILib : Library {
     companion object {
-        internal var INSTANCE: _UniFFILib = loadIndirect(componentName = "{{ ci.namespace() }}")
+        internal var INSTANCE: _UniFFILib = loadIndirect<_UniFFILib>(componentName = "{{ ci.namespace() }}")
+            {%- for cbi in ci.iter_callback_interface_definitions() %}
+            .also { lib: _UniFFILib ->
+                CallbackInterface{{ cbi.name()|class_name_kt }}Internals.register(lib)
+            }
+            {%- endfor %}
     }
 
     {% for func in ci.iter_ffi_function_definitions() -%}
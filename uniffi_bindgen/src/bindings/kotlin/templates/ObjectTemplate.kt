@@ -1,11 +1,15 @@
+{%- call kt::docstring(obj, 0) %}
 class {{ obj.name()|class_name_kt }}(
     handle: Long
-) : FFIObject(AtomicLong(handle)) {
+) : {% match obj.parent() %}{% when Some with (parent) %}{{ parent|class_name_kt }}(handle){% else %}FFIObject(AtomicLong(handle)){% endmatch %} {
 
-    {%- for cons in obj.constructors() %}
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    {%- call kt::deprecated(cons, 4) %}
     constructor({% call kt::arg_list_decl(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
-    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
 
     /**
      * Disconnect the object from the underlying Rust object.
@@ -19,8 +23,8 @@ class {{ obj.name()|class_name_kt }}(
         try {
             callWithHandle {
                 super.destroy() // poison the handle so no-one else can use it before we tell rust.
-                rustCall(InternalError.ByReference()) { err ->
-                    _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(it, err)
+                rustCall { status ->
+                    _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(it, status)
                 }
             }
         } catch (e: IllegalStateException) {
@@ -29,10 +33,20 @@ class {{ obj.name()|class_name_kt }}(
     }
 
     {% for meth in obj.methods() -%}
-    {%- match meth.return_type() -%}
+    {%- if meth.is_async() && !meth.is_static() %}
+    {%- call kt::docstring(meth, 4) %}
+    {%- call kt::deprecated(meth, 4) %}
+    suspend fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}){% match meth.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt }}{% else %}{% endmatch %} =
+        callWithHandle {
+            {% call kt::to_async_ffi_call("it", meth) %}
+        }
+    {% else if !meth.is_static() %}
+    {%- call kt::docstring(meth, 4) %}
+    {%- call kt::deprecated(meth, 4) %}
+    {% match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}): {{ return_type|type_kt }} =
+    {% if meth.inherited_from().is_some() %}override {% endif %}fun {{ meth.name()|fn_name_kt }}({% call kt::method_arg_list_decl(meth) %}): {{ return_type|type_kt }} =
         callWithHandle {
             {% call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
@@ -40,10 +54,79 @@ class {{ obj.name()|class_name_kt }}(
         }
     
     {%- when None -%}
-    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}) =
+    {% if meth.inherited_from().is_some() %}override {% endif %}fun {{ meth.name()|fn_name_kt }}({% call kt::method_arg_list_decl(meth) %}) =
         callWithHandle {
             {% call kt::to_ffi_call_with_prefix("it", meth) %} 
         }
     {% endmatch %}
+    {%- endif %}
     {% endfor %}
+
+    {% for prop in obj.properties() -%}
+    {% let getter = prop.getter() -%}
+    {%- if prop.is_readonly() %}val{% else %}var{% endif %} {{ prop.name()|var_name_kt }}: {{ prop.type_()|type_kt }}
+        get() =
+            callWithHandle {
+                {% call kt::to_ffi_call_with_prefix("it", getter) %}
+            }.let {
+                {{ "it"|lift_kt(prop.type_()) }}
+            }
+        {%- match prop.setter() %}
+        {%- when Some with (setter) %}
+        set(value) {
+            callWithHandle {
+                {% call kt::to_ffi_call_with_prefix("it", setter) %}
+            }
+        }
+        {%- when None %}
+        {%- endmatch %}
+
+    {% endfor -%}
+
+    // Rust gets its own reference to the object, so this one stays usable after passing it.
+    internal fun lower(): Long = callWithHandle { it }
+
+    internal fun write(buf: RustBufferBuilder) {
+        buf.putLong(lower())
+    }
+
+    companion object {
+        {%- for c in obj.constants() %}
+        const val {{ c.name()|enum_variant_kt }}: {{ c.type_()|type_kt }} = {{ c.value()|literal_kt(c.type_()) }}
+        {%- endfor %}
+
+        {%- for cons in obj.alternate_constructors() %}
+        {%- call kt::deprecated(cons, 8) %}
+        fun {{ cons.name()|fn_name_kt }}({% call kt::arg_list_decl(cons) %}): {{ obj.name()|class_name_kt }} =
+            {{ obj.name()|class_name_kt }}({% call kt::to_ffi_call(cons) %})
+        {% endfor %}
+        {%- for meth in obj.methods() %}
+        {%- if meth.is_async() && meth.is_static() %}
+        {%- call kt::docstring(meth, 8) %}
+        {%- call kt::deprecated(meth, 8) %}
+        suspend fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}){% match meth.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt }}{% else %}{% endmatch %} =
+            {% call kt::to_async_ffi_call("", meth) %}
+        {% else if meth.is_static() %}
+        {%- call kt::docstring(meth, 8) %}
+        {%- call kt::deprecated(meth, 8) %}
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}): {{ return_type|type_kt }} {
+            val _retval = {% call kt::to_ffi_call(meth) %}
+            return {{ "_retval"|lift_kt(return_type) }}
+        }
+        {%- when None %}
+        fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %}) =
+            {% call kt::to_ffi_call(meth) %}
+        {%- endmatch %}
+        {% endif %}
+        {%- endfor %}
+        internal fun lift(handle: Long): {{ obj.name()|class_name_kt }} {
+            return {{ obj.name()|class_name_kt }}(handle)
+        }
+
+        internal fun read(buf: ByteBuffer): {{ obj.name()|class_name_kt }} {
+            return {{ obj.name()|class_name_kt }}(buf.getLong())
+        }
+    }
 }
@@ -1,6 +1,14 @@
+{%- call kt::docstring(rec, 0) %}
 data class {{ rec.name()|class_name_kt }} (
     {%- for field in rec.fields() %}
-    val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt }}{% if loop.last %}{% else %},{% endif %}
+    {%- call kt::docstring(field, 4) %}
+    {%- call kt::deprecated(field, 4) %}
+    val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt }}
+    {%- match field.default_value() %}
+    {%- when Some with(literal) %} = {{ literal|literal_kt(field.type_()) }}
+    {%- else %}
+    {%- endmatch -%}
+    {% if loop.last %}{% else %},{% endif %}
     {%- endfor %}
 ) {
     companion object {
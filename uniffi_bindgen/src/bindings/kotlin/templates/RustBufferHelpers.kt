@@ -282,6 +282,100 @@ internal fun String.write(buf: RustBufferBuilder) {
     buf.put(byteArr)
 }
 
+{% when Type::Bytes -%}
+
+// Kotlin's `ByteArray` has no companion object for us to extend, so unlike strings,
+// bytes are handled by standalone functions. At the top level they are passed as the
+// raw contents of a `RustBuffer`, and in a serialized buffer they have an i32 length prefix.
+
+internal fun liftBytes(rbuf: RustBuffer.ByValue): ByteArray {
+    try {
+        val byteArr = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()!!.get(byteArr)
+        return byteArr
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+internal fun readBytes(buf: ByteBuffer): ByteArray {
+    val len = buf.getInt()
+    val byteArr = ByteArray(len)
+    buf.get(byteArr)
+    return byteArr
+}
+
+internal fun lowerBytes(v: ByteArray): RustBuffer.ByValue {
+    val rbuf = RustBuffer.alloc(v.size)
+    rbuf.asByteBuffer()!!.put(v)
+    return rbuf
+}
+
+internal fun writeBytes(v: ByteArray, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    buf.put(v)
+}
+
+{% when Type::Timestamp -%}
+
+// Timestamps are passed as the seconds since the unix epoch (rounded down) followed by the
+// nanoseconds after that second, which is also how `java.time.Instant` represents them.
+
+internal fun liftTimestamp(rbuf: RustBuffer.ByValue): java.time.Instant {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readTimestamp(buf)
+    }
+}
+
+internal fun readTimestamp(buf: ByteBuffer): java.time.Instant {
+    val seconds = buf.getLong()
+    val nanoseconds = buf.getInt().toLong()
+    return java.time.Instant.ofEpochSecond(seconds, nanoseconds)
+}
+
+internal fun lowerTimestamp(v: java.time.Instant): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeTimestamp(v, buf)
+    }
+}
+
+internal fun writeTimestamp(v: java.time.Instant, buf: RustBufferBuilder) {
+    buf.putLong(v.epochSecond)
+    buf.putInt(v.nano)
+}
+
+{% when Type::Duration -%}
+
+internal fun liftDuration(rbuf: RustBuffer.ByValue): java.time.Duration {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readDuration(buf)
+    }
+}
+
+internal fun readDuration(buf: ByteBuffer): java.time.Duration {
+    // The seconds are an unsigned 64-bit integer, which must fit in the Long used by java.time.Duration.
+    val seconds = buf.getLong()
+    val nanoseconds = buf.getInt().toLong()
+    if (seconds < 0) {
+        throw java.time.DateTimeException("Duration exceeds minimum or maximum value supported by uniffi")
+    }
+    return java.time.Duration.ofSeconds(seconds, nanoseconds)
+}
+
+internal fun lowerDuration(v: java.time.Duration): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeDuration(v, buf)
+    }
+}
+
+internal fun writeDuration(v: java.time.Duration, buf: RustBufferBuilder) {
+    if (v.isNegative) {
+        throw IllegalArgumentException("Invalid duration, must be non-negative")
+    }
+    buf.putLong(v.seconds)
+    buf.putInt(v.nano)
+}
+
 {% when Type::Optional with (inner_type) -%}
 {% let inner_type_name = inner_type|type_kt %}
 
@@ -346,40 +440,151 @@ internal fun write{{ canonical_type_name }}(v: List<{{ inner_type_name }}>, buf:
     }
 }
 
-{% when Type::Map with (inner_type) -%}
-{% let inner_type_name = inner_type|type_kt %}
+{% when Type::Map with (key_type, value_type) -%}
+{% let key_type_name = key_type|type_kt %}
+{% let value_type_name = value_type|type_kt %}
 
 // Helper functions for pasing values of type {{ typ|type_kt }}
 
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<String, {{ inner_type_name }}> {
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<{{ key_type_name }}, {{ value_type_name }}> {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<String, {{ inner_type_name }}> {
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<{{ key_type_name }}, {{ value_type_name }}> {
     // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
-    val items : MutableMap<String, {{ inner_type_name }}> = mutableMapOf()
+    val items : MutableMap<{{ key_type_name }}, {{ value_type_name }}> = mutableMapOf()
     val len = buf.getInt()
     repeat(len) {
-        val k = String.read(buf)
-        val v = {{ "buf"|read_kt(inner_type) }}
+        val k = {{ "buf"|read_kt(key_type) }}
+        val v = {{ "buf"|read_kt(value_type) }}
         items[k] = v
     }
     return items
 }
 
-internal fun lower{{ canonical_type_name }}(m: Map<String, {{ inner_type_name }}>): RustBuffer.ByValue {
+internal fun lower{{ canonical_type_name }}(m: Map<{{ key_type_name }}, {{ value_type_name }}>): RustBuffer.ByValue {
     return lowerIntoRustBuffer(m) { m, buf ->
         write{{ canonical_type_name }}(m, buf)
     }
 }
 
-internal fun write{{ canonical_type_name }}(v: Map<String, {{ inner_type_name }}>, buf: RustBufferBuilder) {
+internal fun write{{ canonical_type_name }}(v: Map<{{ key_type_name }}, {{ value_type_name }}>, buf: RustBufferBuilder) {
     buf.putInt(v.size)
     v.forEach { k, v ->
-        k.write(buf)
-        {{ "v"|write_kt("buf", inner_type) }}
+        {{ "k"|write_kt("buf", key_type) }}
+        {{ "v"|write_kt("buf", value_type) }}
+    }
+}
+
+{% when Type::Iterator with (item_type) -%}
+{% let item_type_name = item_type|type_kt %}
+
+// Helper functions for pasing values of type {{ typ|type_kt }}
+
+internal fun lift{{ canonical_type_name }}(handle: Long): RustIterator<{{ item_type_name }}> {
+    return RustIterator(
+        handle,
+        { handle, maxItems ->
+            val rbuf = rustCall { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_iterator_next(item_type).name() }}(handle, maxItems, status)
+            }
+            liftFromRustBuffer(rbuf) { buf ->
+                List<{{ item_type_name }}>(buf.getInt()) {
+                    {{ "buf"|read_kt(item_type) }}
+                }
+            }
+        },
+        { handle ->
+            rustCall { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_iterator_free(item_type).name() }}(handle, status)
+            }
+        }
+    )
+}
+
+{% when Type::Custom with (name, builtin) -%}
+{% let builtin_type_name = builtin|type_kt %}
+{% let builtin_ffi_type = builtin|ffi_type_kt %}
+
+// Helper functions for passing values of custom type {{ name }}, which is passed over the FFI as {{ builtin|type_kt }}
+{% match config.bindings.custom_type(name) -%}
+{% when Some with (custom) %}
+typealias {{ name|class_name_kt }} = {{ custom.type_name.as_deref().unwrap_or(builtin_type_name.as_str()) }}
+
+internal fun lift{{ canonical_type_name }}(v: {{ builtin_ffi_type }}): {{ name|class_name_kt }} {
+    val builtinValue = {{ "v"|lift_kt(builtin) }}
+    return {{ custom.into_custom_expr("builtinValue") }}
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ name|class_name_kt }} {
+    val builtinValue = {{ "buf"|read_kt(builtin) }}
+    return {{ custom.into_custom_expr("builtinValue") }}
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ name|class_name_kt }}): {{ builtin_ffi_type }} {
+    val builtinValue = {{ custom.from_custom_expr("v") }}
+    return {{ "builtinValue"|lower_kt(builtin) }}
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ name|class_name_kt }}, buf: RustBufferBuilder) {
+    val builtinValue = {{ custom.from_custom_expr("v") }}
+    {{ "builtinValue"|write_kt("buf", builtin) }}
+}
+{% when None %}
+typealias {{ name|class_name_kt }} = {{ builtin_type_name }}
+
+internal fun lift{{ canonical_type_name }}(v: {{ builtin_ffi_type }}): {{ name|class_name_kt }} {
+    return {{ "v"|lift_kt(builtin) }}
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ name|class_name_kt }} {
+    return {{ "buf"|read_kt(builtin) }}
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ name|class_name_kt }}): {{ builtin_ffi_type }} {
+    return {{ "v"|lower_kt(builtin) }}
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ name|class_name_kt }}, buf: RustBufferBuilder) {
+    {{ "v"|write_kt("buf", builtin) }}
+}
+{% endmatch %}
+
+{% when Type::External with (name, namespace) -%}
+{% let type_name = name|class_name_kt %}
+
+// Helper functions for passing values of type {{ type_name }}, which is defined by the `{{ namespace }}` component.
+// We read them using the helpers from its bindings, but it writes them into buffers that
+// belong to its own library, so we have to copy them across into ours.
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return {{ type_name }}.read(buf)
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
+    val rbuf = v.lower()
+    try {
+        rbuf.asByteBuffer()?.let { bytes ->
+            buf.reserve(rbuf.len) { bbuf ->
+                bbuf.put(bytes)
+            }
+        }
+    } finally {
+        uniffi.{{ namespace }}.RustBuffer.free(rbuf)
     }
 }
 
@@ -390,10 +595,16 @@ internal fun write{{ canonical_type_name }}(v: Map<String, {{ inner_type_name }}
 {# Helpers for Record types are defined inline with the Record class #}
 
 {% when Type::Object with (object_name) -%}
-{# Object types cannot be lifted, lowered or serialized (yet) #}
+{# Helpers for Object types are defined inline with the Object class #}
 
 {% when Type::Error with (error_name) -%}
-{# Error types cannot be lifted, lowered or serialized (yet) #}
+{# Helpers for Error types are defined inline with the Error class #}
+
+{% when Type::Union with (union_name, members) -%}
+{# Helpers for Union types are defined inline with the Union class #}
+
+{% when Type::CallbackInterface with (interface_name) -%}
+{# Helpers for Callback Interface types are defined inline with the CallbackInterfaceInternals class #}
 
 {% endmatch %}
 {% endfor %}
@@ -9,18 +9,27 @@ open class RustBuffer : Structure() {
     @JvmField var data: Pointer? = null
 
     class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference {
+        // Used by callback interfaces to hand a buffer back to rust through an out-pointer.
+        internal fun setValue(other: RustBuffer) {
+            this.capacity = other.capacity
+            this.len = other.len
+            this.data = other.data
+            this.write()
+        }
+    }
 
     companion object {
-        internal fun alloc(size: Int = 0) = rustCall(InternalError.ByReference()) { err ->
-            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_alloc().name() }}(size, err)
+        internal fun alloc(size: Int = 0) = rustCall { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_alloc().name() }}(size, status)
         }
 
-        internal fun free(buf: RustBuffer.ByValue) = rustCall(InternalError.ByReference()) { err ->
-            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_free().name() }}(buf, err)
+        internal fun free(buf: RustBuffer.ByValue) = rustCall { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_free().name() }}(buf, status)
         }
 
-        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall(InternalError.ByReference()) { err ->
-            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_reserve().name() }}(buf, additional, err)
+        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rustbuffer_reserve().name() }}(buf, additional, status)
         }
     }
 
@@ -0,0 +1,45 @@
+// Helpers for `[Iterator]` functions and methods.
+//
+// These return a handle to a rust iterator, from which we take the items in batches as they're
+// needed, rather than rust producing all of them up front. A batch that's shorter than we asked
+// for means that the iterator is exhausted, at which point we free it straight away.
+
+class RustIterator<T> internal constructor(
+    private var handle: Long,
+    private val takeBatch: (handle: Long, maxItems: Int) -> List<T>,
+    private val free: (handle: Long) -> Unit
+) : Iterator<T>, AutoCloseable {
+    private var batch: List<T> = listOf()
+    private var offset = 0
+
+    override fun hasNext(): Boolean {
+        if (offset == batch.size && handle != 0L) {
+            batch = takeBatch(handle, BATCH_SIZE)
+            offset = 0
+            if (batch.size < BATCH_SIZE) {
+                close()
+            }
+        }
+        return offset < batch.size
+    }
+
+    override fun next(): T {
+        if (!hasNext()) {
+            throw NoSuchElementException()
+        }
+        return batch[offset++]
+    }
+
+    // Free the rust iterator, without taking any more items from it.
+    override fun close() {
+        val handle = this.handle
+        if (handle != 0L) {
+            this.handle = 0L
+            free(handle)
+        }
+    }
+
+    companion object {
+        internal const val BATCH_SIZE = 100
+    }
+}
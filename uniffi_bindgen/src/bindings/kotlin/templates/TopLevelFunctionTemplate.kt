@@ -1,13 +1,22 @@
+{%- if func.is_async() %}
+{% call kt::docstring(func, 0) %}
+{%- call kt::deprecated(func, 0) %}
+suspend fun {{ func.name()|fn_name_kt }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt }}{% else %}{% endmatch %} =
+    {% call kt::to_async_ffi_call("", func) %}
+{% else -%}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
-
+{% call kt::docstring(func, 0) %}
+{%- call kt::deprecated(func, 0) %}
 fun {{ func.name()|fn_name_kt }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_kt }} {
     val _retval = {% call kt::to_ffi_call(func) %}
     return {{ "_retval"|lift_kt(return_type) }}
 }
 
 {% when None -%}
-
+{% call kt::docstring(func, 0) %}
+{%- call kt::deprecated(func, 0) %}
 fun {{ func.name()|fn_name_kt }}({% call kt::arg_list_decl(func) %}) =
     {% call kt::to_ffi_call(func) %}
 {% endmatch %}
+{%- endif %}
@@ -0,0 +1,41 @@
+{#
+// Kotlin doesn't have anonymous sum types, so we use a `sealed class` with a
+// subclass wrapping a value of each member type. The subclasses get a `Value`
+// suffix so that they don't shadow the member types themselves.
+#}
+
+sealed class {{ name|class_name_kt }} {
+    {% for member in members -%}
+    data class {{ member.union_member_name()|class_name_kt }}Value(val value: {{ member|type_kt }}) : {{ name|class_name_kt }}()
+    {% endfor %}
+
+    companion object {
+        internal fun lift(rbuf: RustBuffer.ByValue): {{ name|class_name_kt }} {
+            return liftFromRustBuffer(rbuf) { buf -> {{ name|class_name_kt }}.read(buf) }
+        }
+
+        internal fun read(buf: ByteBuffer): {{ name|class_name_kt }} {
+            return when(buf.getInt()) {
+                {%- for member in members %}
+                {{ loop.index }} -> {{ name|class_name_kt }}.{{ member.union_member_name()|class_name_kt }}Value({{ "buf"|read_kt(member) }})
+                {%- endfor %}
+                else -> throw RuntimeException("invalid union tag, something is very wrong!!")
+            }
+        }
+    }
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    internal fun write(buf: RustBufferBuilder) {
+        when(this) {
+            {%- for member in members %}
+            is {{ name|class_name_kt }}.{{ member.union_member_name()|class_name_kt }}Value -> {
+                buf.putInt({{ loop.index }})
+                {{ "(this.value)"|write_kt("buf", member) }}
+            }
+            {%- endfor %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+}
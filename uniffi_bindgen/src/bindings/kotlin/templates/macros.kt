@@ -5,33 +5,52 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
-rustCall(
-    {% match func.throws() %}
-    {% when Some with (e) %}
-    {{e}}.ByReference()
-    {% else %}
-    InternalError.ByReference()
-    {% endmatch %}
-) { err ->
-    _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}err)
+    {%- match func.throws() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|class_name_kt }}Exception)
+    {%- else %}
+    rustCall
+    {%- endmatch %} { status ->
+    _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}status)
 }
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) %}
-rustCall(
-    {% match func.throws() %}
-    {% when Some with (e) %}
-    {{e}}.ByReference()
-    {% else %}
-    InternalError.ByReference()
-    {% endmatch %}
-) { err ->
+    {%- match func.throws() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|class_name_kt }}Exception)
+    {%- else %}
+    rustCall
+    {%- endmatch %} { status ->
     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
-        {{- prefix }}, {% call _arg_list_ffi_call(func) %}{% if func.arguments().len() > 0 %}, {% endif %}err)
+        {{- prefix }}, {% call _arg_list_ffi_call(func) %}{% if func.arguments().len() > 0 %}, {% endif %}status)
 }
 {%- endmacro %}
 
 
+{#
+// Call an `[Async]` function from a suspend function, and lift its result.
+// The prefix holds any leading arguments such as the object handle, and may be empty.
+#}
+{%- macro to_async_ffi_call(prefix, func) %}
+    {%- match func.throws() %}
+    {%- when Some with (e) %}
+    rustCallAsyncWithError({{ e|class_name_kt }}Exception)
+    {%- else %}
+    rustCallAsync
+    {%- endmatch %} { callback, callbackData, status ->
+    _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
+        {%- if !prefix.is_empty() %}{{ prefix }}, {% endif %}{% call _arg_list_ffi_call(func) %}{% if func.arguments().len() > 0 %}, {% endif %}callback, callbackData, status)
+}.let {
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    liftFromRustBuffer(it) { buf -> {{ "buf"|read_kt(return_type) }} }
+    {%- else %}
+    RustBuffer.free(it)
+    {%- endmatch %}
+}
+{%- endmacro %}
+
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
         {{- arg.name()|lower_kt(arg.type_()) }}
@@ -47,10 +66,29 @@ rustCall(
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
         {{ arg.name()|var_name_kt }}: {{ arg.type_()|type_kt -}}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_kt(arg.type_()) }}
+        {%- else %}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
 
+{#-
+// Arglist for a method, which leaves out the default values of the arguments when the method
+// overrides one that it inherits, since Kotlin only lets the overridden method declare them.
+-#}
+{% macro method_arg_list_decl(meth) %}
+    {%- if meth.inherited_from().is_some() %}
+    {%- for arg in meth.arguments() -%}
+        {{ arg.name()|var_name_kt }}: {{ arg.type_()|type_kt -}}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+    {%- else %}
+    {%- call arg_list_decl(meth) %}
+    {%- endif %}
+{%- endmacro %}
+
 {#-
 // Arglist as used in the _UniFFILib function declations.
 // Note unfiltered name but type_ffi filters.
@@ -60,5 +98,27 @@ rustCall(
         {{- arg.name() }}: {{ arg.type_()|type_ffi -}}
         {%- if loop.last %}{% else %},{% endif %}
     {%- endfor %}
-    {% if func.arguments().len() > 0 %},{% endif %} uniffi_out_err: Structure.ByReference
+    {% if func.arguments().len() > 0 %},{% endif %} uniffi_out_err: RustCallStatus
 {%- endmacro -%}
+
+{#
+// Render the doc comment from the IDL for a definition, if it has one, as KDoc.
+#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with(docstring) %}
+{{ docstring|docstring_kt(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#
+// Mark a `[Deprecated]` definition as such, with an annotation.
+#}
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some with(message) %}
+{{ message|deprecated_kt(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
@@ -1,5 +1,11 @@
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
+{%- match ci.namespace_docstring() %}
+{%- when Some with(docstring) %}
+
+{{ docstring|docstring_kt(0) }}
+{%- else %}
+{%- endmatch %}
 
 @file:Suppress("NAME_SHADOWING")
 
@@ -24,6 +30,20 @@ import com.sun.jna.Structure
 import java.nio.ByteBuffer
 import java.nio.ByteOrder
 import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.suspendCoroutine
+{%- for module in config.bindings.custom_type_imports() %}
+import {{ module }}
+{%- endfor %}
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::External with (name, namespace) %}
+import uniffi.{{ namespace }}.{{ name|class_name_kt }}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
 
 {% include "RustBufferTemplate.kt" %}
 
@@ -33,6 +53,14 @@ import java.util.concurrent.atomic.AtomicLong
 
 {% include "Helpers.kt" %}
 
+{% if ci.iter_callback_interface_definitions().len() > 0 || ci.has_async_functions() -%}
+{% include "ConcurrentHandleMap.kt" %}
+{%- endif %}
+
+{% if ci.iter_callback_interface_definitions().len() > 0 -%}
+{% include "CallbackInterfaceRuntime.kt" %}
+{%- endif %}
+
 // Public interface members begin here.
 // Public facing enums
 {% for e in ci.iter_enum_definitions() %}
@@ -42,11 +70,33 @@ import java.util.concurrent.atomic.AtomicLong
 // Error definitions
 {% include "ErrorTemplate.kt" %}
 
+{% if ci.has_async_functions() -%}
+{% include "AsyncRuntime.kt" %}
+{%- endif %}
+
+{% if ci.iter_iterator_item_types().len() > 0 -%}
+{% include "RustIteratorRuntime.kt" %}
+{%- endif %}
+
 // Public facing records
 {%- for rec in ci.iter_record_definitions() %}
 {% include "RecordTemplate.kt" %}
 {% endfor %}
 
+// Public facing unions
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Union with (name, members) %}
+{% include "UnionTemplate.kt" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+// Namespace constants
+{%- for c in ci.iter_constant_definitions() %}
+const val {{ c.name()|enum_variant_kt }}: {{ c.type_()|type_kt }} = {{ c.value()|literal_kt(c.type_()) }}
+{%- endfor %}
+
 // Namespace functions
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.kt" %}
@@ -54,7 +104,16 @@ import java.util.concurrent.atomic.AtomicLong
 
 // Objects
 {% for obj in ci.iter_object_definitions() %}
+{%- if obj.is_base() %}
+{% include "BaseObjectTemplate.kt" %}
+{%- else %}
 {% include "ObjectTemplate.kt" %}
+{%- endif %}
+{% endfor %}
+
+// Callback Interfaces
+{% for cbi in ci.iter_callback_interface_definitions() %}
+{% include "CallbackInterfaceTemplate.kt" %}
 {% endfor %}
 
 {% import "macros.kt" as kt %}
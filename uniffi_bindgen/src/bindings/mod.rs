@@ -8,6 +8,8 @@
 //! along with some helpers for executing foreign language scripts or tests.
 
 use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::path::Path;
 
@@ -23,13 +25,23 @@ pub mod swift;
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TargetLanguage {
     Kotlin,
     Swift,
     Python,
 }
 
+impl std::fmt::Display for TargetLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TargetLanguage::Kotlin => "kotlin",
+            TargetLanguage::Swift => "swift",
+            TargetLanguage::Python => "python",
+        })
+    }
+}
+
 impl TryFrom<&str> for TargetLanguage {
     type Error = anyhow::Error;
     fn try_from(value: &str) -> Result<Self> {
@@ -59,9 +71,81 @@ impl TryFrom<String> for TargetLanguage {
     }
 }
 
+/// Options for customizing the generated foreign language bindings.
+///
+/// These are read from the `[bindings]` table of the component's `uniffi.toml` file, with
+/// a sub-table for each target language. They can only change how the generated code looks
+/// to its consumers, since how the component behaves is entirely determined by its IDL.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BindingsConfig {
+    #[serde(default)]
+    pub kotlin: LanguageConfig,
+    #[serde(default)]
+    pub swift: LanguageConfig,
+    #[serde(default)]
+    pub python: LanguageConfig,
+}
+
+/// Options for customizing the generated bindings for a single target language.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LanguageConfig {
+    /// How to represent the component's custom types, keyed by the name of the type in the IDL.
+    /// Custom types that aren't listed here are represented as the builtin type that they wrap.
+    #[serde(default)]
+    pub custom_types: HashMap<String, CustomTypeConfig>,
+}
+
+impl LanguageConfig {
+    pub fn custom_type(&self, name: &str) -> Option<&CustomTypeConfig> {
+        self.custom_types.get(name)
+    }
+
+    /// The modules imported by all the configured custom types, in a stable order.
+    pub fn custom_type_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .custom_types
+            .values()
+            .flat_map(|t| t.imports.iter().cloned())
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+}
+
+/// How to represent a custom type in the foreign language, in place of the builtin type it wraps.
+///
+/// The conversion expressions are snippets of foreign-language code, in which `{}` is replaced
+/// by the value to be converted. For example, a Kotlin binding might represent `[Custom] typedef
+/// string Url;` with `type_name = "java.net.URL"`, `into_custom = "java.net.URL({})"` and
+/// `from_custom = "{}.toString()"`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomTypeConfig {
+    /// The foreign-language type; omitted for dynamically-typed languages like Python.
+    pub type_name: Option<String>,
+    /// Modules that must be imported by the generated code in order to use the type.
+    #[serde(default)]
+    pub imports: Vec<String>,
+    /// Expression converting a value of the builtin type into the custom type.
+    pub into_custom: String,
+    /// Expression converting a value of the custom type into the builtin type.
+    pub from_custom: String,
+}
+
+impl CustomTypeConfig {
+    pub fn into_custom_expr(&self, nm: &str) -> String {
+        self.into_custom.replace("{}", nm)
+    }
+
+    pub fn from_custom_expr(&self, nm: &str) -> String {
+        self.from_custom.replace("{}", nm)
+    }
+}
+
 /// Generate foreign language bindings from a compiled `uniffi` library.
 pub fn write_bindings<P>(
     ci: &ComponentInterface,
+    config: &BindingsConfig,
     out_dir: P,
     language: TargetLanguage,
     try_format_code: bool,
@@ -71,9 +155,15 @@ where
 {
     let out_dir = out_dir.as_ref();
     match language {
-        TargetLanguage::Kotlin => kotlin::write_bindings(&ci, out_dir, try_format_code)?,
-        TargetLanguage::Swift => swift::write_bindings(&ci, out_dir, try_format_code)?,
-        TargetLanguage::Python => python::write_bindings(&ci, out_dir, try_format_code)?,
+        TargetLanguage::Kotlin => {
+            kotlin::write_bindings(ci, &config.kotlin, out_dir, try_format_code)?
+        }
+        TargetLanguage::Swift => {
+            swift::write_bindings(ci, &config.swift, out_dir, try_format_code)?
+        }
+        TargetLanguage::Python => {
+            python::write_bindings(ci, &config.python, out_dir, try_format_code)?
+        }
     }
     Ok(())
 }
@@ -89,8 +179,8 @@ where
 {
     let out_dir = out_dir.as_ref();
     match language {
-        TargetLanguage::Kotlin => kotlin::compile_bindings(&ci, out_dir)?,
-        TargetLanguage::Swift => swift::compile_bindings(&ci, out_dir)?,
+        TargetLanguage::Kotlin => kotlin::compile_bindings(ci, out_dir)?,
+        TargetLanguage::Swift => swift::compile_bindings(ci, out_dir)?,
         TargetLanguage::Python => (),
     }
     Ok(())
@@ -6,19 +6,20 @@ use anyhow::Result;
 use askama::Template;
 use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 
+use crate::bindings::LanguageConfig;
 use crate::interface::*;
 
 // Some config options for it the caller wants to customize the generated python.
 // Note that this can only be used to control details of the python *that do not affect the underlying component*,
 // sine the details of the underlying component are entirely determined by the `ComponentInterface`.
 pub struct Config {
-    // No config options yet.
+    pub bindings: LanguageConfig,
 }
 
 impl Config {
-    pub fn from(_ci: &ComponentInterface) -> Self {
+    pub fn from(_ci: &ComponentInterface, bindings: &LanguageConfig) -> Self {
         Config {
-            // No config options yet
+            bindings: bindings.clone(),
         }
     }
 }
@@ -26,12 +27,12 @@ impl Config {
 #[derive(Template)]
 #[template(syntax = "py", escape = "none", path = "wrapper.py")]
 pub struct PythonWrapper<'a> {
-    _config: Config,
+    config: Config,
     ci: &'a ComponentInterface,
 }
 impl<'a> PythonWrapper<'a> {
-    pub fn new(_config: Config, ci: &'a ComponentInterface) -> Self {
-        Self { _config, ci }
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
     }
 }
 
@@ -51,10 +52,10 @@ mod filters {
             FFIType::UInt64 => "ctypes.c_uint64".to_string(),
             FFIType::Float32 => "ctypes.c_float".to_string(),
             FFIType::Float64 => "ctypes.c_double".to_string(),
-            FFIType::RustCString => "ctypes.c_voidp".to_string(),
             FFIType::RustBuffer => "RustBuffer".to_string(),
-            FFIType::RustError => "RustError".to_string(),
             FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::ForeignCallback => "FOREIGN_CALLBACK_T".to_string(),
+            FFIType::FutureCallback => "FUTURE_CALLBACK_T".to_string(),
         })
     }
 
@@ -70,6 +71,68 @@ mod filters {
         Ok(nm.to_string().to_snake_case())
     }
 
+    /// Get the name of the instance attribute that holds the value of a record field.
+    ///
+    /// Deprecated fields are exposed as a property that warns when it's used, so their value
+    /// lives in a private attribute behind it.
+    pub fn field_attr_py(field: &Field) -> Result<String, askama::Error> {
+        let name = var_name_py(&field.name())?;
+        Ok(match field.deprecated() {
+            Some(_) => format!("_{}", name),
+            None => name,
+        })
+    }
+
+    /// Get the Python syntax for a literal value of the given type, e.g. a default argument value.
+    pub fn literal_py(literal: &Literal, type_: &Type) -> Result<String, askama::Error> {
+        Ok(match (literal, type_) {
+            (_, Type::Optional(t)) if !matches!(literal, Literal::Null) => literal_py(literal, t)?,
+            (Literal::Boolean(true), _) => "True".into(),
+            (Literal::Boolean(false), _) => "False".into(),
+            (Literal::String(s), _) => format!("\"{}\"", s.replace('\\', "\\\\")),
+            (Literal::Int(v), _) => v.to_string(),
+            (Literal::UInt(v), _) => v.to_string(),
+            (Literal::Float(v), _) => v.clone(),
+            (Literal::Enum(v), Type::Enum(name)) => {
+                format!("{}.{}", class_name_py(name)?, enum_name_py(v)?)
+            }
+            (Literal::Enum(_), _) => unreachable!("enum literals are only used for enums"),
+            (Literal::EmptySequence, _) => "[]".into(),
+            (Literal::EmptyMap, _) => "{}".into(),
+            (Literal::Null, _) => "None".into(),
+        })
+    }
+
+    /// Format a doc comment from the IDL as a Python docstring, indented by the given number of spaces.
+    pub fn docstring_py(docstring: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let indent = " ".repeat(*spaces);
+        let docstring = docstring
+            .replace('\\', "\\\\")
+            .replace("\"\"\"", "\\\"\\\"\\\"");
+        // Short docstrings fit on one line, as long as they don't end in a quote.
+        if !docstring.contains('\n') && !docstring.ends_with('"') {
+            return Ok(format!("{}\"\"\"{}\"\"\"", indent, docstring));
+        }
+        let mut pydoc = format!("{}\"\"\"\n", indent);
+        for line in docstring.lines() {
+            pydoc.push_str(format!("{}{}", indent, line).trim_end());
+            pydoc.push('\n');
+        }
+        pydoc.push_str(&format!("{}\"\"\"", indent));
+        Ok(pydoc)
+    }
+
+    /// Get a statement that warns about using a `[Deprecated]` definition, indented by the given
+    /// number of spaces. The warning is attributed to the caller of the generated code.
+    pub fn deprecated_py(message: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let message = message.replace('\\', "\\\\").replace('"', "\\\"");
+        Ok(format!(
+            "{}warnings.warn(\"{}\", DeprecationWarning, stacklevel=2)",
+            " ".repeat(*spaces),
+            message
+        ))
+    }
+
     pub fn enum_name_py(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
         Ok(nm.to_string().to_shouty_snake_case())
     }
@@ -88,18 +151,24 @@ mod filters {
             | Type::Float64
             | Type::String
             | Type::Boolean
+            | Type::Bytes
+            | Type::Timestamp
+            | Type::Duration
             | Type::Object(_)
-            | Type::Error(_) => format!("{} = {}", nm, nm),
-            Type::Enum(type_name) => format!("{} = {}({})", nm, type_name, nm),
-            Type::Record(type_name) => format!("{} = {}._coerce({})", nm, type_name, nm),
+            | Type::Error(_)
+            | Type::CallbackInterface(_)
+            | Type::Custom(..) => format!("{} = {}", nm, nm),
+            Type::Enum(type_name)
+            | Type::Record(type_name)
+            | Type::Union(type_name, _)
+            | Type::External(type_name, _) => {
+                format!("{} = {}._coerce({})", nm, type_name, nm)
+            }
             Type::Optional(t) => format!("(None if {} is None else {})", nm, coerce_py(nm, t)?),
             Type::Sequence(t) => format!("({} for x in {})", coerce_py(&"x", t)?, nm), // TODO: name hygiene,
-            Type::Map(t) => format!(
-                "({}:{} for (k, v) in {}.items())",
-                coerce_py(&"k", t)?,
-                coerce_py(&"v", t)?,
-                nm
-            ),
+            // Accept any mapping, not just dicts.
+            Type::Map(..) => format!("{} = dict({})", nm, nm),
+            Type::Iterator(_) => unreachable!("iterators are only ever returned from rust"),
         })
     }
 
@@ -116,14 +185,33 @@ mod filters {
             | Type::Float32
             | Type::Float64
             | Type::Boolean => nm.to_string(),
-            Type::Enum(_) => format!("{}.value", nm),
-            Type::String => format!("{}.encode('utf-8')", nm),
-            Type::Record(type_name) => format!("{}._lower({})", type_name, nm),
+            Type::String => format!("lowerBytes({}.encode('utf-8'))", nm),
+            Type::Bytes => format!("lowerBytes({})", nm),
+            Type::Timestamp => format!("lowerTimestamp({})", nm),
+            Type::Duration => format!("lowerDuration({})", nm),
+            Type::Enum(type_name) | Type::Record(type_name) | Type::Union(type_name, _) => {
+                format!("{}._lower({})", type_name, nm)
+            }
+            Type::Object(type_name) => format!("{}._lower({})", class_name_py(type_name)?, nm),
+            Type::Custom(..) | Type::External(..) => {
+                format!("lower{}({})", class_name_py(&type_.canonical_name())?, nm)
+            }
+            Type::CallbackInterface(type_name) => format!(
+                "CallbackInterface{}Internals.lower({})",
+                class_name_py(type_name)?,
+                nm
+            ),
             Type::Optional(_type) => format!(
                 "lowerOptional({}, lambda buf, v: {})",
                 nm,
                 lower_into_py(&"buf", &"v", type_)?
             ),
+            Type::Map(k, v) => format!(
+                "lowerMap({}, lambda buf, k: {}, lambda buf, v: {})",
+                nm,
+                lower_into_py(&"k", &"buf", k)?,
+                lower_into_py(&"v", &"buf", v)?
+            ),
             _ => panic!("[TODO: lower_py({:?})]", type_),
         })
     }
@@ -135,10 +223,49 @@ mod filters {
     ) -> Result<String, askama::Error> {
         let nm = var_name_py(nm)?;
         Ok(match type_ {
-            Type::Float64 => format!("{}.putDouble({})", target, nm),
+            Type::Int8 => format!("{}.putI8({})", target, nm),
+            Type::UInt8 => format!("{}.putU8({})", target, nm),
+            Type::Int16 => format!("{}.putI16({})", target, nm),
+            Type::UInt16 => format!("{}.putU16({})", target, nm),
+            Type::Int32 => format!("{}.putI32({})", target, nm),
             Type::UInt32 => format!("{}.putInt({})", target, nm),
+            Type::Int64 => format!("{}.putI64({})", target, nm),
+            Type::UInt64 => format!("{}.putLong({})", target, nm),
+            Type::Float32 => format!("{}.putFloat({})", target, nm),
+            Type::Float64 => format!("{}.putDouble({})", target, nm),
+            Type::Boolean => format!("{}.putI8(1 if {} else 0)", target, nm),
             Type::String => format!("{}.putString({})", target, nm),
-            Type::Record(type_name) => format!("{}._lowerInto({}, {})", type_name, nm, target),
+            Type::Bytes => format!("{}.putBytes({})", target, nm),
+            Type::Timestamp => format!("{}.putTimestamp({})", target, nm),
+            Type::Duration => format!("{}.putDuration({})", target, nm),
+            Type::Enum(type_name) | Type::Record(type_name) | Type::Union(type_name, _) => {
+                format!("{}._lowerInto({}, {})", type_name, nm, target)
+            }
+            Type::Object(type_name) => format!(
+                "{}._lowerInto({}, {})",
+                class_name_py(type_name)?,
+                nm,
+                target
+            ),
+            Type::Custom(..) | Type::External(..) => format!(
+                "lowerInto{}({}, {})",
+                class_name_py(&type_.canonical_name())?,
+                nm,
+                target
+            ),
+            Type::CallbackInterface(type_name) => format!(
+                "CallbackInterface{}Internals.lowerInto({}, {})",
+                class_name_py(type_name)?,
+                nm,
+                target
+            ),
+            Type::Map(k, v) => format!(
+                "lowerIntoMap({}, {}, lambda buf, k: {}, lambda buf, v: {})",
+                nm,
+                target,
+                lower_into_py(&"k", &"buf", k)?,
+                lower_into_py(&"v", &"buf", v)?
+            ),
             _ => panic!("[TODO: lower_into_py({:?})]", type_),
         })
     }
@@ -156,9 +283,17 @@ mod filters {
             | Type::Float32
             | Type::Float64
             | Type::Boolean => format!("{}", nm),
-            Type::Enum(type_name) => format!("{}({})", type_name, nm),
             Type::String => format!("liftString({})", nm),
-            Type::Record(type_name) => format!("{}._lift({})", type_name, nm),
+            Type::Bytes => format!("liftBytes({})", nm),
+            Type::Timestamp => format!("liftTimestamp({})", nm),
+            Type::Duration => format!("liftDuration({})", nm),
+            Type::Enum(type_name) | Type::Record(type_name) | Type::Union(type_name, _) => {
+                format!("{}._lift({})", type_name, nm)
+            }
+            Type::Object(type_name) => format!("{}._lift({})", class_name_py(type_name)?, nm),
+            Type::Custom(..) | Type::External(..) | Type::Iterator(_) => {
+                format!("lift{}({})", class_name_py(&type_.canonical_name())?, nm)
+            }
             Type::Optional(type_) => format!(
                 "liftOptional({}, lambda buf: {})",
                 nm,
@@ -169,16 +304,48 @@ mod filters {
                 nm,
                 lift_from_py(&"buf", type_)?
             ),
+            Type::Map(k, v) => format!(
+                "liftMap({}, lambda buf: {}, lambda buf: {})",
+                nm,
+                lift_from_py(&"buf", k)?,
+                lift_from_py(&"buf", v)?
+            ),
             _ => panic!("[TODO: lift_py({:?})]", type_),
         })
     }
 
     pub fn lift_from_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
+            Type::Int8 => format!("{}.getI8()", nm),
+            Type::UInt8 => format!("{}.getU8()", nm),
+            Type::Int16 => format!("{}.getI16()", nm),
+            Type::UInt16 => format!("{}.getU16()", nm),
+            Type::Int32 => format!("{}.getI32()", nm),
             Type::UInt32 => format!("{}.getInt()", nm),
+            Type::Int64 => format!("{}.getI64()", nm),
+            Type::UInt64 => format!("{}.getLong()", nm),
+            Type::Float32 => format!("{}.getFloat()", nm),
             Type::Float64 => format!("{}.getDouble()", nm),
-            Type::Record(type_name) => format!("{}._liftFrom({})", type_name, nm),
+            Type::Boolean => format!("({}.getI8() != 0)", nm),
+            Type::Enum(type_name) | Type::Record(type_name) | Type::Union(type_name, _) => {
+                format!("{}._liftFrom({})", type_name, nm)
+            }
+            Type::Object(type_name) => format!("{}._liftFrom({})", class_name_py(type_name)?, nm),
+            Type::Custom(..) | Type::External(..) => format!(
+                "liftFrom{}({})",
+                class_name_py(&type_.canonical_name())?,
+                nm
+            ),
             Type::String => format!("{}.getString()", nm),
+            Type::Bytes => format!("{}.getBytes()", nm),
+            Type::Timestamp => format!("{}.getTimestamp()", nm),
+            Type::Duration => format!("{}.getDuration()", nm),
+            Type::Map(k, v) => format!(
+                "liftFromMap({}, lambda buf: {}, lambda buf: {})",
+                nm,
+                lift_from_py(&"buf", k)?,
+                lift_from_py(&"buf", v)?
+            ),
             _ => panic!("[TODO: lift_from_py({:?})]", type_),
         })
     }
@@ -17,18 +17,20 @@ pub mod gen_python;
 pub use gen_python::{Config, PythonWrapper};
 
 use super::super::interface::ComponentInterface;
+use super::LanguageConfig;
 
 // Generate python bindings for the given ComponentInterface, in the given output directory.
 
 pub fn write_bindings(
     ci: &ComponentInterface,
+    bindings_config: &LanguageConfig,
     out_dir: &Path,
     try_format_code: bool,
 ) -> Result<()> {
     let mut py_file = PathBuf::from(out_dir);
     py_file.push(format!("{}.py", ci.namespace()));
     let mut f = File::create(&py_file).context("Failed to create .py file for bindings")?;
-    write!(f, "{}", generate_python_bindings(&ci)?)?;
+    write!(f, "{}", generate_python_bindings(ci, bindings_config)?)?;
 
     if try_format_code {
         if let Err(e) = Command::new("yapf").arg(py_file.to_str().unwrap()).output() {
@@ -45,10 +47,13 @@ pub fn write_bindings(
 
 // Generate python bindings for the given ComponentInterface, as a string.
 
-pub fn generate_python_bindings(ci: &ComponentInterface) -> Result<String> {
-    let config = Config::from(&ci);
+pub fn generate_python_bindings(
+    ci: &ComponentInterface,
+    bindings_config: &LanguageConfig,
+) -> Result<String> {
+    let config = Config::from(ci, bindings_config);
     use askama::Template;
-    PythonWrapper::new(config, &ci)
+    PythonWrapper::new(config, ci)
         .render()
         .map_err(|_| anyhow::anyhow!("failed to render python bindings"))
 }
@@ -0,0 +1,61 @@
+# Support for calling `[Async]` functions.
+#
+# The FFI function for an async function starts a rust future and returns straight away.
+# When the future completes, rust calls `_complete_async_call` from one of its own threads,
+# passing back the handle under which we stored the pending call. The result is handed over
+# to the event loop that made the call, which resolves the asyncio future being awaited.
+
+# The signature of the function through which rust reports the outcome of an async call:
+# it receives the handle of the pending call, a `RustCallStatus` code and a buffer holding
+# the result, the error or the panic message, depending on the code.
+FUTURE_CALLBACK_T = ctypes.CFUNCTYPE(None, ctypes.c_uint64, ctypes.c_int8, RustBuffer)
+
+# The async calls that are waiting for rust to report their outcome, keyed by handle.
+_PENDING_ASYNC_CALLS = ConcurrentHandleMap()
+
+def rust_call_async(error_class, lift_func, fn, *args):
+    # Call an async rust function, returning an asyncio future for its result.
+    #
+    # `lift_func` reads the return value from a `RustBufferStream`, and is None for
+    # functions that don't return anything.
+    loop = asyncio.get_running_loop()
+    future = loop.create_future()
+    handle = _PENDING_ASYNC_CALLS.insert((loop, future, error_class, lift_func))
+    try:
+        rust_call(fn, *args, _complete_async_call, handle)
+    except:
+        # Rust never got as far as starting the future, so it won't call us back.
+        _PENDING_ASYNC_CALLS.remove(handle)
+        raise
+    return future
+
+def _py_complete_async_call(handle, code, rbuf):
+    loop, future, error_class, lift_func = _PENDING_ASYNC_CALLS.remove(handle)
+    # ctypes only lends us the struct for the duration of the callback, so take a copy.
+    rbuf = RustBuffer(rbuf.capacity, rbuf.len, rbuf.data)
+    try:
+        loop.call_soon_threadsafe(_resolve_async_call, future, error_class, lift_func, code, rbuf)
+    except RuntimeError:
+        # The event loop has been closed, so there's nobody left to receive the result.
+        rbuf.free()
+
+def _resolve_async_call(future, error_class, lift_func, code, rbuf):
+    if future.cancelled():
+        rbuf.free()
+        return
+    try:
+        future.set_result(_lift_async_result(error_class, lift_func, code, rbuf))
+    except Exception as e:
+        future.set_exception(e)
+
+def _lift_async_result(error_class, lift_func, code, rbuf):
+    _check_call_status(error_class, RustCallStatus(code=code, error_buf=rbuf))
+    try:
+        if lift_func is not None:
+            return lift_func(RustBufferStream(rbuf))
+    finally:
+        rbuf.free()
+
+# The ctypes function pointer must stay alive for as long as rust might call it,
+# so we keep it in a module-level variable.
+_complete_async_call = FUTURE_CALLBACK_T(_py_complete_async_call)
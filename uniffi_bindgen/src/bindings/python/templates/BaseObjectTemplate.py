@@ -0,0 +1,14 @@
+{#
+# Interfaces that other interfaces inherit from are base classes, whose methods are overridden
+# by the classes for the inheriting interfaces. They can't be instantiated themselves.
+#}
+class {{ obj.name()|class_name_py }}({% match obj.parent() %}{% when Some with (parent) %}{{ parent|class_name_py }}{% else %}object{% endmatch %}):
+    {%- call py::docstring(obj, 4) %}
+    {%- for meth in obj.methods() %}
+    {%- if meth.inherited_from().is_none() %}
+
+    def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        raise NotImplementedError
+    {%- endif %}
+    {%- endfor %}
@@ -0,0 +1,33 @@
+# Magic number for the Rust proxy to call using the same mechanism as every other method,
+# to free the callback once it's dropped by Rust.
+IDX_CALLBACK_FREE = 0
+
+class CallbackInternals:
+    """
+    Holds the objects implementing a callback interface, along with the
+    ForeignCallback through which rust calls methods on them.
+    """
+
+    def __init__(self, foreign_callback):
+        self._foreign_callback = foreign_callback
+        self._handle_map = ConcurrentHandleMap()
+
+    def register(self, init_callback):
+        rust_call(init_callback, self._foreign_callback)
+
+    def get(self, handle):
+        return self._handle_map.get(handle)
+
+    def drop(self, handle):
+        self._handle_map.remove(handle)
+
+    def lower(self, cb):
+        return self._handle_map.insert(cb)
+
+    def lowerInto(self, cb, buf):
+        buf.putLong(self.lower(cb))
+
+# The signature of the function through which rust calls back into Python:
+# it receives an object handle, a method index and the serialized arguments,
+# writes any return value through the given pointer, and returns zero on success.
+FOREIGN_CALLBACK_T = ctypes.CFUNCTYPE(ctypes.c_int32, ctypes.c_uint64, ctypes.c_uint32, RustBuffer, ctypes.POINTER(RustBuffer))
@@ -0,0 +1,72 @@
+{% let class_name = cbi.name()|class_name_py -%}
+{% let internals = format!("CallbackInterface{}Internals", class_name) -%}
+# Declaration and helpers for the {{ class_name }} callback interface.
+
+class {{ class_name }}:
+    {% for meth in cbi.methods() -%}
+    def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        raise NotImplementedError
+
+    {% endfor %}
+
+def py_foreignCallback{{ class_name }}(handle, method, args, buf_ptr):
+    {% for meth in cbi.methods() -%}
+    {% let method_name = format!("invoke_{}", meth.name())|fn_name_py %}
+    def {{ method_name }}(python_callback, args):
+        {%- if meth.arguments().len() > 0 %}
+        buf = RustBufferStream(args)
+        {%- endif %}
+        {% match meth.return_type() -%}
+        {%- when Some with (return_type) -%}
+        rval = python_callback.{{ meth.name()|fn_name_py }}(
+        {%- else -%}
+        python_callback.{{ meth.name()|fn_name_py }}(
+        {%- endmatch %}
+            {% for arg in meth.arguments() -%}
+            {{ "buf"|lift_from_py(arg.type_()) }}
+            {%- if !loop.last %}, {% endif %}
+            {% endfor -%}
+        )
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        builder = RustBufferBuilder()
+        try:
+            {{ "rval"|lower_into_py("builder", return_type) }}
+        except:
+            builder.discard()
+            raise
+        return builder.finalize()
+        {%- else %}
+        return RustBuffer()
+        {%- endmatch %}
+    {% endfor %}
+
+    try:
+        cb = {{ internals }}.get(handle)
+        if cb is None:
+            raise InternalError("No callback in handlemap; this is a uniffi bug")
+
+        if method == IDX_CALLBACK_FREE:
+            {{ internals }}.drop(handle)
+            return 0
+
+        {% for meth in cbi.methods() -%}
+        {% let method_name = format!("invoke_{}", meth.name())|fn_name_py -%}
+        if method == {{ loop.index }}:
+            buf_ptr[0] = {{ method_name }}(cb, args)
+            return 0
+
+        {% endfor %}
+        # This should never happen, because rust only ever calls the methods declared in the IDL.
+        return -1
+    except Exception:
+        # There's no way to surface the exception to the rust caller, which will panic.
+        return -1
+    finally:
+        args.free()
+
+# The ctypes function pointer must stay alive for as long as rust might call it,
+# so we keep it in a module-level variable.
+foreignCallback{{ class_name }} = FOREIGN_CALLBACK_T(py_foreignCallback{{ class_name }})
+{{ internals }} = CallbackInternals(foreignCallback{{ class_name }})
+{{ internals }}.register(_UniFFILib.{{ cbi.ffi_init_callback().name() }})
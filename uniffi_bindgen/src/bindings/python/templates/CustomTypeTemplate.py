@@ -0,0 +1,35 @@
+{%- let canonical_type_name = type_.canonical_name()|class_name_py %}
+# Helpers for the custom type {{ name }}, which is passed over the FFI as {{ builtin.canonical_name() }}.
+{%- match config.bindings.custom_type(name) %}
+{%- when Some with (custom) %}
+
+def lift{{ canonical_type_name }}(v):
+    builtin_value = {{ "v"|lift_py(builtin) }}
+    return {{ custom.into_custom_expr("builtin_value") }}
+
+def liftFrom{{ canonical_type_name }}(buf):
+    builtin_value = {{ "buf"|lift_from_py(builtin) }}
+    return {{ custom.into_custom_expr("builtin_value") }}
+
+def lower{{ canonical_type_name }}(v):
+    builtin_value = {{ custom.from_custom_expr("v") }}
+    return {{ "builtin_value"|lower_py(builtin) }}
+
+def lowerInto{{ canonical_type_name }}(v, buf):
+    builtin_value = {{ custom.from_custom_expr("v") }}
+    {{ "builtin_value"|lower_into_py("buf", builtin) }}
+{%- when None %}
+
+def lift{{ canonical_type_name }}(v):
+    return {{ "v"|lift_py(builtin) }}
+
+def liftFrom{{ canonical_type_name }}(buf):
+    return {{ "buf"|lift_from_py(builtin) }}
+
+def lower{{ canonical_type_name }}(v):
+    return {{ "v"|lower_py(builtin) }}
+
+def lowerInto{{ canonical_type_name }}(v, buf):
+    {{ "v"|lower_into_py("buf", builtin) }}
+{%- endmatch %}
+
@@ -1,4 +1,134 @@
+{#
+# Python has a built-in `enum` module which is nice to use, but doesn't support
+# variants with associated data. So, we switch here, and generate a stdlib `enum`
+# for flat enums and a little class hierarchy for enums with associated data.
+#}
+
+{% if e.is_flat() %}
+
 class {{ e.name() }}(enum.Enum):
+    {%- call py::docstring(e, 4) %}
     {% for variant in e.variants() -%}
-    {{ variant|enum_name_py }} = {{ loop.index }}
-    {% endfor -%}
\ No newline at end of file
+    {{ variant.name()|enum_name_py }} = {{ loop.index }}
+    {%- call py::docstring(variant, 4) %}
+    {% endfor %}
+
+    @classmethod
+    def _coerce(cls, v):
+        return cls(v)
+
+    @classmethod
+    def _lift(cls, rbuf):
+        return cls._liftFrom(RustBufferStream(rbuf))
+
+    @classmethod
+    def _liftFrom(cls, buf):
+        return cls(buf.getInt())
+
+    @classmethod
+    def _lower(cls, v):
+        buf = RustBufferBuilder()
+        try:
+            cls._lowerInto(v, buf)
+            return buf.finalize()
+        except Exception:
+            buf.discard()
+            raise
+
+    @classmethod
+    def _lowerInto(cls, v, buf):
+        buf.putInt(v.value)
+
+{% else %}
+
+class {{ e.name() }}(object):
+    {%- call py::docstring(e, 4) %}
+    def __init__(self):
+        raise RuntimeError("{{ e.name() }} cannot be instantiated directly")
+
+    # Each enum variant is a nested class of the enum itself.
+    {% for variant in e.variants() -%}
+    class {{ variant.name()|enum_name_py }}(object):
+        {%- call py::docstring(variant, 8) %}
+        def __init__(self,{% for field in variant.fields() %}{{ field.name()|var_name_py }}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
+            {%- call py::deprecated(variant, 12) %}
+            {% if variant.has_fields() %}
+            {%- for field in variant.fields() %}
+            self.{{ field.name()|var_name_py }} = {{ field.name()|var_name_py }}
+            {%- endfor %}
+            {% else %}
+            pass
+            {% endif %}
+
+        def __str__(self):
+            return "{{ e.name() }}.{{ variant.name()|enum_name_py }}({% for field in variant.fields() %}{{ field.name() }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in variant.fields() %}self.{{ field.name()|var_name_py }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+
+        def __eq__(self, other):
+            if not other.is_{{ variant.name()|var_name_py }}():
+                return False
+            {%- for field in variant.fields() %}
+            if self.{{ field.name()|var_name_py }} != other.{{ field.name()|var_name_py }}:
+                return False
+            {%- endfor %}
+            return True
+    {% endfor %}
+
+    # For each variant, we have an `is_NAME` method for easily checking
+    # whether an instance is that variant.
+    {% for variant in e.variants() -%}
+    def is_{{ variant.name()|var_name_py }}(self):
+        return isinstance(self, {{ e.name() }}.{{ variant.name()|enum_name_py }})
+    {% endfor %}
+
+    @classmethod
+    def _coerce(cls, v):
+        # TODO: maybe we could do a bit of duck-typing here, details TBD
+        assert isinstance(v, {{ e.name() }})
+        return v
+
+    @classmethod
+    def _lift(cls, rbuf):
+        return cls._liftFrom(RustBufferStream(rbuf))
+
+    @classmethod
+    def _liftFrom(cls, buf):
+        variant = buf.getInt()
+        {% for variant in e.variants() -%}
+        if variant == {{ loop.index }}:
+            return cls.{{ variant.name()|enum_name_py }}(
+                {%- for field in variant.fields() %}
+                {{ "buf"|lift_from_py(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+                {%- endfor %}
+            )
+        {% endfor -%}
+        raise InternalError("Unexpected variant tag for {{ e.name() }}")
+
+    @classmethod
+    def _lower(cls, v):
+        buf = RustBufferBuilder()
+        try:
+            cls._lowerInto(v, buf)
+            return buf.finalize()
+        except Exception:
+            buf.discard()
+            raise
+
+    @classmethod
+    def _lowerInto(cls, v, buf):
+        {% for variant in e.variants() -%}
+        if v.is_{{ variant.name()|var_name_py }}():
+            buf.putInt({{ loop.index }})
+            {%- for field in variant.fields() %}
+            {{ "(v.{})"|format(field.name())|lower_into_py("buf", field.type_()) }}
+            {%- endfor %}
+            return
+        {% endfor %}
+
+# Now, a little trick - we make each nested variant class be a subclass of the main
+# enum class, so that method calls and instance checks etc will work intuitively.
+# We might be able to do this a little more neatly with a metaclass, but this'll do.
+{% for variant in e.variants() -%}
+{{ e.name() }}.{{ variant.name()|enum_name_py }} = type("{{ e.name() }}.{{ variant.name()|enum_name_py }}", ({{ e.name() }}.{{ variant.name()|enum_name_py }}, {{ e.name() }},), {})
+{% endfor %}
+
+{% endif %}
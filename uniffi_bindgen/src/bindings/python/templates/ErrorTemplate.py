@@ -1,49 +1,113 @@
-class RustError(ctypes.Structure):
+# The status of a call into rust, passed as the final argument of every FFI function.
+class RustCallStatus(ctypes.Structure):
     _fields_ = [
-        ("code", ctypes.c_int32),
-        ("message", ctypes.c_void_p),
+        ("code", ctypes.c_int8),
+        ("error_buf", RustBuffer),
     ]
 
-    def free(self):
-        rust_call_with_error(InternalError, _UniFFILib.{{ ci.ffi_string_free().name() }}, self.message)
+    # These match the values from the uniffi::rustcalls module
+    CALL_SUCCESS = 0
+    CALL_ERROR = 1
+    CALL_PANIC = 2
 
     def __str__(self):
-        return "RustError(code={}, message={})".format(
-            self.code,
-            str(ctypes.cast(self.message, ctypes.c_char_p).value, "utf-8"),
-        )
+        if self.code == RustCallStatus.CALL_SUCCESS:
+            return "RustCallStatus(CALL_SUCCESS)"
+        elif self.code == RustCallStatus.CALL_ERROR:
+            return "RustCallStatus(CALL_ERROR)"
+        elif self.code == RustCallStatus.CALL_PANIC:
+            return "RustCallStatus(CALL_PANIC)"
+        else:
+            return "RustCallStatus(<invalid code>)"
 
 class InternalError(Exception):
-    @staticmethod
-    def raise_err(code, message):
-        raise InternalError(message)
+    pass
 
 {% for e in ci.iter_error_definitions() %}
-class {{ e.name()|class_name_py }}:
-    {%- for value in e.values() %}
-    class {{ value|class_name_py }}(Exception):
+class {{ e.name()|class_name_py }}(Exception):
+    # Each variant is a nested class of the error itself.
+    {%- for variant in e.variants() %}
+    {%- if e.is_flat() %}
+    # It just carries a string error message, so no special implementation is necessary.
+    class {{ variant.name()|class_name_py }}(Exception):
         pass
+    {%- else %}
+    class {{ variant.name()|class_name_py }}(Exception):
+        def __init__(self{% for field in variant.fields() %}, {{ field.name()|var_name_py }}{% endfor %}):
+            {%- for field in variant.fields() %}
+            self.{{ field.name()|var_name_py }} = {{ field.name()|var_name_py }}
+            {%- endfor %}
+            super().__init__(", ".join([
+                {%- for field in variant.fields() %}
+                "{{ field.name()|var_name_py }}={!r}".format({{ field.name()|var_name_py }}),
+                {%- endfor %}
+            ]))
+    {%- endif %}
     {%- endfor %}
 
-    @staticmethod
-    def raise_err(code, message):
-        {%- for value in e.values() %}
-        if code == {{ loop.index }}:
-            raise {{ e.name()|class_name_py }}.{{ value|class_name_py }}(message)
-        {% endfor %}
-        raise Exception("Unknown error code")
+    @classmethod
+    def _lift(cls, rbuf):
+        try:
+            return cls._liftFrom(RustBufferStream(rbuf))
+        finally:
+            rbuf.free()
+
+    @classmethod
+    def _liftFrom(cls, buf):
+        variant = buf.getInt()
+        {%- for variant in e.variants() %}
+        if variant == {{ loop.index }}:
+            {%- if e.is_flat() %}
+            return cls.{{ variant.name()|class_name_py }}(buf.getString())
+            {%- else %}
+            return cls.{{ variant.name()|class_name_py }}(
+                {%- for field in variant.fields() %}
+                {{ "buf"|lift_from_py(field.type_()) }},
+                {%- endfor %}
+            )
+            {%- endif %}
+        {%- endfor %}
+        raise InternalError("Unexpected variant tag for {{ e.name() }}")
+
+# Now, a little trick - we make each nested variant class be a subclass of the main
+# error class, so that catching `{{ e.name()|class_name_py }}` catches all of its variants.
+{%- for variant in e.variants() %}
+{{ e.name()|class_name_py }}.{{ variant.name()|class_name_py }} = type("{{ e.name()|class_name_py }}.{{ variant.name()|class_name_py }}", ({{ e.name()|class_name_py }}.{{ variant.name()|class_name_py }}, {{ e.name()|class_name_py }}), {})
+{%- endfor %}
 {% endfor %}
 
+def rust_call(fn, *args):
+    # Call a rust function that isn't expected to return one of the errors declared in the IDL.
+    return rust_call_with_error(None, fn, *args)
+
 def rust_call_with_error(error_class, fn, *args):
-    error = RustError()
-    error.code = 0
+    # Call a rust function and handle any errors.
+    #
+    # Use this when the rust function returns a Result<>; `error_class` must be set to the
+    # error class that corresponds to the result.
+    call_status = RustCallStatus(code=RustCallStatus.CALL_SUCCESS, error_buf=RustBuffer(0, 0, None))
 
-    args_with_error = args + (ctypes.byref(error),)
+    args_with_error = args + (ctypes.byref(call_status),)
     result = fn(*args_with_error)
-    if error.code != 0:
-        message = str(error)
-        error.free()
-
-        error_class.raise_err(error.code, message)
-    
+    _check_call_status(error_class, call_status)
     return result
+
+def _check_call_status(error_class, call_status):
+    # Raise the appropriate exception if a call into rust didn't succeed.
+    if call_status.code == RustCallStatus.CALL_SUCCESS:
+        return
+    elif call_status.code == RustCallStatus.CALL_ERROR:
+        if error_class is None:
+            call_status.error_buf.free()
+            raise InternalError("rust_call_with_error: CALL_ERROR, but no error class set")
+        else:
+            raise error_class._lift(call_status.error_buf)
+    elif call_status.code == RustCallStatus.CALL_PANIC:
+        # When the rust code sees a panic, it tries to send back the message in a RustBuffer.
+        try:
+            msg = call_status.error_buf.data[0:call_status.error_buf.len].decode('utf-8')
+        finally:
+            call_status.error_buf.free()
+        raise InternalError(msg)
+    else:
+        raise InternalError("Invalid RustCallStatus code: {}".format(call_status.code))
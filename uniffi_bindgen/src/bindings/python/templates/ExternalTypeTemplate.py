@@ -0,0 +1,21 @@
+{%- let canonical_type_name = type_.canonical_name()|class_name_py %}
+# Helpers for the external type {{ name }}, which is defined by the `{{ namespace }}` component.
+# Its bindings know how to read and write it, but we use our own buffers to pass it over the FFI.
+
+def lift{{ canonical_type_name }}(rbuf):
+    return {{ name }}._liftFrom(RustBufferStream(rbuf))
+
+def liftFrom{{ canonical_type_name }}(buf):
+    return {{ name }}._liftFrom(buf)
+
+def lower{{ canonical_type_name }}(v):
+    buf = RustBufferBuilder()
+    try:
+        {{ name }}._lowerInto(v, buf)
+        return buf.finalize()
+    except Exception:
+        buf.discard()
+        raise
+
+def lowerInto{{ canonical_type_name }}(v, buf):
+    {{ name }}._lowerInto(v, buf)
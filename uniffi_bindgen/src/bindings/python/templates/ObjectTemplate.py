@@ -1,30 +1,124 @@
-class {{ obj.name()|class_name_py }}(object):
-    # XXX TODO: support for multiple constructors...
-    {%- for cons in obj.constructors() %}
+class {{ obj.name()|class_name_py }}({% match obj.parent() %}{% when Some with (parent) %}{{ parent|class_name_py }}{% else %}object{% endmatch %}):
+    {%- call py::docstring(obj, 4) %}
+    {%- for c in obj.constants() %}
+    {{ c.name()|enum_name_py }} = {{ c.value()|literal_py(c.type_()) }}
+    {%- endfor %}
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
+        {%- call py::deprecated(cons, 8) %}
         {%- call py::coerce_args_extra_indent(cons) %}
         self._handle = {% call py::to_ffi_call(cons) %}
-    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
 
     def __del__(self):
-        rust_call_with_error(
-            InternalError,
+        rust_call(
             _UniFFILib.{{ obj.ffi_object_free().name() }},
             self._handle
         )
 
+    {% for cons in obj.alternate_constructors() -%}
+    @classmethod
+    def {{ cons.name()|fn_name_py }}(cls, {% call py::arg_list_decl(cons) %}):
+        {%- call py::deprecated(cons, 8) %}
+        {%- call py::coerce_args_extra_indent(cons) %}
+        # Call the (fallible) function before creating any half-baked object instances.
+        handle = {% call py::to_ffi_call(cons) %}
+        return cls._lift(handle)
+    {% endfor %}
+
+    @classmethod
+    def _lift(cls, handle):
+        # Bypass `__init__`, since the Rust object already exists.
+        inst = cls.__new__(cls)
+        inst._handle = handle
+        return inst
+
+    @classmethod
+    def _liftFrom(cls, buf):
+        return cls._lift(buf.getLong())
+
+    @classmethod
+    def _lower(cls, v):
+        # Rust gets its own reference to the object, so `v` stays usable after passing it.
+        assert isinstance(v, cls)
+        return v._handle
+
+    @classmethod
+    def _lowerInto(cls, v, buf):
+        buf.putLong(cls._lower(v))
+
     {% for meth in obj.methods() -%}
+    {%- if meth.is_async() -%}
+    {%- if meth.is_static() %}
+    @staticmethod
+    async def {{ meth.name()|fn_name_py }}({% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        return await {% call py::to_async_ffi_call("", meth) %}
+    {%- else %}
+    async def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        return await {% call py::to_async_ffi_call("self._handle", meth) %}
+    {%- endif %}
+    {% else if meth.is_static() -%}
+    @staticmethod
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) %}
+    def {{ meth.name()|fn_name_py }}({% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        _retval = {% call py::to_ffi_call(meth) %}
+        return {{ "_retval"|lift_py(return_type) }}
+
+    {%- when None %}
+    def {{ meth.name()|fn_name_py }}({% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
+        {%- call py::coerce_args_extra_indent(meth) %}
+        {% call py::to_ffi_call(meth) %}
+    {% endmatch %}
+    {%- else -%}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
     def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
         {%- call py::coerce_args_extra_indent(meth) %}
         _retval = {% call py::to_ffi_call_with_prefix("self._handle", meth) %}
         return {{ "_retval"|lift_py(return_type) }}
     
     {%- when None -%}
     def {{ meth.name()|fn_name_py }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated(meth, 8) %}
         {%- call py::coerce_args_extra_indent(meth) %}
         {% call py::to_ffi_call_with_prefix("self._handle", meth) %}
     {% endmatch %}
+    {%- endif %}
     {% endfor %}
+
+    {%- for prop in obj.properties() %}
+    {%- let getter = prop.getter() %}
+
+    @property
+    def {{ prop.name()|fn_name_py }}(self):
+        _retval = {% call py::to_ffi_call_with_prefix("self._handle", getter) %}
+        return {{ "_retval"|lift_py(prop.type_()) }}
+    {%- match prop.setter() %}
+    {%- when Some with (setter) %}
+
+    @{{ prop.name()|fn_name_py }}.setter
+    def {{ prop.name()|fn_name_py }}(self, value):
+        {%- call py::coerce_args_extra_indent(setter) %}
+        {% call py::to_ffi_call_with_prefix("self._handle", setter) %}
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
@@ -1,15 +1,42 @@
 class {{ rec.name() }}(object):
-    def __init__(self,{% for field in rec.fields() %}{{ field.name()|var_name_py }}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
+    {%- call py::docstring(rec, 4) %}
+    {#- Default values are filled in when the record is created, rather than in the argument list,
+    // so that mutable ones like `[]` aren't shared between all the instances. #}
+    def __init__(self,{% for field in rec.fields() %}{{ field.name()|var_name_py }}
+        {%- if field.default_value().is_some() %} = DEFAULT{% endif %}
+        {%- if loop.last %}{% else %}, {% endif %}{% endfor %}):
         {%- for field in rec.fields() %}
-        self.{{ field.name()|var_name_py }} = {{ field.name()|var_name_py }}
+        {%- match field.default_value() %}
+        {%- when Some with(literal) %}
+        if {{ field.name()|var_name_py }} is DEFAULT:
+            {{ field.name()|var_name_py }} = {{ literal|literal_py(field.type_()) }}
+        {%- else %}
+        {%- endmatch %}
+        self.{{ field|field_attr_py }} = {{ field.name()|var_name_py }}
+        {%- call py::docstring(field, 8) %}
         {%- endfor %}
+    {%- for field in rec.fields() %}
+    {%- if field.deprecated().is_some() %}
+
+    @property
+    def {{ field.name()|var_name_py }}(self):
+        {%- call py::docstring(field, 8) %}
+        {%- call py::deprecated(field, 8) %}
+        return self.{{ field|field_attr_py }}
+
+    @{{ field.name()|var_name_py }}.setter
+    def {{ field.name()|var_name_py }}(self, value):
+        {%- call py::deprecated(field, 8) %}
+        self.{{ field|field_attr_py }} = value
+    {%- endif %}
+    {%- endfor %}
 
     def __str__(self):
-        return "{{ rec.name() }}({% for field in rec.fields() %}{{ field.name() }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name() }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+        return "{{ rec.name() }}({% for field in rec.fields() %}{{ field.name() }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field|field_attr_py }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
 
     def __eq__(self, other):
         {%- for field in rec.fields() %}
-        if self.{{ field.name()|var_name_py }} != other.{{ field.name()|var_name_py }}:
+        if self.{{ field|field_attr_py }} != other.{{ field|field_attr_py }}:
             return False
         return True
         {%- endfor %}
@@ -45,5 +72,11 @@ class {{ rec.name() }}(object):
     @classmethod
     def _lowerInto(cls, v, buf):
         {%- for field in rec.fields() %}
+        {%- if field.deprecated().is_some() %}
+        {#- Read the value directly, so that lowering doesn't warn about the deprecation. #}
+        {{ field.name()|var_name_py }} = v.{{ field|field_attr_py }}
+        {{ field.name()|lower_into_py("buf", field.type_()) }}
+        {%- else %}
         {{ "(v.{})"|format(field.name())|lower_into_py("buf", field.type_()) }}
+        {%- endif %}
         {%- endfor %}
@@ -1,5 +1,8 @@
 # Helpers for lifting/lowering primitive data types from/to a bytebuffer.
 
+# Timestamps are exposed to python as timezone-aware datetimes, in UTC.
+UNIX_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
 class RustBufferStream(object):
     """Helper for structured reading of values for a RustBuffer."""
 
@@ -24,6 +27,27 @@ class RustBufferStream(object):
     def getByte(self):
         return self._unpack_from(1, ">c")
 
+    def getI8(self):
+        return self._unpack_from(1, ">b")
+
+    def getU8(self):
+        return self._unpack_from(1, ">B")
+
+    def getI16(self):
+        return self._unpack_from(2, ">h")
+
+    def getU16(self):
+        return self._unpack_from(2, ">H")
+
+    def getI32(self):
+        return self._unpack_from(4, ">i")
+
+    def getI64(self):
+        return self._unpack_from(8, ">q")
+
+    def getFloat(self):
+        return self._unpack_from(4, ">f")
+
     def getDouble(self):
         return self._unpack_from(8, ">d")
 
@@ -37,6 +61,22 @@ class RustBufferStream(object):
         numBytes = self.getInt()
         return self._unpack_from(numBytes, ">{}s".format(numBytes)).decode('utf-8')
 
+    def getBytes(self):
+        numBytes = self.getInt()
+        return self._unpack_from(numBytes, ">{}s".format(numBytes))
+
+    # Timestamps are passed as the seconds since the unix epoch (rounded down), followed by the
+    # nanoseconds after that second. Python's datetimes only have microsecond precision.
+    def getTimestamp(self):
+        seconds = self.getI64()
+        nanoseconds = self.getInt()
+        return UNIX_EPOCH + datetime.timedelta(seconds=seconds, microseconds=nanoseconds // 1000)
+
+    def getDuration(self):
+        seconds = self.getLong()
+        nanoseconds = self.getInt()
+        return datetime.timedelta(seconds=seconds, microseconds=nanoseconds // 1000)
+
 
 class RustBufferBuilder(object):
     """Helper for structured writing of values into a RustBuffer."""
@@ -70,6 +110,27 @@ class RustBufferBuilder(object):
     def putByte(self, v):
         self._pack_into(1, ">c", v)
 
+    def putI8(self, v):
+        self._pack_into(1, ">b", v)
+
+    def putU8(self, v):
+        self._pack_into(1, ">B", v)
+
+    def putI16(self, v):
+        self._pack_into(2, ">h", v)
+
+    def putU16(self, v):
+        self._pack_into(2, ">H", v)
+
+    def putI32(self, v):
+        self._pack_into(4, ">i", v)
+
+    def putI64(self, v):
+        self._pack_into(8, ">q", v)
+
+    def putFloat(self, v):
+        self._pack_into(4, ">f", v)
+
     def putDouble(self, v):
         self._pack_into(8, ">d", v)
 
@@ -85,6 +146,25 @@ class RustBufferBuilder(object):
         self.putInt(numBytes)
         self._pack_into(numBytes, ">{}s".format(numBytes), valueBytes)
 
+    def putBytes(self, v):
+        numBytes = len(v)
+        self.putInt(numBytes)
+        with self._reserve(numBytes):
+            ctypes.memmove(ctypes.addressof(self.rbuf.data.contents) + self.rbuf.len, v, numBytes)
+
+    def putTimestamp(self, v):
+        # Timedeltas are normalized so that only the days can be negative, which gives us
+        # the seconds rounded down, as required.
+        delta = v - UNIX_EPOCH
+        self.putI64(delta.days * 86400 + delta.seconds)
+        self.putInt(delta.microseconds * 1000)
+
+    def putDuration(self, v):
+        if v < datetime.timedelta(0):
+            raise ValueError("Invalid duration, must be non-negative")
+        self.putLong(v.days * 86400 + v.seconds)
+        self.putInt(v.microseconds * 1000)
+
 
 def liftSequence(rbuf, liftFrom):
     return liftFromSequence(RustBufferStream(rbuf), liftFrom)
@@ -93,7 +173,7 @@ def liftFromSequence(buf, liftFrom):
     seq_len = buf.getInt()
     seq = []
     for i in range(0, seq_len):
-        seq.append(listFrom(buf))
+        seq.append(liftFrom(buf))
     return seq
 
 def liftOptional(rbuf, liftFrom):
@@ -104,8 +184,68 @@ def liftFromOptional(buf, liftFrom):
         return None
     return liftFrom(buf)
 
-def liftString(cPtr):
-    # TODO: update strings to lift from a `RustBuffer`.
-    # There's currently no test coverage for this, so it can come in a separate PR
-    # that cleans up a bunch of python lifting/lowering stuff.
-    raise NotImplementedError
+def liftMap(rbuf, liftKeyFrom, liftValueFrom):
+    return liftFromMap(RustBufferStream(rbuf), liftKeyFrom, liftValueFrom)
+
+def liftFromMap(buf, liftKeyFrom, liftValueFrom):
+    map_len = buf.getInt()
+    items = {}
+    for i in range(0, map_len):
+        key = liftKeyFrom(buf)
+        items[key] = liftValueFrom(buf)
+    return items
+
+def lowerMap(v, lowerKeyInto, lowerValueInto):
+    buf = RustBufferBuilder()
+    try:
+        lowerIntoMap(v, buf, lowerKeyInto, lowerValueInto)
+        return buf.finalize()
+    except Exception:
+        buf.discard()
+        raise
+
+def lowerIntoMap(v, buf, lowerKeyInto, lowerValueInto):
+    buf.putInt(len(v))
+    for (key, value) in v.items():
+        lowerKeyInto(buf, key)
+        lowerValueInto(buf, value)
+
+# Bytes are passed as the raw contents of a RustBuffer, so we can copy them in one go.
+def liftBytes(rbuf):
+    try:
+        return ctypes.string_at(rbuf.data, rbuf.len)
+    finally:
+        rbuf.free()
+
+def lowerBytes(v):
+    rbuf = RustBuffer.alloc(len(v))
+    ctypes.memmove(rbuf.data, v, len(v))
+    return rbuf
+
+def liftTimestamp(rbuf):
+    return RustBufferStream(rbuf).getTimestamp()
+
+def liftDuration(rbuf):
+    return RustBufferStream(rbuf).getDuration()
+
+def lowerTimestamp(v):
+    buf = RustBufferBuilder()
+    try:
+        buf.putTimestamp(v)
+        return buf.finalize()
+    except Exception:
+        buf.discard()
+        raise
+
+def lowerDuration(v):
+    buf = RustBufferBuilder()
+    try:
+        buf.putDuration(v)
+        return buf.finalize()
+    except Exception:
+        buf.discard()
+        raise
+
+def liftString(rbuf):
+    # Strings are passed as a `RustBuffer` holding their utf-8 bytes.
+    return liftBytes(rbuf).decode('utf-8')
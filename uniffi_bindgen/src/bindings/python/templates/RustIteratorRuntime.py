@@ -0,0 +1,63 @@
+# Helpers for `[Iterator]` functions and methods.
+#
+# These return a handle to a rust iterator, from which we take the items in batches as they're
+# needed, rather than rust producing all of them up front. A batch that's shorter than we asked
+# for means that the iterator is exhausted, at which point we free it straight away.
+
+class RustIterator(object):
+    BATCH_SIZE = 100
+
+    def __init__(self, handle, next_batch, free, liftFrom):
+        self._handle = handle
+        self._next_batch = next_batch
+        self._free = free
+        self._liftFrom = liftFrom
+        self._batch = []
+        self._offset = 0
+
+    def __iter__(self):
+        return self
+
+    def __next__(self):
+        if self._offset == len(self._batch):
+            if self._handle is None:
+                raise StopIteration
+            self._take_batch()
+            if not self._batch:
+                raise StopIteration
+        item = self._batch[self._offset]
+        self._offset += 1
+        return item
+
+    def _take_batch(self):
+        rbuf = rust_call(self._next_batch, self._handle, self.BATCH_SIZE)
+        try:
+            self._batch = liftFromSequence(RustBufferStream(rbuf), self._liftFrom)
+            self._offset = 0
+        finally:
+            rbuf.free()
+        if len(self._batch) < self.BATCH_SIZE:
+            self.close()
+
+    def close(self):
+        """Free the rust iterator, without taking any more items from it."""
+        if self._handle is not None:
+            rust_call(self._free, self._handle)
+            self._handle = None
+
+    def __del__(self):
+        self.close()
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Iterator with (item_type) %}
+
+def lift{{ type_.canonical_name()|class_name_py }}(handle):
+    return RustIterator(
+        handle,
+        _UniFFILib.{{ ci.ffi_iterator_next(item_type).name() }},
+        _UniFFILib.{{ ci.ffi_iterator_free(item_type).name() }},
+        lambda buf: {{ "buf"|lift_from_py(item_type) }},
+    )
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
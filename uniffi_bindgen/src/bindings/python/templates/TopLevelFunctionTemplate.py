@@ -1,7 +1,17 @@
+{%- if func.is_async() %}
+
+async def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::docstring(func, 4) %}
+    {%- call py::deprecated(func, 4) %}
+    {%- call py::coerce_args(func) %}
+    return await {% call py::to_async_ffi_call("", func) %}
+{% else -%}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
 def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::docstring(func, 4) %}
+    {%- call py::deprecated(func, 4) %}
     {%- call py::coerce_args(func) %}
     _retval = {% call py::to_ffi_call(func) %}
     return {{ "_retval"|lift_py(return_type) }}
@@ -9,6 +19,9 @@ def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
 {% when None -%}
 
 def {{ func.name()|fn_name_py }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::docstring(func, 4) %}
+    {%- call py::deprecated(func, 4) %}
     {%- call py::coerce_args(func) %}
     {% call py::to_ffi_call(func) %}
-{% endmatch %}
\ No newline at end of file
+{% endmatch %}
+{%- endif %}
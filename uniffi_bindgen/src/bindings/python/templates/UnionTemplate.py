@@ -0,0 +1,65 @@
+{#
+# Python doesn't have sum types, so like for enums with associated data, we generate
+# a little class hierarchy with a nested class wrapping a value of each member type.
+#}
+
+class {{ name|class_name_py }}(object):
+    def __init__(self):
+        raise RuntimeError("{{ name|class_name_py }} cannot be instantiated directly")
+
+    # Each member type has a nested class, holding a value of that type.
+    {% for member in members -%}
+    class {{ member.union_member_name()|class_name_py }}(object):
+        def __init__(self, value):
+            self.value = value
+
+        def __str__(self):
+            return "{{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }}({})".format(self.value)
+
+        def __eq__(self, other):
+            if not isinstance(other, {{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }}):
+                return False
+            return self.value == other.value
+    {% endfor %}
+
+    @classmethod
+    def _coerce(cls, v):
+        assert isinstance(v, {{ name|class_name_py }})
+        return v
+
+    @classmethod
+    def _lift(cls, rbuf):
+        return cls._liftFrom(RustBufferStream(rbuf))
+
+    @classmethod
+    def _liftFrom(cls, buf):
+        tag = buf.getInt()
+        {% for member in members -%}
+        if tag == {{ loop.index }}:
+            return cls.{{ member.union_member_name()|class_name_py }}({{ "buf"|lift_from_py(member) }})
+        {% endfor -%}
+        raise InternalError("Unexpected member tag for {{ name|class_name_py }}")
+
+    @classmethod
+    def _lower(cls, v):
+        buf = RustBufferBuilder()
+        try:
+            cls._lowerInto(v, buf)
+            return buf.finalize()
+        except Exception:
+            buf.discard()
+            raise
+
+    @classmethod
+    def _lowerInto(cls, v, buf):
+        {% for member in members -%}
+        if isinstance(v, {{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }}):
+            buf.putInt({{ loop.index }})
+            {{ "(v.value)"|lower_into_py("buf", member) }}
+            return
+        {% endfor %}
+
+# Make each nested member class be a subclass of the union class, so that instance checks work intuitively.
+{% for member in members -%}
+{{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }} = type("{{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }}", ({{ name|class_name_py }}.{{ member.union_member_name()|class_name_py }}, {{ name|class_name_py }},), {})
+{% endfor %}
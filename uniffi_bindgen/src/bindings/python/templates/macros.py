@@ -6,27 +6,39 @@
 
 {%- macro to_ffi_call(func) -%}
 
-rust_call_with_error(
-    {%- match func.throws() -%}
-    {%- when Some with (e) -%}
-    {{ e|class_name_py }}
-    {%- else -%}
-    InternalError
-    {%- endmatch -%},
+
+{%- match func.throws() -%}
+{%- when Some with (e) -%}
+rust_call_with_error({{ e|class_name_py }},
+{%- else -%}
+rust_call(
+{%- endmatch -%}
     _UniFFILib.{{ func.ffi_func().name() }}{% if func.arguments().len() > 0 %},{% endif %}{% call _arg_list_ffi_call(func) -%}
 )
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
-rust_call_with_error(
-    {%- match func.throws() -%}
-    {%- when Some with (e) -%}
-    {{ e|class_name_py }}
-    {%- else -%}
-    InternalError
-    {%- endmatch -%},
+
+{%- match func.throws() -%}
+{%- when Some with (e) -%}
+rust_call_with_error({{ e|class_name_py }},
+{%- else -%}
+rust_call(
+{%- endmatch -%}
     _UniFFILib.{{ func.ffi_func().name() }},{{- prefix }}{% if func.arguments().len() > 0 %},{% endif %}{% call _arg_list_ffi_call(func) %})
-)
+{%- endmacro -%}
+
+{#
+// Call an `[Async]` function, giving an asyncio future for its result.
+// The prefix holds any leading arguments such as the object handle, and may be empty.
+#}
+{%- macro to_async_ffi_call(prefix, func) -%}
+rust_call_async(
+    {%- match func.throws() %}{% when Some with (e) %}{{ e|class_name_py }}{% else %}None{% endmatch %},
+    {%- match func.return_type() %}{% when Some with (return_type) %} lambda buf: {{ "buf"|lift_from_py(return_type) }}{% else %} None{% endmatch %},
+    _UniFFILib.{{ func.ffi_func().name() }}
+    {%- if !prefix.is_empty() %}, {{ prefix }}{% endif %}
+    {%- if func.arguments().len() > 0 %},{% endif %}{% call _arg_list_ffi_call(func) %})
 {%- endmacro -%}
 
 {%- macro _arg_list_ffi_call(func) %}
@@ -44,6 +56,10 @@ rust_call_with_error(
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
         {{ arg.name()|var_name_py }}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_py(arg.type_()) }}
+        {%- else %}
+        {%- endmatch %}
         {%- if !loop.last %},{% endif -%}
     {%- endfor %}
 {%- endmacro %}
@@ -56,7 +72,7 @@ rust_call_with_error(
     {%- for arg in func.arguments() -%}
         {{ arg.type_()|type_ffi }},{##}
     {%- endfor %}
-    ctypes.POINTER(RustError)
+    ctypes.POINTER(RustCallStatus),
 {%- endmacro -%}
 
 {%- macro coerce_args(func) %}
@@ -70,3 +86,25 @@ rust_call_with_error(
         {{ arg.name()|coerce_py(arg.type_()) }}
         {%- endfor %}
 {%- endmacro -%}
+
+{#
+# Render the doc comment from the IDL for a definition, if it has one, as a docstring.
+#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with(docstring) %}
+{{ docstring|docstring_py(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#
+# Mark a `[Deprecated]` definition as such, by warning when it is used.
+#}
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some with(message) %}
+{{ message|deprecated_py(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
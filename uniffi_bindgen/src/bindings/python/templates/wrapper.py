@@ -1,5 +1,11 @@
 # This file was autogenerated by some hot garbage in the `uniffi` crate.
 # Trust me, you don't want to mess with it!
+{%- match ci.namespace_docstring() %}
+{%- when Some with(docstring) %}
+
+{{ docstring|docstring_py(0) }}
+{%- else %}
+{%- endmatch %}
 
 # Common helper code.
 #
@@ -14,20 +20,63 @@
 # helpers directly inline like we're doing here.
 
 import sys
+import asyncio
 import ctypes
 import enum
 import struct
 import contextlib
+import datetime
+import threading
+import warnings
+{%- for module in config.bindings.custom_type_imports() %}
+import {{ module }}
+{%- endfor %}
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::External with (name, namespace) %}
+from {{ namespace }} import {{ name }}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
 
 {% include "RustBufferTemplate.py" %}
 
 {% include "RustBufferHelper.py" %}
 
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Custom with (name, builtin) %}
+{% include "CustomTypeTemplate.py" %}
+{%- when Type::External with (name, namespace) %}
+{% include "ExternalTypeTemplate.py" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
 # Error definitions
 {% include "ErrorTemplate.py" %}
 
+{% if ci.iter_callback_interface_definitions().len() > 0 || ci.has_async_functions() -%}
+{% include "ConcurrentHandleMap.py" %}
+{%- endif %}
+
+{% if ci.iter_callback_interface_definitions().len() > 0 -%}
+{% include "CallbackInterfaceRuntime.py" %}
+{%- endif %}
+
+{% if ci.has_async_functions() -%}
+{% include "AsyncRuntime.py" %}
+{%- endif %}
+
+{% if ci.iter_iterator_item_types().len() > 0 -%}
+{% include "RustIteratorRuntime.py" %}
+{%- endif %}
+
 {% include "NamespaceLibraryTemplate.py" %}
 
+# Marks a record field that wasn't given, so that its default value can be filled in.
+DEFAULT = object()
+
 # Public interface members begin here.
 
 {% for e in ci.iter_enum_definitions() %}
@@ -38,12 +87,32 @@ import contextlib
 {% include "RecordTemplate.py" %}
 {% endfor %}
 
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Union with (name, members) %}
+{% include "UnionTemplate.py" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for c in ci.iter_constant_definitions() %}
+{{ c.name()|enum_name_py }} = {{ c.value()|literal_py(c.type_()) }}
+{%- endfor %}
+
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.py" %}
 {% endfor %}
 
 {% for obj in ci.iter_object_definitions() %}
+{%- if obj.is_base() %}
+{% include "BaseObjectTemplate.py" %}
+{%- else %}
 {% include "ObjectTemplate.py" %}
+{%- endif %}
+{% endfor %}
+
+{% for cbi in ci.iter_callback_interface_definitions() %}
+{% include "CallbackInterfaceTemplate.py" %}
 {% endfor %}
 
 __all__ = [
@@ -54,6 +123,16 @@ __all__ = [
     {%- for record in ci.iter_record_definitions() %}
     "{{ record.name()|class_name_py }}",
     {%- endfor %}
+    {%- for type_ in ci.iter_types() %}
+    {%- match type_ %}
+    {%- when Type::Union with (name, members) %}
+    "{{ name|class_name_py }}",
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+    {%- for c in ci.iter_constant_definitions() %}
+    "{{ c.name()|enum_name_py }}",
+    {%- endfor %}
     {%- for func in ci.iter_function_definitions() %}
     "{{ func.name()|fn_name_py }}",
     {%- endfor %}
@@ -63,6 +142,9 @@ __all__ = [
     {%- for e in ci.iter_error_definitions() %}
     "{{ e.name()|class_name_py }}",
     {%- endfor %}
+    {%- for cbi in ci.iter_callback_interface_definitions() %}
+    "{{ cbi.name()|class_name_py }}",
+    {%- endfor %}
 ]
 
 {% import "macros.py" as py %}
@@ -8,19 +8,20 @@ use anyhow::Result;
 use askama::Template;
 use heck::{CamelCase, MixedCase};
 
+use crate::bindings::LanguageConfig;
 use crate::interface::*;
 
-// Some config options for it the caller wants to customize the generated python.
-// Note that this can only be used to control details of the python *that do not affect the underlying component*,
+// Some config options for it the caller wants to customize the generated Swift.
+// Note that this can only be used to control details of the Swift *that do not affect the underlying component*,
 // sine the details of the underlying component are entirely determined by the `ComponentInterface`.
 pub struct Config {
-    // No config options yet.
+    pub bindings: LanguageConfig,
 }
 
 impl Config {
-    pub fn from(_ci: &ComponentInterface) -> Self {
+    pub fn from(_ci: &ComponentInterface, bindings: &LanguageConfig) -> Self {
         Config {
-            // No config options yet
+            bindings: bindings.clone(),
         }
     }
 }
@@ -57,16 +58,13 @@ impl<'ci, 'header> ModuleMap<'ci, 'header> {
 #[derive(Template)]
 #[template(syntax = "swift", escape = "none", path = "wrapper.swift")]
 pub struct SwiftWrapper<'config, 'ci> {
-    _config: &'config Config,
+    config: &'config Config,
     ci: &'ci ComponentInterface,
 }
 
 impl<'config, 'ci> SwiftWrapper<'config, 'ci> {
     pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
-        Self {
-            _config: config,
-            ci,
-        }
+        Self { config, ci }
     }
 }
 
@@ -91,12 +89,21 @@ mod filters {
             Type::Float64 => "Double".into(),
             Type::Boolean => "Bool".into(),
             Type::String => "String".into(),
-            Type::Enum(name) | Type::Record(name) | Type::Object(name) | Type::Error(name) => {
-                class_name_swift(name)?
-            }
+            Type::Bytes => "Data".into(),
+            Type::Timestamp => "Date".into(),
+            Type::Duration => "TimeInterval".into(),
+            Type::Enum(name)
+            | Type::Record(name)
+            | Type::Object(name)
+            | Type::Error(name)
+            | Type::CallbackInterface(name)
+            | Type::Union(name, _)
+            | Type::Custom(name, _)
+            | Type::External(name, _) => class_name_swift(name)?,
             Type::Optional(type_) => format!("{}?", type_swift(type_)?),
             Type::Sequence(type_) => format!("[{}]", type_swift(type_)?),
-            Type::Map(type_) => format!("[String:{}]", type_swift(type_)?),
+            Type::Iterator(type_) => format!("RustIterator<{}>", type_swift(type_)?),
+            Type::Map(k, v) => format!("[{}:{}]", type_swift(k)?, type_swift(v)?),
         })
     }
 
@@ -114,25 +121,48 @@ mod filters {
             FFIType::UInt64 => "uint64_t".into(),
             FFIType::Float32 => "float".into(),
             FFIType::Float64 => "double".into(),
-            FFIType::RustCString => "const char*_Nonnull".into(),
             FFIType::RustBuffer => "RustBuffer".into(),
-            FFIType::RustError => "NativeRustError".into(),
             FFIType::ForeignBytes => "ForeignBytes".into(),
+            FFIType::ForeignCallback => "ForeignCallback _Nonnull".into(),
+            FFIType::FutureCallback => "FutureCallback _Nonnull".into(),
         })
     }
 
     /// Lower a Swift type into an FFI type.
     ///
     /// This is used to pass arguments over the FFI, from Swift to Rust.
-    pub fn lower_swift(name: &dyn fmt::Display, _type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.lower()", var_name_swift(name)?))
+    pub fn lower_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        let name = var_name_swift(name)?;
+        Ok(if contains_duration(type_) {
+            format!(
+                "lowerIntoRustBuffer({}, {{ {} }})",
+                name,
+                write_expr("$0", "$1", type_)?
+            )
+        } else {
+            format!("{}.lower()", name)
+        })
     }
 
     /// Lift a Swift type from an FFI type.
     ///
     /// This is used to receive values over the FFI, from Rust to Swift.
     pub fn lift_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.lift({})", type_swift(type_)?, name))
+        Ok(if let Type::Iterator(_) = type_ {
+            format!(
+                "lift{}({})",
+                class_name_swift(&type_.canonical_name())?,
+                name
+            )
+        } else if contains_duration(type_) {
+            format!(
+                "liftFromRustBuffer({}, {{ try {} }})",
+                name,
+                read_swift(&"$0", type_)?
+            )
+        } else {
+            format!("{}.lift({})", type_swift(type_)?, name)
+        })
     }
 
     /// Read a Swift type from a byte buffer.
@@ -140,7 +170,97 @@ mod filters {
     /// This is used to receive values over the FFI, when they're part of a complex type
     /// that is passed by serializing into bytes.
     pub fn read_swift(name: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
-        Ok(format!("{}.read(from: {})", type_swift(type_)?, name))
+        if !contains_duration(type_) {
+            return Ok(format!("{}.read(from: {})", type_swift(type_)?, name));
+        }
+        Ok(match type_ {
+            Type::Optional(t) => format!(
+                "readOptional({}, {{ try {} }})",
+                name,
+                read_swift(&"$0", t)?
+            ),
+            Type::Sequence(t) => format!(
+                "readSequence({}, {{ try {} }})",
+                name,
+                read_swift(&"$0", t)?
+            ),
+            Type::Map(k, v) => format!(
+                "readMap({}, {{ try {} }}, {{ try {} }})",
+                name,
+                read_swift(&"$0", k)?,
+                read_swift(&"$0", v)?
+            ),
+            _ => format!("readDuration({})", name),
+        })
+    }
+
+    /// Write a Swift type into a byte buffer.
+    ///
+    /// This is used to send values over the FFI, when they're part of a complex type
+    /// that is passed by serializing into bytes.
+    pub fn write_swift(
+        name: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+        type_: &Type,
+    ) -> Result<String, askama::Error> {
+        write_expr(&var_name_swift(name)?, &target.to_string(), type_)
+    }
+
+    // Like `write_swift`, but without turning `name` into a Swift variable name, so that it
+    // can refer to closure arguments like `$0`.
+    fn write_expr(name: &str, target: &str, type_: &Type) -> Result<String, askama::Error> {
+        if !contains_duration(type_) {
+            return Ok(format!("{}.write(into: {})", name, target));
+        }
+        Ok(match type_ {
+            Type::Optional(t) => format!(
+                "writeOptional({}, {}, {{ {} }})",
+                name,
+                target,
+                write_expr("$0", "$1", t)?
+            ),
+            Type::Sequence(t) => format!(
+                "writeSequence({}, {}, {{ {} }})",
+                name,
+                target,
+                write_expr("$0", "$1", t)?
+            ),
+            Type::Map(k, v) => format!(
+                "writeMap({}, {}, {{ {} }}, {{ {} }})",
+                name,
+                target,
+                write_expr("$0", "$1", k)?,
+                write_expr("$0", "$1", v)?
+            ),
+            _ => format!("writeDuration({}, {})", name, target),
+        })
+    }
+
+    // Swift's `TimeInterval` is just a `Double`, so it can't conform to our protocols with
+    // its own serialization. Durations, and compound types that contain them, are instead
+    // lifted, lowered, read and written using explicit helper functions.
+    fn contains_duration(type_: &Type) -> bool {
+        match type_ {
+            Type::Duration => true,
+            Type::Optional(t) | Type::Sequence(t) | Type::Map(_, t) => contains_duration(t),
+            _ => false,
+        }
+    }
+
+    /// Get the Swift syntax for a literal value of the given type, e.g. a default argument value.
+    pub fn literal_swift(literal: &Literal, _type_: &Type) -> Result<String, askama::Error> {
+        Ok(match literal {
+            Literal::Boolean(v) => v.to_string(),
+            Literal::String(s) => format!("\"{}\"", s.replace('\\', "\\\\")),
+            Literal::Int(v) => v.to_string(),
+            Literal::UInt(v) => v.to_string(),
+            Literal::Float(v) => v.clone(),
+            // The type is known from context, so the enum name can be left out.
+            Literal::Enum(v) => format!(".{}", enum_variant_swift(v)?),
+            Literal::EmptySequence => "[]".into(),
+            Literal::EmptyMap => "[:]".into(),
+            Literal::Null => "nil".into(),
+        })
     }
 
     pub fn enum_variant_swift(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
@@ -159,6 +279,26 @@ mod filters {
         Ok(nm.to_string().to_mixed_case())
     }
 
+    /// Format a doc comment from the IDL as `///` lines, indented by the given number of spaces.
+    pub fn docstring_swift(docstring: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let indent = " ".repeat(*spaces);
+        Ok(docstring
+            .lines()
+            .map(|line| format!("{}/// {}", indent, line).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Get the attribute for a `[Deprecated]` definition, indented by the given number of spaces.
+    pub fn deprecated_swift(message: &str, spaces: &usize) -> Result<String, askama::Error> {
+        let message = message.replace('\\', "\\\\").replace('"', "\\\"");
+        Ok(format!(
+            "{}@available(*, deprecated, message: \"{}\")",
+            " ".repeat(*spaces),
+            message
+        ))
+    }
+
     pub fn header_path(path: &Path) -> Result<String, askama::Error> {
         Ok(path.to_str().expect("Invalid bridging header path").into())
     }
@@ -14,7 +14,8 @@ use std::{
 pub mod gen_swift;
 pub use gen_swift::{BridgingHeader, Config, ModuleMap, SwiftWrapper};
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{ComponentInterface, Type};
+use super::LanguageConfig;
 
 pub struct Bindings {
     header: String,
@@ -28,6 +29,7 @@ pub struct Bindings {
 /// declarations, and a `.modulemap` file to tell swift how to use it.
 pub fn write_bindings(
     ci: &ComponentInterface,
+    bindings_config: &LanguageConfig,
     out_dir: &Path,
     try_format_code: bool,
 ) -> Result<()> {
@@ -42,14 +44,14 @@ pub fn write_bindings(
     let mut source_file = out_path;
     source_file.push(format!("{}.swift", ci.namespace()));
 
-    let Bindings { header, library } = generate_bindings(&ci)?;
+    let Bindings { header, library } = generate_bindings(ci, bindings_config)?;
 
     let mut h = File::create(&header_file).context("Failed to create .h file for bindings")?;
     write!(h, "{}", header)?;
 
     let mut m =
         File::create(&module_map_file).context("Failed to create .modulemap file for bindings")?;
-    write!(m, "{}", generate_module_map(&ci, &header_file)?)?;
+    write!(m, "{}", generate_module_map(ci, &header_file)?)?;
 
     let mut l = File::create(&source_file).context("Failed to create .swift file for bindings")?;
     write!(l, "{}", library)?;
@@ -71,13 +73,55 @@ pub fn write_bindings(
 }
 
 /// Generate Swift bindings for the given ComponentInterface, as a string.
-pub fn generate_bindings(ci: &ComponentInterface) -> Result<Bindings> {
-    let config = Config::from(&ci);
+pub fn generate_bindings(
+    ci: &ComponentInterface,
+    bindings_config: &LanguageConfig,
+) -> Result<Bindings> {
+    // The helpers for passing data over the FFI are private to the Swift module of each component,
+    // so there's no way for the bindings of one component to pass the types of another (yet).
+    if let Some(namespace) = ci.iter_external_namespaces().first() {
+        bail!(
+            "Swift bindings don't support external types yet, but {} uses types from {}",
+            ci.namespace(),
+            namespace
+        );
+    }
+    // Custom types are given their Swift representation by extending the configured type,
+    // so each one needs a distinct type of its own, which isn't already extended for a builtin type.
+    let builtin_type_names = ci
+        .iter_types()
+        .iter()
+        .filter_map(|t| match t {
+            Type::Timestamp => Some("Date"),
+            Type::Bytes => Some("Data"),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut type_names = std::collections::HashSet::new();
+    for (name, custom) in &bindings_config.custom_types {
+        match &custom.type_name {
+            None => bail!(
+                "Swift config for custom type {} must specify a type_name",
+                name
+            ),
+            Some(type_name) if builtin_type_names.contains(&type_name.as_str()) => bail!(
+                "Swift config can't map custom type {} to {}, which is used for a builtin type",
+                name,
+                type_name
+            ),
+            Some(type_name) if !type_names.insert(type_name) => bail!(
+                "Swift config maps more than one custom type to {}",
+                type_name
+            ),
+            Some(_) => (),
+        }
+    }
+    let config = Config::from(ci, bindings_config);
     use askama::Template;
-    let header = BridgingHeader::new(&config, &ci)
+    let header = BridgingHeader::new(&config, ci)
         .render()
         .map_err(|_| anyhow!("failed to render Swift bridging header"))?;
-    let library = SwiftWrapper::new(&config, &ci)
+    let library = SwiftWrapper::new(&config, ci)
         .render()
         .map_err(|_| anyhow!("failed to render Swift library"))?;
     Ok(Bindings { header, library })
@@ -85,7 +129,7 @@ pub fn generate_bindings(ci: &ComponentInterface) -> Result<Bindings> {
 
 fn generate_module_map(ci: &ComponentInterface, header_path: &Path) -> Result<String> {
     use askama::Template;
-    let module_map = ModuleMap::new(&ci, header_path)
+    let module_map = ModuleMap::new(ci, header_path)
         .render()
         .map_err(|_| anyhow!("failed to render Swift module map"))?;
     Ok(module_map)
@@ -0,0 +1,61 @@
+// Helpers for calling `[Async]` functions.
+//
+// The FFI function for an async function starts a rust future and returns straight away. When the future
+// completes, rust calls a single `FutureCallback` from one of its own threads, passing back the handle
+// under which we stored the suspended call, which is then resumed with the result.
+
+// An async call that's waiting for rust to report its outcome, given the status code and the buffer
+// holding the result.
+fileprivate typealias PendingAsyncCall = (Int8, RustBuffer) -> Void
+
+fileprivate let pendingAsyncCalls = ConcurrentHandleMap<PendingAsyncCall>()
+
+fileprivate let completeAsyncCall: FutureCallback = { callbackData, code, buf in
+    pendingAsyncCalls.remove(handle: callbackData)?(code, buf)
+}
+
+private func rustCallAsync<T>(
+    _ readResult: @escaping (Reader) throws -> T,
+    _ callback: (FutureCallback, UInt64, UnsafeMutablePointer<RustCallStatus>) -> Void
+) async throws -> T {
+    try await makeRustCallAsync(readResult, callback, errorHandler: {
+        $0.deallocate()
+        return InternalError.unexpectedRustCallError
+    })
+}
+
+private func rustCallAsyncWithError<T, E: ViaFfiUsingByteBuffer & Error>(
+    _ errorClass: E.Type,
+    _ readResult: @escaping (Reader) throws -> T,
+    _ callback: (FutureCallback, UInt64, UnsafeMutablePointer<RustCallStatus>) -> Void
+) async throws -> T {
+    try await makeRustCallAsync(readResult, callback, errorHandler: { return try E.lift($0) })
+}
+
+private func makeRustCallAsync<T>(
+    _ readResult: @escaping (Reader) throws -> T,
+    _ callback: (FutureCallback, UInt64, UnsafeMutablePointer<RustCallStatus>) -> Void,
+    errorHandler: @escaping (RustBuffer) throws -> Error
+) async throws -> T {
+    try await withCheckedThrowingContinuation { continuation in
+        let callbackData = pendingAsyncCalls.insert(obj: { code, buf in
+            continuation.resume(with: Result {
+                try checkCallStatus(RustCallStatus(code: code, errorBuf: buf), errorHandler: errorHandler)
+                let reader = Reader(data: Data(rustBuffer: buf))
+                buf.deallocate()
+                let value = try readResult(reader)
+                if reader.hasRemaining() {
+                    throw InternalError.incompleteData
+                }
+                return value
+            })
+        })
+        do {
+            try rustCall { callback(completeAsyncCall, callbackData, $0) }
+        } catch {
+            // Rust never got as far as starting the future, so it won't call us back.
+            pendingAsyncCalls.remove(handle: callbackData)
+            continuation.resume(throwing: error)
+        }
+    }
+}
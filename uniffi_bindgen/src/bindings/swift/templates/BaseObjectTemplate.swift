@@ -0,0 +1,20 @@
+{#
+// Interfaces that other interfaces inherit from are base classes, whose methods are overridden
+// by the classes for the inheriting interfaces. They can't be instantiated themselves.
+#}
+{%- call swift::docstring(obj, 0) %}
+public class {{ obj.name() }}{% match obj.parent() %}{% when Some with (parent) %}: {{ parent }}{% else %}{% endmatch %} {
+    {%- if obj.parent().is_none() %}
+    fileprivate init() {}
+    {%- endif %}
+
+    {%- for meth in obj.methods() %}
+    {%- if meth.inherited_from().is_none() %}
+    {%- call swift::docstring(meth, 4) %}
+    {%- call swift::deprecated(meth, 4) %}
+    public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_swift }}{% else %}{% endmatch %} {
+        fatalError("{{ obj.name() }}.{{ meth.name()|fn_name_swift }} is implemented by the classes that inherit from it")
+    }
+    {%- endif %}
+    {%- endfor %}
+}
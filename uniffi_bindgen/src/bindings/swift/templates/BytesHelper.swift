@@ -0,0 +1,35 @@
+// Bytes are passed as the raw contents of a `RustBuffer`, or with an i32 length prefix
+// when they're serialized as part of a compound type.
+extension Data: ViaFfi {
+    typealias FfiType = RustBuffer
+
+    static func lift(_ v: FfiType) throws -> Self {
+        defer {
+            v.deallocate()
+        }
+        if v.data == nil {
+            return Data()
+        }
+        return Data(rustBuffer: v)
+    }
+
+    func lower() -> FfiType {
+        return self.withUnsafeBytes { ptr in
+            let bytes = ForeignBytes(bufferPointer: ptr.bindMemory(to: UInt8.self))
+            return try! rustCall { status in
+                {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, status)
+            }
+        }
+    }
+
+    static func read(from buf: Reader) throws -> Self {
+        let len: Int32 = try buf.readInt()
+        return Data(try buf.readBytes(count: Int(len)))
+    }
+
+    func write(into buf: Writer) {
+        let len = Int32(self.count)
+        buf.writeInt(len)
+        buf.writeBytes(self)
+    }
+}
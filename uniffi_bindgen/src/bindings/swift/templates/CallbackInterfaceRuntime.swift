@@ -0,0 +1,9 @@
+// Helpers for Swift implementations of callback interfaces.
+//
+// Objects implementing a callback interface are kept in a handle map, and only the handle is passed to rust.
+// When rust wants to call a method on the object, it calls a single `ForeignCallback` registered for the
+// callback interface, which looks up the object by handle and dispatches on the given method index.
+
+// Magic number for the Rust proxy to call using the same mechanism as every other method,
+// to free the callback once it's dropped by Rust.
+fileprivate let IDX_CALLBACK_FREE: UInt32 = 0
@@ -0,0 +1,93 @@
+{% let type_name = cbi.name()|class_name_swift -%}
+{% let foreign_callback = format!("foreignCallback{}", type_name) -%}
+{% let handle_map = format!("callbackHandleMap{}", type_name) -%}
+{% let init_once = format!("initCallbackOnce{}", type_name) -%}
+
+// Declaration and helpers for the {{ type_name }} callback interface.
+
+public protocol {{ type_name }}: AnyObject {
+    {% for meth in cbi.methods() -%}
+    func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %})
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {{ return_type|type_swift -}}
+    {%- else -%}
+    {%- endmatch %}
+    {% endfor %}
+}
+
+fileprivate let {{ handle_map }} = ConcurrentHandleMap<{{ type_name }}>()
+
+// The ForeignCallback that is passed to Rust.
+fileprivate let {{ foreign_callback }}: ForeignCallback =
+    { (handle: UInt64, method: UInt32, args: RustBuffer, out_buf: UnsafeMutablePointer<RustBuffer>) -> Int32 in
+        {% for meth in cbi.methods() -%}
+        {% let method_name = format!("invoke_{}", meth.name())|fn_name_swift -%}
+        func {{ method_name }}(_ swiftCallbackInterface: {{ type_name }}, _ args: RustBuffer) throws -> RustBuffer {
+            {%- if meth.arguments().len() > 0 %}
+            let reader = Reader(data: Data(rustBuffer: args))
+            {%- endif %}
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            let result = swiftCallbackInterface.{{ meth.name()|fn_name_swift }}(
+                {% for arg in meth.arguments() -%}
+                {{ arg.name()|var_name_swift }}: try {{ "reader"|read_swift(arg.type_()) }}
+                {%- if !loop.last %}, {% endif %}
+                {% endfor -%}
+            )
+            let writer = Writer()
+            {{ "result"|write_swift("writer", return_type) }}
+            return RustBuffer(bytes: writer.bytes)
+            {%- else %}
+            swiftCallbackInterface.{{ meth.name()|fn_name_swift }}(
+                {% for arg in meth.arguments() -%}
+                {{ arg.name()|var_name_swift }}: try {{ "reader"|read_swift(arg.type_()) }}
+                {%- if !loop.last %}, {% endif %}
+                {% endfor -%}
+            )
+            return RustBuffer()
+            {%- endmatch %}
+        }
+
+        {% endfor %}
+        defer { args.deallocate() }
+        guard let cb = {{ handle_map }}.get(handle: handle) else {
+            return -1
+        }
+        switch method {
+            case IDX_CALLBACK_FREE:
+                {{ handle_map }}.remove(handle: handle)
+                return 0
+            {% for meth in cbi.methods() -%}
+            {% let method_name = format!("invoke_{}", meth.name())|fn_name_swift -%}
+            case {{ loop.index }}:
+                do {
+                    out_buf.pointee = try {{ method_name }}(cb, args)
+                    return 0
+                } catch {
+                    // There's no way to surface the error to the Rust caller, which will panic.
+                    return -1
+                }
+            {% endfor %}
+            // This should never happen, because rust only ever calls the methods declared in the IDL.
+            default:
+                return -1
+        }
+    }
+
+// Registers the ForeignCallback with Rust the first time a {{ type_name }} is passed across the FFI.
+private let {{ init_once }}: () = {
+    try! rustCall { status in
+        {{ cbi.ffi_init_callback().name() }}({{ foreign_callback }}, status)
+    }
+}()
+
+extension {{ type_name }} {
+    func lower() -> UInt64 {
+        let _ = {{ init_once }}
+        return {{ handle_map }}.insert(obj: self)
+    }
+
+    func write(into buf: Writer) {
+        buf.writeInt(self.lower())
+    }
+}
@@ -0,0 +1,29 @@
+// A map where inserting, getting and removing data is synchronized with a lock.
+fileprivate class ConcurrentHandleMap<T> {
+    private var leftMap: [UInt64: T] = [:]
+    private let lock = NSLock()
+    private var currentHandle: UInt64 = 0
+    private let stride: UInt64 = 1
+
+    func insert(obj: T) -> UInt64 {
+        lock.lock()
+        defer { lock.unlock() }
+        let handle = currentHandle
+        currentHandle += stride
+        leftMap[handle] = obj
+        return handle
+    }
+
+    func get(handle: UInt64) -> T? {
+        lock.lock()
+        defer { lock.unlock() }
+        return leftMap[handle]
+    }
+
+    @discardableResult
+    func remove(handle: UInt64) -> T? {
+        lock.lock()
+        defer { lock.unlock() }
+        return leftMap.removeValue(forKey: handle)
+    }
+}
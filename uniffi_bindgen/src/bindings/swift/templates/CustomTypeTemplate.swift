@@ -0,0 +1,34 @@
+{%- let builtin_type_name = builtin|type_swift %}
+{%- match config.bindings.custom_type(name) %}
+{%- when Some with (custom) %}
+{%- let type_name = custom.type_name.as_deref().unwrap_or(builtin_type_name.as_str()) %}
+// The custom type {{ name }} is passed over the FFI as {{ builtin_type_name }}.
+public typealias {{ name|class_name_swift }} = {{ type_name }}
+
+extension {{ type_name }}: ViaFfi {
+    typealias FfiType = {{ builtin_type_name }}.FfiType
+
+    static func read(from buf: Reader) throws -> {{ type_name }} {
+        let builtinValue = try {{ "buf"|read_swift(builtin) }}
+        return {{ custom.into_custom_expr("builtinValue") }}
+    }
+
+    func write(into buf: Writer) {
+        let builtinValue = {{ custom.from_custom_expr("self") }}
+        builtinValue.write(into: buf)
+    }
+
+    static func lift(_ v: FfiType) throws -> {{ type_name }} {
+        let builtinValue = try {{ "v"|lift_swift(builtin) }}
+        return {{ custom.into_custom_expr("builtinValue") }}
+    }
+
+    func lower() -> FfiType {
+        let builtinValue = {{ custom.from_custom_expr("self") }}
+        return builtinValue.lower()
+    }
+}
+{%- when None %}
+// The custom type {{ name }} is passed over the FFI as {{ builtin_type_name }}.
+public typealias {{ name|class_name_swift }} = {{ builtin_type_name }}
+{%- endmatch %}
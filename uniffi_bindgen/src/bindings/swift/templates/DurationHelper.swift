@@ -0,0 +1,90 @@
+// Durations are represented as a `TimeInterval`, which is just a `Double` and so already has a
+// different serialization. Instead of extending it, we use these helper functions for durations
+// and for any compound types that contain them.
+
+fileprivate func readDuration(_ buf: Reader) throws -> TimeInterval {
+    let seconds: UInt64 = try buf.readInt()
+    let nanoseconds: UInt32 = try buf.readInt()
+    return Double(seconds) + Double(nanoseconds) / 1.0e9
+}
+
+fileprivate func writeDuration(_ v: TimeInterval, _ buf: Writer) {
+    // TODO: would be nice not to crash here.
+    precondition(v >= 0, "Invalid duration, must be non-negative")
+    var seconds = v.rounded(.down)
+    var nanoseconds = ((v - seconds) * 1.0e9).rounded()
+    if nanoseconds >= 1.0e9 {
+        seconds += 1
+        nanoseconds = 0
+    }
+    buf.writeInt(UInt64(seconds))
+    buf.writeInt(UInt32(nanoseconds))
+}
+
+fileprivate func liftFromRustBuffer<T>(_ rbuf: RustBuffer, _ readItem: (Reader) throws -> T) throws -> T {
+    let reader = Reader(data: Data(rustBuffer: rbuf))
+    let value = try readItem(reader)
+    if reader.hasRemaining() {
+        throw InternalError.incompleteData
+    }
+    rbuf.deallocate()
+    return value
+}
+
+fileprivate func lowerIntoRustBuffer<T>(_ v: T, _ writeItem: (T, Writer) -> Void) -> RustBuffer {
+    let writer = Writer()
+    writeItem(v, writer)
+    return RustBuffer(bytes: writer.bytes)
+}
+
+fileprivate func readOptional<T>(_ buf: Reader, _ readItem: (Reader) throws -> T) throws -> T? {
+    switch try buf.readInt() as Int8 {
+    case 0: return nil
+    case 1: return try readItem(buf)
+    default: throw InternalError.unexpectedOptionalTag
+    }
+}
+
+fileprivate func writeOptional<T>(_ v: T?, _ buf: Writer, _ writeItem: (T, Writer) -> Void) {
+    guard let value = v else {
+        buf.writeInt(Int8(0))
+        return
+    }
+    buf.writeInt(Int8(1))
+    writeItem(value, buf)
+}
+
+fileprivate func readSequence<T>(_ buf: Reader, _ readItem: (Reader) throws -> T) throws -> [T] {
+    let len: Int32 = try buf.readInt()
+    var seq = [T]()
+    seq.reserveCapacity(Int(len))
+    for _ in 0..<len {
+        seq.append(try readItem(buf))
+    }
+    return seq
+}
+
+fileprivate func writeSequence<T>(_ v: [T], _ buf: Writer, _ writeItem: (T, Writer) -> Void) {
+    buf.writeInt(Int32(v.count))
+    for item in v {
+        writeItem(item, buf)
+    }
+}
+
+fileprivate func readMap<K, V>(_ buf: Reader, _ readKey: (Reader) throws -> K, _ readValue: (Reader) throws -> V) throws -> [K: V] {
+    let len: Int32 = try buf.readInt()
+    var dict = [K: V]()
+    dict.reserveCapacity(Int(len))
+    for _ in 0..<len {
+        dict[try readKey(buf)] = try readValue(buf)
+    }
+    return dict
+}
+
+fileprivate func writeMap<K, V>(_ v: [K: V], _ buf: Writer, _ writeKey: (K, Writer) -> Void, _ writeValue: (V, Writer) -> Void) {
+    buf.writeInt(Int32(v.count))
+    for (key, value) in v {
+        writeKey(key, buf)
+        writeValue(value, buf)
+    }
+}
@@ -1,30 +1,39 @@
-public enum {{ e.name()|class_name_swift }}: ViaFfi {
+{%- call swift::docstring(e, 0) %}
+public enum {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi, Equatable {
     {% for variant in e.variants() %}
-    case {{ variant|enum_variant_swift }}
+    {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
+    case {{ variant.name()|enum_variant_swift }}{% if variant.has_fields() %}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}){% endif -%}
     {% endfor %}
 
     static func read(from buf: Reader) throws -> {{ e.name()|class_name_swift }} {
-        return try {{ e.name()|class_name_swift }}.lift(UInt32.read(from: buf))
-    }
-
-    static func lift(_ number: UInt32) throws -> {{ e.name()|class_name_swift }} {
-        switch number {
+        let variant: UInt32 = try buf.readInt()
+        switch variant {
         {% for variant in e.variants() %}
-        case {{ loop.index }}: return .{{ variant|enum_variant_swift }}
+        case {{ loop.index }}: return {% if variant.has_fields() -%}try .{{ variant.name()|enum_variant_swift }}(
+            {% for field in variant.fields() -%}
+            {{ field.name()|var_name_swift }}: {{ "buf"|read_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+            {% endfor -%}
+        ){%- else -%}.{{ variant.name()|enum_variant_swift }}{%- endif %}
         {% endfor %}
         default: throw InternalError.unexpectedEnumCase
         }
     }
 
     func write(into buf: Writer) {
-        self.lower().write(into: buf)
-    }
-
-    func lower() -> UInt32 {
         switch self {
         {% for variant in e.variants() %}
-        case .{{ variant|enum_variant_swift }}: return {{ loop.index }}
-        {% endfor %}
+        {% if variant.has_fields() %}
+        case let .{{ variant.name()|enum_variant_swift }}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
+            buf.writeInt(UInt32({{ loop.index }}))
+            {% for field in variant.fields() -%}
+            {{ field.name()|write_swift("buf", field.type_()) }}
+            {% endfor -%}
+        {% else %}
+        case .{{ variant.name()|enum_variant_swift }}:
+            buf.writeInt(UInt32({{ loop.index }}))
+        {% endif %}
+        {%- endfor %}
         }
     }
 }
@@ -1,23 +1,19 @@
 {#
-// In here we define conversions between a native reference to Swift errors
-// We use the RustError protocol to define the requirements. Any implementers of the protocol
-// Can be generated from a NativeRustError.
-
+// In here we define conversions between the status of a call into rust and Swift errors.
+// Every FFI function takes a `RustCallStatus` as its final argument; if the call fails then the status
+// holds either an error declared in the IDL, serialized into `errorBuf`, or the message from a rust panic.
 #}
 
-protocol RustError: LocalizedError {
-    static func fromConsuming(_ rustError: NativeRustError) throws -> Self?
-}
-
 // An error type for FFI errors. These errors occur at the UniFFI level, not
 // the library level.
-enum InternalError: RustError {
+enum InternalError: LocalizedError {
     case bufferOverflow
     case incompleteData
     case unexpectedOptionalTag
     case unexpectedEnumCase
-    case emptyResult
-    case unknown(message: String = "")
+    case unexpectedRustCallStatusCode
+    case unexpectedRustCallError
+    case rustPanic(_ message: String)
 
     public var errorDescription: String? {
         switch self {
@@ -25,99 +21,112 @@ enum InternalError: RustError {
         case .incompleteData: return "The buffer still has data after lifting its containing value"
         case .unexpectedOptionalTag: return "Unexpected optional tag; should be 0 or 1"
         case .unexpectedEnumCase: return "Raw enum value doesn't match any cases"
-        case .emptyResult: return "Unexpected nil returned from FFI function"
-        case let .unknown(message): return "FFI function returned unknown error: \(message)"
-        }
-    }
-
-    static func fromConsuming(_ rustError: NativeRustError) throws -> Self? {
-        let message = rustError.message
-        defer {
-            if message != nil {
-                try! rustCall(InternalError.unknown()) { err in
-                    {{ ci.ffi_string_free().name() }}(message!, err)
-                }
-            }
-        }
-        switch rustError.code {
-        case 0: return nil
-        default: return .unknown(message: String(cString: message!))
+        case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
+        case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
+        case let .rustPanic(message): return message
         }
     }
 }
 
+// These match the values from the uniffi::rustcalls module.
+private let CALL_SUCCESS: Int8 = 0
+private let CALL_ERROR: Int8 = 1
+private let CALL_PANIC: Int8 = 2
+
 {% for e in ci.iter_error_definitions() %}
-public enum {{e.name()}}: RustError {
-    case NoError
-    {% for value in e.values() %}
-    case {{value}}(message: String)
+{%- if e.is_flat() %}
+// The variants of a flat error carry the message from the rust error's `Display` implementation.
+{%- endif %}
+public enum {{ e.name()|class_name_swift }} {
+    {% for variant in e.variants() %}
+    {%- if e.is_flat() %}
+    case {{ variant.name()|class_name_swift }}(message: String)
+    {%- else %}
+    case {{ variant.name()|class_name_swift }}{% if variant.has_fields() %}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}){% endif -%}
+    {%- endif %}
     {% endfor %}
+}
 
-
-    /// Our implementation of the localizedError protocol
-    public var errorDescription: String? {
-        switch self {
-        {% for value in e.values() %}
-        case let .{{value}}(message):
-            return "{{e.name()}}.{{value}}: \(message)"
+extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
+    static func read(from buf: Reader) throws -> {{ e.name()|class_name_swift }} {
+        let variant: UInt32 = try buf.readInt()
+        switch variant {
+        {% for variant in e.variants() %}
+        {%- if e.is_flat() %}
+        case {{ loop.index }}: return .{{ variant.name()|class_name_swift }}(
+            message: try String.read(from: buf)
+        )
+        {%- else %}
+        case {{ loop.index }}: return {% if variant.has_fields() -%}try .{{ variant.name()|class_name_swift }}(
+            {% for field in variant.fields() -%}
+            {{ field.name()|var_name_swift }}: {{ "buf"|read_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
+            {% endfor -%}
+        ){%- else -%}.{{ variant.name()|class_name_swift }}{%- endif %}
+        {%- endif %}
         {% endfor %}
-        default:
-            return nil
+        default: throw InternalError.unexpectedEnumCase
         }
     }
 
-    // The name is attempting to indicate that we free message if it
-    // existed, and that it's a very bad idea to touch it after you call this
-    // function
-    static func fromConsuming(_ rustError: NativeRustError) throws -> Self? {
-        let message = rustError.message
-        defer {
-            if message != nil {
-                try! rustCall(InternalError.unknown()) { err in
-                    {{ ci.ffi_string_free().name() }}(message!, err)
-                }
-            }
-        }
-        switch rustError.code {
-            case 0:
-                return nil
-            {% for value in e.values() %}
-            case {{loop.index}}:
-                return .{{value}}(message: String(cString: message!))
-            {% endfor %}
-            default:
-                return nil
+    func write(into buf: Writer) {
+        switch self {
+        {% for variant in e.variants() %}
+        {%- if e.is_flat() %}
+        case let .{{ variant.name()|class_name_swift }}(message):
+            buf.writeInt(UInt32({{ loop.index }}))
+            message.write(into: buf)
+        {%- else if variant.has_fields() %}
+        case let .{{ variant.name()|class_name_swift }}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
+            buf.writeInt(UInt32({{ loop.index }}))
+            {% for field in variant.fields() -%}
+            {{ field.name()|write_swift("buf", field.type_()) }}
+            {% endfor -%}
+        {%- else %}
+        case .{{ variant.name()|class_name_swift }}:
+            buf.writeInt(UInt32({{ loop.index }}))
+        {%- endif %}
+        {%- endfor %}
         }
     }
 }
+
+extension {{ e.name()|class_name_swift }}: Equatable, Error {}
 {% endfor %}
 
-func rustCall<T, E: RustError>(_ err: E, _ cb: (UnsafeMutablePointer<NativeRustError>) throws -> T?) throws -> T {
-    return try unwrap(err) { native_err in
-        return try cb(native_err)
-    }
+private func rustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>) -> T) throws -> T {
+    try makeRustCall(callback, errorHandler: {
+        $0.deallocate()
+        return InternalError.unexpectedRustCallError
+    })
 }
 
-func nullableRustCall<T, E: RustError>(_ err: E, _ cb: (UnsafeMutablePointer<NativeRustError>) throws -> T?) throws -> T? {
-    return try tryUnwrap(err) { native_err in
-        return try cb(native_err)
-    }
+private func rustCallWithError<T, E: ViaFfiUsingByteBuffer & Error>(_ errorClass: E.Type, _ callback: (UnsafeMutablePointer<RustCallStatus>) -> T) throws -> T {
+    try makeRustCall(callback, errorHandler: { return try E.lift($0) })
 }
 
-@discardableResult
- func unwrap<T, E: RustError>(_ err: E, _ callback: (UnsafeMutablePointer<NativeRustError>) throws -> T?) throws -> T {
-    guard let result = try tryUnwrap(err, callback) else {
-        throw InternalError.emptyResult
-    }
-    return result
+private func makeRustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>) -> T, errorHandler: (RustBuffer) throws -> Error) throws -> T {
+    var callStatus = RustCallStatus()
+    let returnedVal = callback(&callStatus)
+    try checkCallStatus(callStatus, errorHandler: errorHandler)
+    return returnedVal
 }
 
-@discardableResult
-func tryUnwrap<T, E: RustError>(_ err: E, _ callback: (UnsafeMutablePointer<NativeRustError>) throws -> T?) throws -> T? {
-    var native_err = NativeRustError(code: 0, message: nil)
-    let returnedVal = try callback(&native_err)
-    if let retErr = try E.fromConsuming(native_err) {
-        throw retErr
+// Throw the appropriate error if a call into rust didn't succeed.
+private func checkCallStatus(_ callStatus: RustCallStatus, errorHandler: (RustBuffer) throws -> Error) throws {
+    switch callStatus.code {
+        case CALL_SUCCESS:
+            return
+
+        case CALL_ERROR:
+            throw try errorHandler(callStatus.errorBuf)
+
+        case CALL_PANIC:
+            // When the rust code sees a panic, it sends back the message as the raw
+            // UTF-8 bytes of the error buffer.
+            throw InternalError.rustPanic(try String.lift(callStatus.errorBuf))
+
+        default:
+            callStatus.errorBuf.deallocate()
+            throw InternalError.unexpectedRustCallStatusCode
     }
-    return returnedVal
 }
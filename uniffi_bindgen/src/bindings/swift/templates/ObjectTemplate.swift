@@ -1,32 +1,128 @@
-public class {{ obj.name() }} {
+{%- call swift::docstring(obj, 0) %}
+public final class {{ obj.name() }}{% match obj.parent() %}{% when Some with (parent) %}: {{ parent }}{% else %}{% endmatch %} {
     private let handle: UInt64
 
-    {%- for cons in obj.constructors() %}
+    {%- for c in obj.constants() %}
+    public static let {{ c.name()|var_name_swift }}: {{ c.type_()|type_swift }} = {{ c.value()|literal_swift(c.type_()) }}
+    {%- endfor %}
+
+    fileprivate init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    {%- call swift::deprecated(cons, 4) %}
     public init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
         self.handle = {% call swift::to_ffi_call(cons) %}
     }
-    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
+
+    {% for cons in obj.alternate_constructors() %}
+    {%- call swift::deprecated(cons, 4) %}
+    public static func {{ cons.name()|fn_name_swift }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ obj.name() }} {
+        return {{ obj.name() }}(unsafeFromHandle: {% call swift::to_ffi_call(cons) %})
+    }
+    {% endfor %}
 
     deinit {
-        try! rustCall(InternalError.unknown()) { err in
-            {{ obj.ffi_object_free().name() }}(handle, err)
+        try! rustCall { status in
+            {{ obj.ffi_object_free().name() }}(handle, status)
         }
     }
 
     // TODO: Maybe merge the two templates (i.e the one with a return type and the one without)
     {% for meth in obj.methods() -%}
+    {%- call swift::docstring(meth, 4) %}
+    {%- call swift::deprecated(meth, 4) %}
+    {% if meth.is_async() -%}
+    {%- let static_ = meth.is_static() %}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    public {% if static_ %}static {% endif %}func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) async {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
+        return {% if static_ %}{% call swift::to_async_ffi_call("", meth) %}{% else %}{% call swift::to_async_ffi_call("self.handle", meth) %}{% endif %}
+    }
+
+    {%- when None -%}
+    public {% if static_ %}static {% endif %}func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) async {% call swift::throws(meth) %} {
+        {% if static_ %}{% call swift::to_async_ffi_call("", meth) %}{% else %}{% call swift::to_async_ffi_call("self.handle", meth) %}{% endif %}
+    }
+    {%- endmatch %}
+    {%- else if meth.is_static() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) -%}
+    public static func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
+        let _retval = {% call swift::to_ffi_call(meth) %}
+        return {% call swift::try(meth) %} {{ "_retval"|lift_swift(return_type) }}
+    }
+
+    {%- when None -%}
+    public static func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_call(meth) %}
+    }
+    {%- endmatch %}
+    {%- else -%}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
+    public {% if meth.inherited_from().is_some() %}override {% endif %}func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_swift }} {
         let _retval = {% call swift::to_ffi_call_with_prefix("self.handle", meth) %}
         return {% call swift::try(meth) %} {{ "_retval"|lift_swift(return_type) }}
     }
 
     {%- when None -%}
-    public func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+    public {% if meth.inherited_from().is_some() %}override {% endif %}func {{ meth.name()|fn_name_swift }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
         {% call swift::to_ffi_call_with_prefix("self.handle", meth) %}
     }
     {%- endmatch %}
+    {%- endif %}
     {% endfor %}
-}
\ No newline at end of file
+
+    {%- for prop in obj.properties() %}
+    {%- let getter = prop.getter() %}
+    public var {{ prop.name()|var_name_swift }}: {{ prop.type_()|type_swift }} {
+        get {
+            let _retval = {% call swift::to_ffi_call_with_prefix("self.handle", getter) %}
+            return try! {{ "_retval"|lift_swift(prop.type_()) }}
+        }
+        {%- match prop.setter() %}
+        {%- when Some with (setter) %}
+        set(value) {
+            {% call swift::to_ffi_call_with_prefix("self.handle", setter) %}
+        }
+        {%- when None %}
+        {%- endmatch %}
+    }
+    {% endfor %}
+}
+
+// Objects are equal only if they're the same instance, so that records holding them can be compared.
+extension {{ obj.name() }}: Equatable {
+    public static func ==(lhs: {{ obj.name() }}, rhs: {{ obj.name() }}) -> Bool {
+        return lhs === rhs
+    }
+}
+
+extension {{ obj.name() }}: ViaFfi {
+    typealias FfiType = UInt64
+
+    static func read(from buf: Reader) throws -> {{ obj.name() }} {
+        return try lift(buf.readInt())
+    }
+
+    func write(into buf: Writer) {
+        buf.writeInt(lower())
+    }
+
+    static func lift(_ handle: UInt64) throws -> {{ obj.name() }} {
+        return {{ obj.name() }}(unsafeFromHandle: handle)
+    }
+
+    // Rust gets its own reference to the object, so this instance stays usable after passing it.
+    func lower() -> UInt64 {
+        return self.handle
+    }
+}
@@ -1,5 +1,8 @@
+{%- call swift::docstring(rec, 0) %}
 public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi, Equatable {
     {%- for field in rec.fields() %}
+    {%- call swift::docstring(field, 4) %}
+    {%- call swift::deprecated(field, 4) %}
     let {{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}
     {%- endfor %}
 
@@ -7,7 +10,12 @@ public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi,
     // declare one manually.
     public init(
         {%- for field in rec.fields() %}
-        {{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}{% if loop.last %}{% else %},{% endif %}
+        {{ field.name()|var_name_swift }}: {{ field.type_()|type_swift }}
+        {%- match field.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_swift(field.type_()) }}
+        {%- else %}
+        {%- endmatch -%}
+        {% if loop.last %}{% else %},{% endif %}
         {%- endfor %}
     ) {
         {%- for field in rec.fields() %}
@@ -34,7 +42,7 @@ public struct {{ rec.name()|class_name_swift }}:  ViaFfiUsingByteBuffer, ViaFfi,
 
     func write(into buf: Writer) {
         {%- for field in rec.fields() %}
-        {{ field.name()|var_name_swift }}.write(into: buf)
+        {{ field.name()|write_swift("buf", field.type_()) }}
         {%- endfor %}
     }
 }
@@ -167,8 +167,8 @@ extension String: ViaFfi {
 
     static func lift(_ v: FfiType) throws -> Self {
         defer {
-            try! rustCall(InternalError.unknown()) { err in
-                {{ ci.ffi_rustbuffer_free().name() }}(v, err)
+            try! rustCall { status in
+                {{ ci.ffi_rustbuffer_free().name() }}(v, status)
             }
         }
         if v.data == nil {
@@ -185,8 +185,8 @@ extension String: ViaFfi {
                 // The swift string gives us a trailing null byte, we don't want it.
                 let buf = UnsafeBufferPointer(rebasing: ptr.prefix(upTo: ptr.count - 1))
                 let bytes = ForeignBytes(bufferPointer: buf)
-                return try! rustCall(InternalError.unknown()) { err in
-                    {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, err)
+                return try! rustCall { status in
+                    {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, status)
                 }
             }
         }
@@ -364,13 +364,13 @@ extension Array: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Element: Seri
     }
 }
 
-extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key == String, Value: Serializable {
+extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key: Serializable, Value: Serializable {
     static func read(from buf: Reader) throws -> Self {
         let len: Int32 = try buf.readInt()
-        var dict = [String: Value]()
+        var dict = [Key: Value]()
         dict.reserveCapacity(Int(len))
         for _ in 1...len {
-            dict[try String.read(from: buf)] = try Value.read(from: buf)
+            dict[try Key.read(from: buf)] = try Value.read(from: buf)
         }
         return dict
     }
@@ -384,3 +384,15 @@ extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key == S
         }
     }
 }
+
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Bytes %}
+{% include "BytesHelper.swift" %}
+{%- when Type::Timestamp %}
+{% include "TimestampHelper.swift" %}
+{%- when Type::Duration %}
+{% include "DurationHelper.swift" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
@@ -2,8 +2,8 @@ extension RustBuffer {
     // Allocate a new buffer, copying the contents of a `UInt8` array.
     init(bytes: [UInt8]) {
         let rbuf = bytes.withUnsafeBufferPointer { ptr in
-            try! rustCall(InternalError.unknown()) { err in
-                {{ ci.ffi_rustbuffer_from_bytes().name() }}(ForeignBytes(bufferPointer: ptr), err)
+            try! rustCall { status in
+                {{ ci.ffi_rustbuffer_from_bytes().name() }}(ForeignBytes(bufferPointer: ptr), status)
             }
         }
         self.init(capacity: rbuf.capacity, len: rbuf.len, data: rbuf.data)
@@ -12,8 +12,8 @@ extension RustBuffer {
     // Frees the buffer in place.
     // The buffer must not be used after this is called.
     func deallocate() {
-        try! rustCall(InternalError.unknown()) { err in
-            {{ ci.ffi_rustbuffer_free().name() }}(self, err)
+        try! rustCall { status in
+            {{ ci.ffi_rustbuffer_free().name() }}(self, status)
         }
     }
 }
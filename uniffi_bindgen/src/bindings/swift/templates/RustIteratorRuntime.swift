@@ -0,0 +1,83 @@
+// Helpers for `[Iterator]` functions and methods.
+//
+// These return a handle to a rust iterator, from which we take the items in batches as they're
+// needed, rather than rust producing all of them up front. A batch that's shorter than we asked
+// for means that the iterator is exhausted, at which point we free it straight away.
+
+fileprivate let rustIteratorBatchSize: UInt32 = 100
+
+public final class RustIterator<Element>: Sequence, IteratorProtocol {
+    private var handle: UInt64
+    private let takeBatch: (UInt64, UInt32) throws -> [Element]
+    private let free: (UInt64) -> Void
+    private var batch: [Element] = []
+    private var offset = 0
+
+    fileprivate init(
+        handle: UInt64,
+        takeBatch: @escaping (UInt64, UInt32) throws -> [Element],
+        free: @escaping (UInt64) -> Void
+    ) {
+        self.handle = handle
+        self.takeBatch = takeBatch
+        self.free = free
+    }
+
+    deinit {
+        close()
+    }
+
+    public func next() -> Element? {
+        if offset == batch.count && handle != 0 {
+            batch = try! takeBatch(handle, rustIteratorBatchSize)
+            offset = 0
+            if batch.count < Int(rustIteratorBatchSize) {
+                close()
+            }
+        }
+        guard offset < batch.count else {
+            return nil
+        }
+        defer { offset += 1 }
+        return batch[offset]
+    }
+
+    // Free the rust iterator, without taking any more items from it.
+    public func close() {
+        let handle = self.handle
+        if handle != 0 {
+            self.handle = 0
+            free(handle)
+        }
+    }
+}
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Iterator with (item_type) %}
+
+fileprivate func lift{{ type_.canonical_name()|class_name_swift }}(_ handle: UInt64) throws -> RustIterator<{{ item_type|type_swift }}> {
+    return RustIterator(
+        handle: handle,
+        takeBatch: { handle, maxItems in
+            let rbuf = try rustCall { {{ ci.ffi_iterator_next(item_type).name() }}(handle, maxItems, $0) }
+            let reader = Reader(data: Data(rustBuffer: rbuf))
+            rbuf.deallocate()
+            let len: Int32 = try reader.readInt()
+            var batch = [{{ item_type|type_swift }}]()
+            batch.reserveCapacity(Int(len))
+            for _ in 0..<len {
+                batch.append(try {{ "reader"|read_swift(item_type) }})
+            }
+            if reader.hasRemaining() {
+                throw InternalError.incompleteData
+            }
+            return batch
+        },
+        free: { handle in
+            try! rustCall { {{ ci.ffi_iterator_free(item_type).name() }}(handle, $0) }
+        }
+    )
+}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
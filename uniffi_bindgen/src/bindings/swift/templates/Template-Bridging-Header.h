@@ -19,12 +19,17 @@ typedef struct ForeignBytes
     const uint8_t *_Nullable data;
 } ForeignBytes;
 
-// Error definitions
-// Each error has an error code enum, and a struct
-typedef struct NativeRustError {
-    int32_t code;
-    char *_Nullable message;
-} NativeRustError;
+// Function pointer through which Rust calls methods on Swift implementations of callback interfaces.
+typedef int32_t (*ForeignCallback)(uint64_t, uint32_t, RustBuffer, RustBuffer *_Nonnull);
+
+// Function pointer through which Rust reports the outcome of an async call.
+typedef void (*FutureCallback)(uint64_t, int8_t, RustBuffer);
+
+// The status of a call into rust, passed as the final argument of every FFI function.
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
 
   
 {% for func in ci.iter_ffi_function_definitions() -%}
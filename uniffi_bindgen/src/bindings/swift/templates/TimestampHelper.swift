@@ -0,0 +1,21 @@
+// Timestamps are passed as the seconds since the unix epoch (rounded down), followed by the
+// nanoseconds after that second.
+extension Date: ViaFfiUsingByteBuffer, ViaFfi {
+    static func read(from buf: Reader) throws -> Date {
+        let seconds: Int64 = try buf.readInt()
+        let nanoseconds: UInt32 = try buf.readInt()
+        return Date(timeIntervalSince1970: Double(seconds) + Double(nanoseconds) / 1.0e9)
+    }
+
+    func write(into buf: Writer) {
+        let interval = self.timeIntervalSince1970
+        var seconds = interval.rounded(.down)
+        var nanoseconds = ((interval - seconds) * 1.0e9).rounded()
+        if nanoseconds >= 1.0e9 {
+            seconds += 1
+            nanoseconds = 0
+        }
+        buf.writeInt(Int64(seconds))
+        buf.writeInt(UInt32(nanoseconds))
+    }
+}
@@ -1,14 +1,31 @@
+{%- if func.is_async() %}
+{% call swift::docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
+{%- match func.return_type() %}
+{%- when Some with (return_type) %}
+public func {{ func.name()|fn_name_swift }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %} -> {{ return_type|type_swift }} {
+    return {% call swift::to_async_ffi_call("", func) %}
+}
+{%- when None %}
+public func {{ func.name()|fn_name_swift }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %} {
+    {% call swift::to_async_ffi_call("", func) %}
+}
+{%- endmatch %}
+{% else -%}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
-
+{% call swift::docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
 public func {{ func.name()|fn_name_swift }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_swift }} {
     let _retval = {% call swift::to_ffi_call(func) %}
     return {% call swift::try(func) %} {{ "_retval"|lift_swift(return_type) }}
 }
 
 {% when None -%}
-
+{% call swift::docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
 public func {{ func.name()|fn_name_swift }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
     {% call swift::to_ffi_call(func) %}
 }
-{% endmatch %}
\ No newline at end of file
+{% endmatch %}
+{%- endif %}
@@ -0,0 +1,25 @@
+public enum {{ name|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi, Equatable {
+    {% for member in members %}
+    case {{ member.union_member_name()|enum_variant_swift }}({{ member|type_swift }})
+    {% endfor %}
+
+    static func read(from buf: Reader) throws -> {{ name|class_name_swift }} {
+        let variant: UInt32 = try buf.readInt()
+        switch variant {
+        {% for member in members %}
+        case {{ loop.index }}: return try .{{ member.union_member_name()|enum_variant_swift }}({{ "buf"|read_swift(member) }})
+        {% endfor %}
+        default: throw InternalError.unexpectedEnumCase
+        }
+    }
+
+    func write(into buf: Writer) {
+        switch self {
+        {% for member in members %}
+        case let .{{ member.union_member_name()|enum_variant_swift }}(v):
+            buf.writeInt(UInt32({{ loop.index }}))
+            {{ "v"|write_swift("buf", member) }}
+        {% endfor %}
+        }
+    }
+}
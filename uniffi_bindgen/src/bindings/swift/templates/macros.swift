@@ -5,29 +5,47 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
-{% call try(func) %} rustCall(
+{% call try(func) %}
     {% match func.throws() %}
     {% when Some with (e) %}
-    {{e}}.NoError
+    rustCallWithError({{ e|class_name_swift }}.self) {
     {% else %}
-    InternalError.unknown()
+    rustCall() {
     {% endmatch %}
-) { err in
-    {{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}err)
+    {{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}$0)
 }
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
-{% call try(func) %} rustCall(
+{% call try(func) %}
     {% match func.throws() %}
     {% when Some with (e) %}
-    {{e}}.NoError
+    rustCallWithError({{ e|class_name_swift }}.self) {
     {% else %}
-    InternalError.unknown()
+    rustCall() {
     {% endmatch %}
-) { err in
     {{ func.ffi_func().name() }}(
-        {{- prefix }}, {% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}err
+        {{- prefix }}, {% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}$0
+    )
+}
+{%- endmacro %}
+
+{#
+// Call an `[Async]` function, suspending until it completes. The prefix holds any leading
+// arguments such as the object handle, and may be empty.
+#}
+{%- macro to_async_ffi_call(prefix, func) -%}
+{% call try(func) %} await
+    {%- match func.throws() %}
+    {%- when Some with (e) %} rustCallAsyncWithError({{ e|class_name_swift }}.self,
+    {%- else %} rustCallAsync(
+    {%- endmatch %}
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %} { try {{ "$0"|read_swift(return_type) }} }) {
+    {%- else %} { _ in }) {
+    {%- endmatch %}
+    {{ func.ffi_func().name() }}(
+        {%- if !prefix.is_empty() %}{{ prefix }}, {% endif %}{% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}$0, $1, $2
     )
 }
 {%- endmacro %}
@@ -47,6 +65,10 @@
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
         {{ arg.name()|var_name_swift }}: {{ arg.type_()|type_swift -}}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_swift(arg.type_()) }}
+        {%- else %}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
@@ -60,7 +82,7 @@
         {{- arg.type_()|type_ffi }} {{ arg.name() -}}
         {% if loop.last %}{% else %},{% endif %}
     {%- endfor %}
-    {% if func.arguments().len() > 0 %},{% endif %}NativeRustError *_Nonnull out_err
+    {% if func.arguments().len() > 0 %},{% endif %}RustCallStatus *_Nonnull out_status
 
 {%- endmacro -%}
 
@@ -71,3 +93,25 @@
 {%- macro try(func) %}
 {%- match func.throws() %}{% when Some with (e) %}try{% else %}try!{% endmatch %}
 {%- endmacro -%}
+
+{#
+// Render the doc comment from the IDL for a definition, if it has one.
+#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with(docstring) %}
+{{ docstring|docstring_swift(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#
+// Mark a `[Deprecated]` definition as such, with an attribute.
+#}
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some with(message) %}
+{{ message|deprecated_swift(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
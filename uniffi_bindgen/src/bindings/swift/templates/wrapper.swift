@@ -1,11 +1,26 @@
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
+{%- match ci.namespace_docstring() %}
+{%- when Some with(docstring) %}
+
+{{ docstring|docstring_swift(0) }}
+{%- else %}
+{%- endmatch %}
 
 import Foundation
+{%- for module in config.bindings.custom_type_imports() %}
+import {{ module }}
+{%- endfor %}
 import uniffi_{{ ci.namespace() }}
 
 {% include "RustBufferTemplate.swift" %}
 {% include "RustBufferHelper.swift" %}
+{% if ci.iter_callback_interface_definitions().len() > 0 || ci.has_async_functions() -%}
+{% include "ConcurrentHandleMap.swift" %}
+{%- endif %}
+{% if ci.iter_callback_interface_definitions().len() > 0 -%}
+{% include "CallbackInterfaceRuntime.swift" %}
+{%- endif %}
 
 // Public interface members begin here.
 
@@ -15,16 +30,46 @@ import uniffi_{{ ci.namespace() }}
 
 {% include "ErrorTemplate.swift" %}
 
+{% if ci.has_async_functions() -%}
+{% include "AsyncRuntime.swift" %}
+{%- endif %}
+
+{% if ci.iter_iterator_item_types().len() > 0 -%}
+{% include "RustIteratorRuntime.swift" %}
+{%- endif %}
+
 {%- for rec in ci.iter_record_definitions() %}
 {% include "RecordTemplate.swift" %}
 {% endfor %}
 
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Union with (name, members) %}
+{% include "UnionTemplate.swift" %}
+{%- when Type::Custom with (name, builtin) %}
+{% include "CustomTypeTemplate.swift" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for c in ci.iter_constant_definitions() %}
+public let {{ c.name()|var_name_swift }}: {{ c.type_()|type_swift }} = {{ c.value()|literal_swift(c.type_()) }}
+{%- endfor %}
+
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.swift" %}
 {% endfor %}
 
 {% for obj in ci.iter_object_definitions() %}
+{%- if obj.is_base() %}
+{% include "BaseObjectTemplate.swift" %}
+{%- else %}
 {% include "ObjectTemplate.swift" %}
+{%- endif %}
+{% endfor %}
+
+{% for cbi in ci.iter_callback_interface_definitions() %}
+{% include "CallbackInterfaceTemplate.swift" %}
 {% endfor %}
 
 {% import "macros.swift" as swift %}
\ No newline at end of file
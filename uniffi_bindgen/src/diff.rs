@@ -0,0 +1,782 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Compare two versions of a component interface.
+//!
+//! This module walks an old and a new [`ComponentInterface`] side by side and reports each
+//! difference between them as a [`Change`]. Every change knows which target languages it would
+//! break, in the sense that foreign code written against the bindings for the old interface
+//! could stop compiling or start behaving differently once it's using bindings generated from
+//! the new one. For example, adding a variant to an enum breaks an exhaustive `when` in Kotlin
+//! or `switch` in Swift, but is harmless to Python code.
+//!
+//! Items are matched up by name, so renaming something is reported as a removal followed by an
+//! addition. Changes that are only visible to the rust side of the component, such as whether
+//! an argument is passed `[ByRef]`, aren't reported, and neither are docstring or
+//! `[Deprecated]` changes.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::bindings::TargetLanguage;
+use crate::interface::*;
+
+const ALL_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+];
+
+/// A single difference between two versions of a component interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    description: String,
+    breaking_in: Vec<TargetLanguage>,
+}
+
+impl Change {
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The target languages in which this change could break existing consumers.
+    pub fn breaking_in(&self) -> &[TargetLanguage] {
+        &self.breaking_in
+    }
+
+    pub fn is_breaking_in(&self, language: TargetLanguage) -> bool {
+        self.breaking_in.contains(&language)
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_in.is_empty()
+    }
+
+    /// The same change, as seen by consumers in the given target languages only, so that it's
+    /// compatible if it doesn't break any of them.
+    pub fn for_languages(&self, languages: &[TargetLanguage]) -> Change {
+        Change {
+            description: self.description.clone(),
+            breaking_in: self
+                .breaking_in
+                .iter()
+                .filter(|language| languages.contains(language))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_breaking() {
+            let languages: Vec<_> = self.breaking_in.iter().map(ToString::to_string).collect();
+            write!(
+                f,
+                "breaking ({}): {}",
+                languages.join(", "),
+                self.description
+            )
+        } else {
+            write!(f, "compatible: {}", self.description)
+        }
+    }
+}
+
+/// List the changes between two versions of a component interface.
+///
+/// The changes are listed in a stable order, following the order in which the items they
+/// apply to are declared.
+pub fn diff(old: &ComponentInterface, new: &ComponentInterface) -> Vec<Change> {
+    let mut differ = Differ::default();
+    if old.namespace() != new.namespace() {
+        differ.breaking(
+            ALL_LANGUAGES,
+            format!(
+                "namespace `{}` was renamed to `{}`",
+                old.namespace(),
+                new.namespace()
+            ),
+        );
+    }
+    differ.diff_functions(
+        &old.iter_function_definitions(),
+        &new.iter_function_definitions(),
+    );
+    differ.diff_constants(
+        "",
+        old.iter_constant_definitions().iter().collect(),
+        new.iter_constant_definitions().iter().collect(),
+    );
+    differ.diff_objects(
+        &old.iter_object_definitions(),
+        &new.iter_object_definitions(),
+    );
+    differ.diff_callback_interfaces(
+        &old.iter_callback_interface_definitions(),
+        &new.iter_callback_interface_definitions(),
+    );
+    differ.diff_records(
+        &old.iter_record_definitions(),
+        &new.iter_record_definitions(),
+    );
+    differ.diff_enums(&old.iter_enum_definitions(), &new.iter_enum_definitions());
+    differ.diff_errors(&old.iter_error_definitions(), &new.iter_error_definitions());
+    differ.changes
+}
+
+// Anything that can be matched up by name between the old and the new interface.
+trait Named {
+    fn name(&self) -> &str;
+}
+
+macro_rules! impl_named {
+    ($($T:ty),*) => {
+        $(impl Named for $T {
+            fn name(&self) -> &str {
+                <$T>::name(self)
+            }
+        })*
+    };
+}
+
+impl_named!(
+    Function,
+    Object,
+    Constructor,
+    Method,
+    Property,
+    Constant,
+    CallbackInterface,
+    Record,
+    Field,
+    Enum,
+    Error,
+    Variant,
+    Argument
+);
+
+// Split two lists of items into the ones that were removed, the ones present in both (as
+// `(old, new)` pairs) and the ones that were added.
+#[allow(clippy::type_complexity)]
+fn match_by_name<'a, T: Named>(
+    old: Vec<&'a T>,
+    new: Vec<&'a T>,
+) -> (Vec<&'a T>, Vec<(&'a T, &'a T)>, Vec<&'a T>) {
+    let mut removed = Vec::new();
+    let mut common = Vec::new();
+    for &o in &old {
+        match new.iter().find(|n| n.name() == o.name()) {
+            Some(&n) => common.push((o, n)),
+            None => removed.push(o),
+        }
+    }
+    let added = new
+        .into_iter()
+        .filter(|n| !old.iter().any(|o| o.name() == n.name()))
+        .collect();
+    (removed, common, added)
+}
+
+// Whether the items present in both lists appear in a different relative order.
+fn is_reordered<T: Named>(old: &[&T], new: &[&T]) -> bool {
+    let names = |items: &[&T], others: &[&T]| -> Vec<String> {
+        items
+            .iter()
+            .filter(|i| others.iter().any(|o| o.name() == i.name()))
+            .map(|i| i.name().to_string())
+            .collect()
+    };
+    names(old, new) != names(new, old)
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn breaking(&mut self, languages: &[TargetLanguage], description: String) {
+        self.changes.push(Change {
+            description,
+            breaking_in: languages.to_vec(),
+        })
+    }
+
+    fn compatible(&mut self, description: String) {
+        self.breaking(&[], description)
+    }
+
+    // Items that foreign code can only use, not implement, can be added freely but not removed.
+    fn diff_membership<T: Named>(&mut self, kind: &str, removed: &[&T], added: &[&T]) {
+        for item in removed {
+            self.breaking(
+                ALL_LANGUAGES,
+                format!("{} `{}` was removed", kind, item.name()),
+            );
+        }
+        for item in added {
+            self.compatible(format!("{} `{}` was added", kind, item.name()));
+        }
+    }
+
+    fn diff_functions(&mut self, old: &[Function], new: &[Function]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("function", &removed, &added);
+        for (o, n) in common {
+            let what = format!("function `{}`", o.name());
+            self.diff_arguments(&what, &o.arguments(), &n.arguments(), false);
+            self.diff_return_type(&what, o.return_type(), n.return_type());
+            self.diff_throws(&what, o.throws(), n.throws());
+            self.diff_async(&what, o.is_async(), n.is_async());
+        }
+    }
+
+    fn diff_objects(&mut self, old: &[Object], new: &[Object]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("interface", &removed, &added);
+        for (o, n) in common {
+            if o.parent() != n.parent() {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!(
+                        "interface `{}` changed its parent from {} to {}",
+                        o.name(),
+                        o.parent().map_or("nothing".into(), |p| format!("`{}`", p)),
+                        n.parent().map_or("nothing".into(), |p| format!("`{}`", p)),
+                    ),
+                );
+            }
+            let (removed, common, added) = match_by_name(o.constructors(), n.constructors());
+            self.diff_membership(
+                &format!("constructor of interface `{}`", o.name()),
+                &removed,
+                &added,
+            );
+            for (oc, nc) in common {
+                let what = format!("constructor `{}` of interface `{}`", oc.name(), o.name());
+                self.diff_arguments(&what, &oc.arguments(), &nc.arguments(), false);
+                self.diff_throws(&what, oc.throws(), nc.throws());
+            }
+            let (removed, common, added) = match_by_name(o.methods(), n.methods());
+            self.diff_membership(
+                &format!("method of interface `{}`", o.name()),
+                &removed,
+                &added,
+            );
+            for (om, nm) in common {
+                let what = format!("method `{}` of interface `{}`", om.name(), o.name());
+                if om.is_static() != nm.is_static() {
+                    self.breaking(
+                        ALL_LANGUAGES,
+                        format!(
+                            "{} became {}",
+                            what,
+                            if nm.is_static() {
+                                "static"
+                            } else {
+                                "an instance method"
+                            }
+                        ),
+                    );
+                }
+                self.diff_arguments(&what, &om.arguments(), &nm.arguments(), false);
+                self.diff_return_type(&what, om.return_type(), nm.return_type());
+                self.diff_throws(&what, om.throws(), nm.throws());
+                self.diff_async(&what, om.is_async(), nm.is_async());
+            }
+            let (removed, common, added) = match_by_name(o.properties(), n.properties());
+            self.diff_membership(
+                &format!("attribute of interface `{}`", o.name()),
+                &removed,
+                &added,
+            );
+            for (op, np) in common {
+                let what = format!("attribute `{}` of interface `{}`", op.name(), o.name());
+                self.diff_type(&what, &op.type_(), &np.type_());
+                match (op.is_readonly(), np.is_readonly()) {
+                    (false, true) => {
+                        self.breaking(ALL_LANGUAGES, format!("{} became readonly", what))
+                    }
+                    (true, false) => self.compatible(format!("{} became writable", what)),
+                    _ => (),
+                }
+            }
+            self.diff_constants(
+                &format!(" of interface `{}`", o.name()),
+                o.constants(),
+                n.constants(),
+            );
+        }
+    }
+
+    // Constants are defined in the bindings, so a new value only changes what foreign code sees
+    // once it's using the new bindings. The `scope` says where they're declared, and is empty
+    // for the namespace's constants.
+    fn diff_constants(&mut self, scope: &str, old: Vec<&Constant>, new: Vec<&Constant>) {
+        let (removed, common, added) = match_by_name(old, new);
+        self.diff_membership(&format!("constant{}", scope), &removed, &added);
+        for (oc, nc) in common {
+            let what = format!("constant `{}`{}", oc.name(), scope);
+            self.diff_type(&what, &oc.type_(), &nc.type_());
+            if oc.type_() == nc.type_() && oc.value() != nc.value() {
+                self.compatible(format!("{} changed its value", what));
+            }
+        }
+    }
+
+    // Callback interfaces are implemented by foreign code rather than called by it, so the
+    // rules are reversed: new methods break existing implementations, and any change to the
+    // signature of a method means that implementations no longer match it.
+    fn diff_callback_interfaces(&mut self, old: &[CallbackInterface], new: &[CallbackInterface]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("callback interface", &removed, &added);
+        for (o, n) in common {
+            let (removed, common, added) = match_by_name(o.methods(), n.methods());
+            for m in removed {
+                // Kotlin implementations mark their methods with `override`, which is an error
+                // once there's nothing left to override. Elsewhere the method just goes unused.
+                self.breaking(
+                    &[TargetLanguage::Kotlin],
+                    format!(
+                        "method `{}` of callback interface `{}` was removed",
+                        m.name(),
+                        o.name()
+                    ),
+                );
+            }
+            for m in added {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!(
+                        "method `{}` was added to callback interface `{}`",
+                        m.name(),
+                        o.name()
+                    ),
+                );
+            }
+            for (om, nm) in common {
+                let what = format!(
+                    "method `{}` of callback interface `{}`",
+                    om.name(),
+                    o.name()
+                );
+                self.diff_arguments(&what, &om.arguments(), &nm.arguments(), true);
+                self.diff_return_type(&what, om.return_type(), nm.return_type());
+            }
+        }
+    }
+
+    fn diff_records(&mut self, old: &[Record], new: &[Record]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("dictionary", &removed, &added);
+        for (o, n) in common {
+            self.diff_fields(
+                &format!("dictionary `{}`", o.name()),
+                &o.fields(),
+                &n.fields(),
+            );
+        }
+    }
+
+    fn diff_enums(&mut self, old: &[Enum], new: &[Enum]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("enum", &removed, &added);
+        for (o, n) in common {
+            let what = format!("enum `{}`", o.name());
+            if o.is_flat() != n.is_flat() {
+                // Flat enums and enums with data are different kinds of type in every language.
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!(
+                        "{} {}",
+                        what,
+                        if n.is_flat() {
+                            "no longer has variants with fields"
+                        } else {
+                            "now has variants with fields"
+                        }
+                    ),
+                );
+                continue;
+            }
+            let (old_variants, new_variants) = (o.variants(), n.variants());
+            let (removed, common, added) =
+                match_by_name(old_variants.clone(), new_variants.clone());
+            for v in removed {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!("variant `{}` of {} was removed", v.name(), what),
+                );
+            }
+            for v in added {
+                // Kotlin `when` and Swift `switch` expressions must be exhaustive.
+                self.breaking(
+                    &[TargetLanguage::Kotlin, TargetLanguage::Swift],
+                    format!("variant `{}` was added to {}", v.name(), what),
+                );
+            }
+            // Variants are sent across the FFI as a `u32` discriminant, numbered from one in
+            // the order they're declared. For flat enums the discriminant is also visible
+            // to consumers, as the value of the Python enum and the Kotlin enum's ordinal.
+            if n.is_flat() {
+                for (old_index, v) in old_variants.iter().enumerate() {
+                    match new_variants.iter().position(|nv| nv.name() == v.name()) {
+                        Some(new_index) if new_index != old_index => self.breaking(
+                            &[TargetLanguage::Kotlin, TargetLanguage::Python],
+                            format!(
+                                "discriminant of variant `{}` of {} changed from {} to {}",
+                                v.name(),
+                                what,
+                                old_index + 1,
+                                new_index + 1
+                            ),
+                        ),
+                        _ => (),
+                    }
+                }
+            }
+            for (ov, nv) in common {
+                self.diff_fields(
+                    &format!("variant `{}` of {}", ov.name(), what),
+                    &ov.fields(),
+                    &nv.fields(),
+                );
+            }
+        }
+    }
+
+    fn diff_errors(&mut self, old: &[Error], new: &[Error]) {
+        let (removed, common, added) = match_by_name(old.iter().collect(), new.iter().collect());
+        self.diff_membership("error", &removed, &added);
+        for (o, n) in common {
+            let what = format!("error `{}`", o.name());
+            let (removed, common, added) = match_by_name(o.variants(), n.variants());
+            for v in removed {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!("variant `{}` of {} was removed", v.name(), what),
+                );
+            }
+            // Errors are caught rather than matched on, so catching code doesn't need to
+            // handle every variant.
+            for v in added {
+                self.compatible(format!("variant `{}` was added to {}", v.name(), what));
+            }
+            for (ov, nv) in common {
+                self.diff_fields(
+                    &format!("variant `{}` of {}", ov.name(), what),
+                    &ov.fields(),
+                    &nv.fields(),
+                );
+            }
+        }
+    }
+
+    // Fields are passed positionally to the constructors of Kotlin and Python classes, and
+    // by label but in declaration order to Swift's memberwise initializers.
+    fn diff_fields(&mut self, what: &str, old: &[&Field], new: &[&Field]) {
+        let (removed, common, added) = match_by_name(old.to_vec(), new.to_vec());
+        for f in removed {
+            self.breaking(
+                ALL_LANGUAGES,
+                format!("field `{}` of {} was removed", f.name(), what),
+            );
+        }
+        for f in added {
+            if f.default_value().is_some() {
+                self.compatible(format!(
+                    "field `{}` with a default value was added to {}",
+                    f.name(),
+                    what
+                ));
+            } else {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!("required field `{}` was added to {}", f.name(), what),
+                );
+            }
+        }
+        for (of, nf) in common {
+            let field = format!("field `{}` of {}", of.name(), what);
+            self.diff_type(&field, &of.type_(), &nf.type_());
+            self.diff_default(&field, of.default_value(), nf.default_value());
+        }
+        if is_reordered(old, new) {
+            self.breaking(ALL_LANGUAGES, format!("fields of {} were reordered", what));
+        }
+    }
+
+    // Arguments can be passed by name in all of the target languages (and must be in Swift),
+    // so renaming one breaks callers just as removing it would.
+    //
+    // When `implemented` is true the arguments belong to a method that foreign code implements,
+    // which means that even an argument with a default value has to be accepted by existing
+    // implementations.
+    fn diff_arguments(
+        &mut self,
+        what: &str,
+        old: &[&Argument],
+        new: &[&Argument],
+        implemented: bool,
+    ) {
+        let mut renamed = HashSet::new();
+        for (i, oa) in old.iter().enumerate() {
+            match new.iter().find(|na| na.name() == oa.name()) {
+                Some(na) => {
+                    let arg = format!("argument `{}` of {}", oa.name(), what);
+                    self.diff_type(&arg, &oa.type_(), &na.type_());
+                    if !implemented {
+                        self.diff_default(&arg, oa.default_value(), na.default_value());
+                    }
+                }
+                None => match new.get(i) {
+                    Some(na)
+                        if !old.iter().any(|a| a.name() == na.name())
+                            && na.type_() == oa.type_() =>
+                    {
+                        renamed.insert(na.name());
+                        self.breaking(
+                            ALL_LANGUAGES,
+                            format!(
+                                "argument `{}` of {} was renamed to `{}`",
+                                oa.name(),
+                                what,
+                                na.name()
+                            ),
+                        );
+                    }
+                    _ => self.breaking(
+                        ALL_LANGUAGES,
+                        format!("argument `{}` of {} was removed", oa.name(), what),
+                    ),
+                },
+            }
+        }
+        for na in new {
+            if old.iter().any(|oa| oa.name() == na.name()) || renamed.contains(na.name()) {
+                continue;
+            }
+            if na.default_value().is_some() && !implemented {
+                self.compatible(format!(
+                    "argument `{}` with a default value was added to {}",
+                    na.name(),
+                    what
+                ));
+            } else {
+                self.breaking(
+                    ALL_LANGUAGES,
+                    format!("required argument `{}` was added to {}", na.name(), what),
+                );
+            }
+        }
+        if is_reordered(old, new) {
+            self.breaking(
+                ALL_LANGUAGES,
+                format!("arguments of {} were reordered", what),
+            );
+        }
+    }
+
+    fn diff_type(&mut self, what: &str, old: &Type, new: &Type) {
+        if old != new {
+            self.breaking(
+                ALL_LANGUAGES,
+                format!(
+                    "{} changed type from `{}` to `{}`",
+                    what,
+                    old.canonical_name(),
+                    new.canonical_name()
+                ),
+            );
+        }
+    }
+
+    fn diff_default(&mut self, what: &str, old: Option<Literal>, new: Option<Literal>) {
+        match (old, new) {
+            (Some(_), None) => {
+                self.breaking(ALL_LANGUAGES, format!("{} lost its default value", what))
+            }
+            (None, Some(_)) => self.compatible(format!("{} gained a default value", what)),
+            (Some(o), Some(n)) if o != n => {
+                self.compatible(format!("{} changed its default value", what))
+            }
+            _ => (),
+        }
+    }
+
+    fn diff_return_type(&mut self, what: &str, old: Option<&Type>, new: Option<&Type>) {
+        match (old, new) {
+            (Some(o), Some(n)) => self.diff_type(&format!("return value of {}", what), o, n),
+            (None, None) => (),
+            (_, Some(n)) => self.breaking(
+                ALL_LANGUAGES,
+                format!("{} now returns `{}`", what, n.canonical_name()),
+            ),
+            (Some(_), None) => {
+                self.breaking(ALL_LANGUAGES, format!("{} no longer returns a value", what))
+            }
+        }
+    }
+
+    fn diff_throws(&mut self, what: &str, old: Option<&str>, new: Option<&str>) {
+        match (old, new) {
+            // Swift callers have to `try` a throwing call; elsewhere errors are unchecked.
+            (None, Some(n)) => self.breaking(
+                &[TargetLanguage::Swift],
+                format!("{} now throws `{}`", what, n),
+            ),
+            (Some(o), None) => self.compatible(format!("{} no longer throws `{}`", what, o)),
+            (Some(o), Some(n)) if o != n => self.breaking(
+                ALL_LANGUAGES,
+                format!("{} now throws `{}` instead of `{}`", what, n, o),
+            ),
+            _ => (),
+        }
+    }
+
+    fn diff_async(&mut self, what: &str, old: bool, new: bool) {
+        if old != new {
+            self.breaking(
+                ALL_LANGUAGES,
+                format!(
+                    "{} is {} async",
+                    what,
+                    if new { "now" } else { "no longer" }
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        const OLD: &str = r#"
+            namespace test {
+                u32 add(u32 a, u32 b);
+                void reset();
+            };
+            enum Color { "Red", "Green" };
+            dictionary Point { i32 x; i32 y; };
+        "#;
+        const NEW: &str = r#"
+            namespace test {
+                u32 add(u32 a, u64 b, optional u32 c = 0);
+            };
+            enum Color { "Green", "Red" };
+            dictionary Point { i32 x; i32 y; i32 z; string label = "p"; };
+        "#;
+        let old = ComponentInterface::from_webidl(OLD).unwrap();
+        let new = ComponentInterface::from_webidl(NEW).unwrap();
+        assert!(diff(&old, &old).is_empty());
+
+        let changes = diff(&old, &new);
+        let lines: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "breaking (kotlin, swift, python): function `reset` was removed",
+                "breaking (kotlin, swift, python): argument `b` of function `add` changed type from `u32` to `u64`",
+                "compatible: argument `c` with a default value was added to function `add`",
+                "breaking (kotlin, swift, python): required field `z` was added to dictionary `Point`",
+                "compatible: field `label` with a default value was added to dictionary `Point`",
+                "breaking (kotlin, python): discriminant of variant `Red` of enum `Color` changed from 1 to 2",
+                "breaking (kotlin, python): discriminant of variant `Green` of enum `Color` changed from 2 to 1",
+            ]
+        );
+        assert!(!changes[5].is_breaking_in(TargetLanguage::Swift));
+        assert!(!changes[2].is_breaking());
+    }
+
+    #[test]
+    fn test_diff_constants() {
+        const OLD: &str = r#"
+            namespace test {};
+            [Constants]
+            interface test {
+                const u32 VERSION = 1;
+                const boolean ENABLED = true;
+                const u8 LIMIT = 10;
+            };
+            interface Widget {
+                const u8 SIZE = 4;
+            };
+        "#;
+        const NEW: &str = r#"
+            namespace test {};
+            [Constants]
+            interface test {
+                const u32 VERSION = 2;
+                const u16 LIMIT = 10;
+                const double RATIO = 0.5;
+            };
+            interface Widget {
+                const u8 SIZE = 5;
+            };
+        "#;
+        let old = ComponentInterface::from_webidl(OLD).unwrap();
+        let new = ComponentInterface::from_webidl(NEW).unwrap();
+        let lines: Vec<String> = diff(&old, &new).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "breaking (kotlin, swift, python): constant `ENABLED` was removed",
+                "compatible: constant `RATIO` was added",
+                "compatible: constant `VERSION` changed its value",
+                "breaking (kotlin, swift, python): constant `LIMIT` changed type from `u8` to `u16`",
+                "compatible: constant `SIZE` of interface `Widget` changed its value",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_for_languages() {
+        const OLD: &str = r#"
+            namespace test {
+                Color mix(Color a, Color b);
+            };
+            enum Color { "Red", "Green" };
+        "#;
+        const NEW: &str = r#"
+            namespace test {
+                [Throws=MixError]
+                Color mix(Color a, Color b);
+            };
+            enum Color { "Red", "Green", "Blue" };
+            [Error]
+            enum MixError { "Unmixable" };
+        "#;
+        let old = ComponentInterface::from_webidl(OLD).unwrap();
+        let new = ComponentInterface::from_webidl(NEW).unwrap();
+        let lines_for = |languages: &[TargetLanguage]| -> Vec<String> {
+            diff(&old, &new)
+                .iter()
+                .map(|change| change.for_languages(languages).to_string())
+                .collect()
+        };
+        assert_eq!(
+            lines_for(ALL_LANGUAGES),
+            vec![
+                "breaking (swift): function `mix` now throws `MixError`",
+                "breaking (kotlin, swift): variant `Blue` was added to enum `Color`",
+                "compatible: error `MixError` was added",
+            ]
+        );
+        assert_eq!(
+            lines_for(&[TargetLanguage::Kotlin]),
+            vec![
+                "compatible: function `mix` now throws `MixError`",
+                "breaking (kotlin): variant `Blue` was added to enum `Color`",
+                "compatible: error `MixError` was added",
+            ]
+        );
+        assert!(!diff(&old, &new).iter().any(|change| change
+            .for_languages(&[TargetLanguage::Python])
+            .is_breaking()));
+    }
+}
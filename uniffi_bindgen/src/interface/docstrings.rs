@@ -0,0 +1,84 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Doc comments for the definitions in a component interface.
+//!
+//! Weedle throws away comments when parsing the IDL, but the syntax nodes it produces borrow
+//! their identifiers from the IDL source. So we make a separate pass over the source to find
+//! all the `///` doc comments, and then find the one for each definition by working out which
+//! line of the source its identifier is on.
+//!
+//! A doc comment documents the definition that starts on the line immediately after it,
+//! possibly with some lines of `[Attributes]` in between:
+//!
+//! ```text
+//! /// A point in two dimensional space.
+//! dictionary Point {
+//!     /// Distance from the origin along the x axis.
+//!     double x;
+//!     double y;
+//! };
+//! ```
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub(crate) struct DocComments {
+    // Where the IDL source is in memory, to check that identifiers are borrowed from it.
+    source_start: usize,
+    source_len: usize,
+    // The offset at which each line of the IDL source starts.
+    line_starts: Vec<usize>,
+    // The text of each doc comment, keyed by the line of the definition that it documents.
+    comments: HashMap<usize, String>,
+}
+
+impl DocComments {
+    pub(crate) fn from_source(source: &str) -> Self {
+        let mut line_starts = Vec::new();
+        let mut comments = HashMap::new();
+        let mut pending: Vec<&str> = Vec::new();
+        let mut offset = 0;
+        for (lineno, line) in source.split('\n').enumerate() {
+            line_starts.push(offset);
+            offset += line.len() + 1;
+            let line = line.trim();
+            if let Some(text) = line.strip_prefix("///") {
+                pending.push(text.strip_prefix(' ').unwrap_or(text));
+            } else if line.starts_with('[') && line.ends_with(']') {
+                // Attributes can come between a doc comment and its definition.
+            } else {
+                // Anything else ends the doc comment, but only a definition gets to keep it.
+                if !pending.is_empty() && !line.is_empty() && !line.starts_with("//") {
+                    comments.insert(lineno, pending.join("\n"));
+                }
+                pending.clear();
+            }
+        }
+        DocComments {
+            source_start: source.as_ptr() as usize,
+            source_len: source.len(),
+            line_starts,
+            comments,
+        }
+    }
+
+    /// Take the doc comment for the definition with the given identifier, if it has one.
+    ///
+    /// The identifier must be borrowed from the IDL source that this was created from, so that
+    /// we can tell where it is. Each doc comment can only be taken once, so if there's more
+    /// than one definition on a line, the doc comment goes to the first one we ask about
+    /// (e.g. to a dictionary rather than to its first field).
+    pub(crate) fn take(&mut self, identifier: &str) -> Option<String> {
+        let offset = (identifier.as_ptr() as usize).checked_sub(self.source_start)?;
+        if offset >= self.source_len {
+            return None;
+        }
+        let lineno = match self.line_starts.binary_search(&offset) {
+            Ok(lineno) => lineno,
+            Err(next_lineno) => next_lineno - 1,
+        };
+        self.comments.remove(&lineno)
+    }
+}
@@ -45,7 +45,7 @@
 //!   * Error messages and general developer experience leave a lot to be desired.
 
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashSet},
     convert::TryFrom,
     hash::{Hash, Hasher},
     str::FromStr,
@@ -53,7 +53,11 @@ use std::{
 
 use anyhow::bail;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 
+mod docstrings;
+use docstrings::DocComments;
+mod rust_source;
 pub mod types;
 use types::TypeUniverse;
 pub use types::{FFIType, Type};
@@ -61,7 +65,7 @@ pub use types::{FFIType, Type};
 /// The main public interface for this module, representing the complete details of an interface exposed
 /// by a rust component and the details of consuming it via an extern-C FFI layer.
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ComponentInterface {
     /// Every ComponentInterface gets tagged with the version of uniffi used to create it.
     /// This helps us avoid using a lib compiled with one version together with bindings created
@@ -71,12 +75,25 @@ pub struct ComponentInterface {
     types: TypeUniverse,
     /// The unique prefix that we'll use for namespacing when exposing this component's API.
     namespace: String,
+    namespace_docstring: Option<String>,
     /// The high-level API provided by the component.
     enums: Vec<Enum>,
     records: Vec<Record>,
     functions: Vec<Function>,
     objects: Vec<Object>,
+    callback_interfaces: Vec<CallbackInterface>,
     errors: Vec<Error>,
+    /// The constants declared in the namespace's `[Constants]` interface.
+    constants: Vec<Constant>,
+    /// The name of the `[Constants]` interface, which must match the namespace.
+    #[serde(skip)]
+    constants_interface: Option<String>,
+    /// The names of the interfaces that other interfaces inherit from.
+    #[serde(skip)]
+    base_interfaces: HashSet<String>,
+    /// The doc comments from the IDL, which are only needed while building the interface.
+    #[serde(skip)]
+    doc_comments: DocComments,
 }
 
 impl<'ci> ComponentInterface {
@@ -84,26 +101,90 @@ impl<'ci> ComponentInterface {
     pub fn from_webidl(idl: &str) -> Result<Self> {
         let mut ci = Self::default();
         ci.uniffi_version = env!("CARGO_PKG_VERSION").to_string();
+        let idl = idl.trim();
         // There's some lifetime thing with the errors returned from weedle::parse
         // that my own lifetime is too short to worry about figuring out; unwrap and move on.
-        let defns = weedle::parse(idl.trim()).unwrap();
+        let defns = weedle::parse(idl).unwrap();
         // We process the WebIDL definitions in two passes.
         // First, go through and look for all the named types.
         ci.types.add_type_definitions_from(defns.as_slice())?;
-        // With those names resolved, we can build a complete representation of the API.
+        // Interfaces that others inherit from are converted differently, so we need to know
+        // which ones they are before converting any of them.
+        ci.base_interfaces = defns
+            .iter()
+            .filter_map(|defn| match defn {
+                weedle::Definition::Interface(d) => d.inheritance,
+                _ => None,
+            })
+            .map(|inheritance| inheritance.identifier.0.to_string())
+            .collect();
+        // With those names resolved, we can build a complete representation of the API,
+        // picking up the doc comments that the parser left behind as we go.
+        ci.doc_comments = DocComments::from_source(idl);
         APIBuilder::process(&defns, &mut ci)?;
+        ci.doc_comments = Default::default();
         if ci.namespace.is_empty() {
             bail!("missing namespace definition");
         }
+        if let Some(name) = ci.constants_interface.take() {
+            if name != ci.namespace {
+                bail!(
+                    "[Constants] interface {} must be named after the namespace {}",
+                    name,
+                    ci.namespace
+                );
+            }
+        }
+        ci.resolve_inheritance()?;
+        ci.check_base_interfaces_not_passed()?;
+        ci.check_object_values()?;
+        ci.check_map_keys()?;
+        ci.check_default_values()?;
         // Now that the high-level API is settled, we can derive the low-level FFI.
         ci.derive_ffi_funcs()?;
         Ok(ci)
     }
 
+    /// Parse a `ComponentInterface` from the definitions exported by a crate's rust source.
+    ///
+    /// This gives the same result as parsing the equivalent IDL, which is what the [`rust_source`]
+    /// module works out from the `#[uniffi_macros::export]` attributes in the source.
+    pub fn from_rust_source(source: &str) -> Result<Self> {
+        Self::from_webidl(&rust_source::idl_from_rust_source(source)?)
+    }
+
+    /// Parse a `ComponentInterface` from the JSON that [`ComponentInterface::to_json`] produces.
+    ///
+    /// The JSON must come from the same version of uniffi, since the representation can change
+    /// between versions, and the version is part of the checksum that the scaffolding checks.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let ci: Self = serde_json::from_str(json)?;
+        if ci.uniffi_version != env!("CARGO_PKG_VERSION") {
+            bail!(
+                "The component interface was dumped by uniffi-bindgen {}, but this is version {}",
+                ci.uniffi_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        Ok(ci)
+    }
+
+    /// Serialize the `ComponentInterface` as JSON, for tools that want to work with the parsed interface.
+    ///
+    /// This includes the FFI functions that the scaffolding exposes, as well as the high-level API
+    /// and every type that it uses.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
     pub fn namespace(&self) -> &str {
         self.namespace.as_str()
     }
 
+    pub fn namespace_docstring(&self) -> Option<&str> {
+        self.namespace_docstring.as_deref()
+    }
+
     pub fn iter_enum_definitions(&self) -> Vec<Enum> {
         self.enums.to_vec()
     }
@@ -120,12 +201,62 @@ impl<'ci> ComponentInterface {
         self.objects.to_vec()
     }
 
+    pub fn iter_callback_interface_definitions(&self) -> Vec<CallbackInterface> {
+        self.callback_interfaces.to_vec()
+    }
+
     pub fn iter_error_definitions(&self) -> Vec<Error> {
         self.errors.to_vec()
     }
 
+    pub fn iter_constant_definitions(&self) -> Vec<Constant> {
+        self.constants.to_vec()
+    }
+
+    /// Whether any function or method is `[Async]`, so the bindings need their async runtime support.
+    pub fn has_async_functions(&self) -> bool {
+        self.functions.iter().any(|f| f.is_async())
+            || self
+                .objects
+                .iter()
+                .flat_map(|obj| obj.methods.iter())
+                .any(|m| m.is_async())
+    }
+
     pub fn iter_types(&self) -> Vec<Type> {
-        self.types.iter_known_types().collect()
+        // Sorted so that the generated code doesn't change from one run to the next.
+        let mut types: Vec<Type> = self.types.iter_known_types().collect();
+        types.sort_by_key(|t| t.canonical_name());
+        types
+    }
+
+    /// List the types of the items produced by the iterators that functions and methods return.
+    pub fn iter_iterator_item_types(&self) -> Vec<Type> {
+        let mut item_types: Vec<Type> = self
+            .types
+            .iter_known_types()
+            .filter_map(|t| match t {
+                Type::Iterator(item_type) => Some(*item_type),
+                _ => None,
+            })
+            .collect();
+        item_types.sort_by_key(|t| t.canonical_name());
+        item_types
+    }
+
+    /// List the namespaces of the other components that define the external types used by this one.
+    pub fn iter_external_namespaces(&self) -> Vec<String> {
+        let mut namespaces: Vec<String> = self
+            .types
+            .iter_known_types()
+            .filter_map(|t| match t {
+                Type::External(_, namespace) => Some(namespace),
+                _ => None,
+            })
+            .collect();
+        namespaces.sort();
+        namespaces.dedup();
+        namespaces
     }
 
     /// Calculate a numeric checksum for this ComponentInterface.
@@ -240,17 +371,43 @@ impl<'ci> ComponentInterface {
         }
     }
 
-    /// Builtin FFI function for freeing a string.
-    /// This is needed for foreign-language code when dealing with errors, so that it can free
-    /// the error message string.
-    /// TODO: make our error class return the message in a `RustBuffer` so we can free it using
-    /// the exisiting bytebuffer-freeing function rather than a special one.
-    pub fn ffi_string_free(&self) -> FFIFunction {
+    /// FFI function for pulling the next batch of items from an iterator with the given item type.
+    ///
+    /// It takes the iterator's handle and the maximum number of items to return, and returns the
+    /// items serialized like a sequence. An empty batch means the iterator is exhausted.
+    pub fn ffi_iterator_next(&self, item_type: &Type) -> FFIFunction {
+        FFIFunction {
+            name: format!(
+                "ffi_{}_iterator_{}_next",
+                self.ffi_namespace(),
+                item_type.canonical_name()
+            ),
+            arguments: vec![
+                FFIArgument {
+                    name: "handle".to_string(),
+                    type_: FFIType::UInt64,
+                },
+                FFIArgument {
+                    name: "max_items".to_string(),
+                    type_: FFIType::UInt32,
+                },
+            ],
+            return_type: Some(FFIType::RustBuffer),
+        }
+    }
+
+    /// FFI function for freeing an iterator with the given item type, which the foreign-language
+    /// code calls once it's exhausted or when it's no longer needed.
+    pub fn ffi_iterator_free(&self, item_type: &Type) -> FFIFunction {
         FFIFunction {
-            name: format!("ffi_{}_string_free", self.ffi_namespace()),
+            name: format!(
+                "ffi_{}_iterator_{}_free",
+                self.ffi_namespace(),
+                item_type.canonical_name()
+            ),
             arguments: vec![FFIArgument {
-                name: "cstr".to_string(),
-                type_: FFIType::RustCString,
+                name: "handle".to_string(),
+                type_: FFIType::UInt64,
             }],
             return_type: None,
         }
@@ -259,21 +416,38 @@ impl<'ci> ComponentInterface {
     pub fn iter_ffi_function_definitions(&self) -> Vec<FFIFunction> {
         self.objects
             .iter()
+            // Base interfaces are only called through the interfaces that inherit from them.
+            .filter(|obj| !obj.is_base())
             .map(|obj| {
                 vec![obj.ffi_object_free().clone()]
                     .into_iter()
                     .chain(obj.constructors.iter().map(|f| f.ffi_func.clone()))
                     .chain(obj.methods.iter().map(|f| f.ffi_func.clone()))
+                    .chain(
+                        obj.properties
+                            .iter()
+                            .flat_map(|p| p.accessors())
+                            .map(|f| f.ffi_func.clone()),
+                    )
             })
             .flatten()
+            .chain(
+                self.callback_interfaces
+                    .iter()
+                    .map(|cb| cb.ffi_init_callback.clone()),
+            )
             .chain(self.functions.iter().map(|f| f.ffi_func.clone()))
+            .chain(
+                self.iter_iterator_item_types()
+                    .iter()
+                    .flat_map(|t| vec![self.ffi_iterator_next(t), self.ffi_iterator_free(t)]),
+            )
             .chain(
                 vec![
                     self.ffi_rustbuffer_alloc(),
                     self.ffi_rustbuffer_from_bytes(),
                     self.ffi_rustbuffer_free(),
                     self.ffi_rustbuffer_reserve(),
-                    self.ffi_string_free(),
                 ]
                 .iter()
                 .cloned(),
@@ -289,11 +463,64 @@ impl<'ci> ComponentInterface {
         self.types.resolve_type_expression(expr)
     }
 
+    /// Resolve the return type of a function or method, which is an iterator if it's marked
+    /// `[Iterator]`. The IDL declares those as returning a sequence of the items.
+    fn resolve_operation_return_type(
+        &mut self,
+        name: &str,
+        expr: &weedle::types::ReturnType<'_>,
+        attributes: &Attributes,
+    ) -> Result<Option<Type>> {
+        let return_type = self.resolve_return_type_expression(expr)?;
+        if let Some(Type::CallbackInterface(_)) = return_type {
+            bail!("Callback interfaces are not currently supported as return types");
+        }
+        if !attributes.contains_iterator_attr() {
+            return Ok(return_type);
+        }
+        if attributes.contains_async_attr() {
+            bail!("{} cannot be both [Async] and [Iterator]", name);
+        }
+        match return_type {
+            Some(Type::Sequence(item_type)) => {
+                Ok(Some(self.types.add_known_type(Type::Iterator(item_type))?))
+            }
+            _ => bail!("[Iterator] {} must return a sequence of the items", name),
+        }
+    }
+
+    /// Take the doc comment for the definition with the given identifier from the IDL source.
+    fn take_docstring(&mut self, identifier: &str) -> Option<String> {
+        self.doc_comments.take(identifier)
+    }
+
+    /// Resolve the return type of a function or method, which is `None` if it doesn't return anything.
+    fn resolve_return_type_expression(
+        &mut self,
+        expr: &weedle::types::ReturnType<'_>,
+    ) -> Result<Option<Type>> {
+        Ok(match expr {
+            weedle::types::ReturnType::Undefined(_) => None,
+            // WebIDL used to spell `undefined` as `void`, and that's what most of our IDL files use.
+            // The parser doesn't know it as a keyword any more, so it looks like a named type.
+            weedle::types::ReturnType::Type(weedle::types::Type::Single(
+                weedle::types::SingleType::NonAny(weedle::types::NonAnyType::Identifier(
+                    weedle::types::MayBeNull {
+                        type_: weedle::common::Identifier("void"),
+                        q_mark: None,
+                    },
+                )),
+            )) => None,
+            weedle::types::ReturnType::Type(t) => Some(self.resolve_type_expression(t)?),
+        })
+    }
+
     fn add_namespace_definition(&mut self, defn: Namespace) -> Result<()> {
         if !self.namespace.is_empty() {
             bail!("duplicate namespace definition");
         }
         self.namespace.push_str(&defn.name);
+        self.namespace_docstring = defn.docstring;
         Ok(())
     }
 
@@ -323,12 +550,281 @@ impl<'ci> ComponentInterface {
         Ok(())
     }
 
+    fn add_callback_interface_definition(&mut self, defn: CallbackInterface) -> Result<()> {
+        // Note that there will be no duplicates thanks to the previous type-finding pass.
+        self.callback_interfaces.push(defn);
+        Ok(())
+    }
+
     fn add_error_definition(&mut self, defn: Error) -> Result<()> {
         // Note that there will be no duplicates thanks to the previous type-finding pass.
         self.errors.push(defn);
         Ok(())
     }
 
+    fn add_constant_definitions(&mut self, name: &str, defns: Vec<Constant>) -> Result<()> {
+        if let Some(previous) = &self.constants_interface {
+            bail!("duplicate [Constants] interface: {} and {}", previous, name);
+        }
+        self.constants_interface = Some(name.to_string());
+        self.constants = defns;
+        Ok(())
+    }
+
+    /// Give each interface that inherits from another the methods it inherits, now that all
+    /// of the interfaces are known.
+    ///
+    /// The inherited methods are copied into the interface as if it had declared them itself,
+    /// so they get FFI functions that take its own handles.
+    fn resolve_inheritance(&mut self) -> Result<()> {
+        let bases = self.objects.clone();
+        for obj in self.objects.iter_mut() {
+            let mut ancestors = vec![obj.name.clone()];
+            let mut next_parent = obj.parent.clone();
+            while let Some(parent_name) = next_parent {
+                let parent = match bases.iter().find(|o| o.name == parent_name) {
+                    Some(parent) => parent,
+                    None => bail!(
+                        "Interface {} inherits from {}, which isn't an interface",
+                        obj.name,
+                        parent_name
+                    ),
+                };
+                if ancestors.contains(&parent.name) {
+                    bail!("Interface {} inherits from itself", obj.name);
+                }
+                ancestors.push(parent.name.clone());
+                // The methods of a `[Threadsafe]` interface must all take `&self`.
+                if obj.threadsafe && !parent.threadsafe {
+                    bail!(
+                        "[Threadsafe] interface {} can't inherit from {}, which isn't [Threadsafe]",
+                        obj.name,
+                        parent.name
+                    );
+                }
+                for meth in parent.methods.iter().filter(|m| m.inherited_from.is_none()) {
+                    if obj.methods.iter().any(|m| m.name == meth.name)
+                        || obj.constructors.iter().any(|c| c.name == meth.name)
+                        || obj.properties.iter().any(|p| p.name == meth.name)
+                    {
+                        bail!(
+                            "Interface {} has a member named {} that clashes with a method it inherits from {}",
+                            obj.name,
+                            meth.name,
+                            parent.name
+                        );
+                    }
+                    let mut meth = meth.clone();
+                    meth.object_name = obj.name.clone();
+                    meth.inherited_from = Some(parent.name.clone());
+                    obj.methods.push(meth);
+                }
+                next_parent = parent.parent.clone();
+            }
+            obj.depth = ancestors.len() - 1;
+        }
+        // Some bindings need each class to be defined after the one it inherits from.
+        self.objects.sort_by_key(|obj| obj.depth);
+        Ok(())
+    }
+
+    /// Check that the objects which are passed across the FFI are all `[Threadsafe]`.
+    ///
+    /// Rust and the foreign-language code share the objects that are passed between them,
+    /// so they must be usable from both sides at once.
+    fn check_object_values(&self) -> Result<()> {
+        for type_ in self.iter_value_types() {
+            if let Type::Object(name) = type_ {
+                if !self
+                    .objects
+                    .iter()
+                    .any(|obj| obj.name == name && obj.threadsafe)
+                {
+                    bail!(
+                        "Interface {} can't be passed across the FFI, since it isn't [Threadsafe]",
+                        name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// List the types of the values that are passed across the FFI, including the types
+    /// nested inside them, but not the objects that methods are called on.
+    ///
+    /// Every interface is a known type, so this looks at the types that are actually used instead.
+    fn iter_value_types(&self) -> Vec<Type> {
+        let mut used_types = Vec::new();
+        let mut add_signature = |args: Vec<&Argument>, return_type: Option<&Type>| {
+            used_types.extend(args.iter().map(|a| a.type_()));
+            used_types.extend(return_type.cloned());
+        };
+        for func in &self.functions {
+            add_signature(func.arguments(), func.return_type());
+        }
+        for obj in &self.objects {
+            for cons in obj.constructors() {
+                add_signature(cons.arguments(), None);
+            }
+            for meth in obj.methods() {
+                add_signature(meth.arguments(), meth.return_type());
+            }
+            for meth in obj.properties().iter().flat_map(|p| p.accessors()) {
+                add_signature(meth.arguments(), meth.return_type());
+            }
+        }
+        for cbi in &self.callback_interfaces {
+            for meth in cbi.methods() {
+                add_signature(meth.arguments(), meth.return_type());
+            }
+        }
+        for rec in &self.records {
+            used_types.extend(rec.fields().iter().map(|f| f.type_()));
+        }
+        for variant in self
+            .enums
+            .iter()
+            .flat_map(|e| e.variants())
+            .chain(self.errors.iter().flat_map(|e| e.variants()))
+        {
+            used_types.extend(variant.fields().iter().map(|f| f.type_()));
+        }
+        let mut value_types = Vec::new();
+        while let Some(type_) = used_types.pop() {
+            match &type_ {
+                Type::Optional(t) | Type::Sequence(t) | Type::Iterator(t) => {
+                    used_types.push(*t.clone())
+                }
+                Type::Map(k, v) => used_types.extend(vec![*k.clone(), *v.clone()]),
+                Type::Union(_, members) => used_types.extend(members.clone()),
+                _ => {}
+            }
+            value_types.push(type_);
+        }
+        value_types
+    }
+
+    /// Check that interfaces which others inherit from are never passed across the FFI.
+    ///
+    /// Base interfaces are only implemented by the interfaces that inherit from them,
+    /// so there's no object that could be passed over the FFI in their place.
+    fn check_base_interfaces_not_passed(&self) -> Result<()> {
+        for type_ in self.iter_value_types() {
+            match type_ {
+                Type::Object(name) if self.base_interfaces.contains(&name) => bail!(
+                    "Interface {} can't be passed across the FFI, since other interfaces inherit from it",
+                    name
+                ),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that maps are only keyed by enums without associated data.
+    ///
+    /// Associated data may contain values that aren't hashable in the foreign language, such as
+    /// records. We can't check this while resolving the map type, since the enum may be defined later.
+    fn check_map_keys(&self) -> Result<()> {
+        for type_ in self.iter_types() {
+            if let Type::Map(key_type, _) = type_ {
+                if let Type::Enum(name) = key_type.as_ref() {
+                    if !self.enums.iter().any(|e| e.name() == name && e.is_flat()) {
+                        bail!(
+                            "Enum {} has associated data, so it can't be a map key",
+                            name
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that default values come last, and that enum defaults name a variant of the enum.
+    ///
+    /// Not every language lets you skip over an argument with a default value, so they need to come
+    /// after all the ones that don't have a default, and the same goes for record fields.
+    fn check_default_values(&self) -> Result<()> {
+        let args = |args: Vec<&Argument>| -> Vec<(Type, Option<Literal>)> {
+            args.iter()
+                .map(|a| (a.type_(), a.default_value()))
+                .collect()
+        };
+        let mut lists = Vec::new();
+        for func in &self.functions {
+            lists.push((func.name().to_string(), args(func.arguments())));
+        }
+        for obj in &self.objects {
+            for cons in obj.constructors() {
+                lists.push((
+                    format!("{}.{}", obj.name(), cons.name()),
+                    args(cons.arguments()),
+                ));
+            }
+            for meth in obj.methods() {
+                lists.push((
+                    format!("{}.{}", obj.name(), meth.name()),
+                    args(meth.arguments()),
+                ));
+            }
+        }
+        // Rust always passes every argument when calling a callback interface,
+        // so defaults wouldn't do anything.
+        for cbi in &self.callback_interfaces {
+            for meth in cbi.methods() {
+                if meth.arguments().iter().any(|a| a.default_value().is_some()) {
+                    bail!(
+                        "{}.{}: callback interface methods can't have default values",
+                        cbi.name(),
+                        meth.name()
+                    );
+                }
+            }
+        }
+        for rec in &self.records {
+            let fields = rec
+                .fields()
+                .iter()
+                .map(|f| (f.type_(), f.default_value()))
+                .collect();
+            lists.push((rec.name().to_string(), fields));
+        }
+        for (name, values) in lists {
+            if let Some(first) = values.iter().position(|(_, v)| v.is_some()) {
+                if values[first..].iter().any(|(_, v)| v.is_none()) {
+                    bail!(
+                        "{}: values with defaults must come after those without",
+                        name
+                    );
+                }
+            }
+            for (type_, value) in values {
+                let type_ = match type_ {
+                    Type::Optional(t) => *t,
+                    t => t,
+                };
+                if let (Some(Literal::Enum(variant)), Type::Enum(enum_name)) = (value, type_) {
+                    let is_variant = self.enums.iter().any(|e| {
+                        e.name() == enum_name
+                            && e.is_flat()
+                            && e.variants().iter().any(|v| v.name() == variant)
+                    });
+                    if !is_variant {
+                        bail!(
+                            "{}: {} is not a variant of enum {}",
+                            name,
+                            variant,
+                            enum_name
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn derive_ffi_funcs(&mut self) -> Result<()> {
         let ci_prefix = self.ffi_namespace().to_string();
         for func in self.functions.iter_mut() {
@@ -337,6 +833,9 @@ impl<'ci> ComponentInterface {
         for obj in self.objects.iter_mut() {
             obj.derive_ffi_funcs(&ci_prefix)?;
         }
+        for callback in self.callback_interfaces.iter_mut() {
+            callback.derive_ffi_funcs(&ci_prefix);
+        }
         Ok(())
     }
 }
@@ -358,7 +857,9 @@ impl Hash for ComponentInterface {
         self.records.hash(state);
         self.functions.hash(state);
         self.objects.hash(state);
+        self.callback_interfaces.hash(state);
         self.errors.hash(state);
+        self.constants.hash(state);
     }
 }
 
@@ -405,9 +906,34 @@ impl APIBuilder for weedle::Definition<'_> {
                 ci.add_record_definition(rec)
             }
             weedle::Definition::Interface(d) => {
+                // Enums and errors with associated data are declared as an `interface`
+                // with a special attribute.
+                let attributes = match &d.attributes {
+                    Some(attrs) => Attributes::try_from(attrs)?,
+                    None => Attributes(Vec::new()),
+                };
+                if attributes.contains_error_attr() {
+                    let err = d.convert(ci)?;
+                    ci.add_error_definition(err)
+                } else if attributes.contains_enum_attr() {
+                    let e = d.convert(ci)?;
+                    ci.add_enum_definition(e)
+                } else if attributes.contains_constants_attr() {
+                    // The namespace's constants are declared in an interface of the same name,
+                    // since WebIDL doesn't allow constants in a `namespace`.
+                    let constants: Vec<Constant> = d.convert(ci)?;
+                    ci.add_constant_definitions(d.identifier.0, constants)
+                } else {
+                    let obj = d.convert(ci)?;
+                    ci.add_object_definition(obj)
+                }
+            }
+            weedle::Definition::CallbackInterface(d) => {
                 let obj = d.convert(ci)?;
-                ci.add_object_definition(obj)
+                ci.add_callback_interface_definition(obj)
             }
+            // Typedefs only name a type, so they were fully dealt with in the `TypeFinder` pass.
+            weedle::Definition::Typedef(_) => Ok(()),
             _ => bail!("don't know how to deal with {:?}", self),
         }
     }
@@ -426,9 +952,10 @@ impl APIBuilder for weedle::Definition<'_> {
 /// Yeah, this is a bit of mis-match between WebIDL and our notion of a component,
 /// but it's close enough to get us up and running for now.
 ///
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct Namespace {
     name: String,
+    docstring: Option<String>,
 }
 
 impl APIBuilder for weedle::NamespaceDefinition<'_> {
@@ -436,8 +963,10 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         if self.attributes.is_some() {
             bail!("namespace attributes are not supported yet");
         }
+        let docstring = ci.take_docstring(self.identifier.0);
         ci.add_namespace_definition(Namespace {
             name: self.identifier.0.to_string(),
+            docstring,
         })?;
         for func in self.members.body.convert(ci)? {
             ci.add_function_definition(func)?;
@@ -473,13 +1002,14 @@ impl<U, T: APIConverter<U>> APIConverter<Vec<U>> for Vec<T> {
 /// and has a corresponding standalone function in the foreign language bindings.
 ///
 /// In the FFI, this will be a standalone function with appropriately lowered types.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     name: String,
     arguments: Vec<Argument>,
     return_type: Option<Type>,
     ffi_func: FFIFunction,
     attributes: Attributes,
+    docstring: Option<String>,
 }
 
 impl Function {
@@ -500,12 +1030,29 @@ impl Function {
         self.attributes.get_throws_err()
     }
 
+    /// Whether the function is `[Async]`, so that the Rust function returns a `Future`.
+    pub fn is_async(&self) -> bool {
+        self.attributes.contains_async_attr()
+    }
+
+    /// The message to show users of the function if it's `[Deprecated]`.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     fn derive_ffi_func(&mut self, ci_prefix: &str) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push_str("_");
         self.ffi_func.name.push_str(&self.name);
         self.ffi_func.arguments = self.arguments.iter().map(FFIArgument::from).collect();
         self.ffi_func.return_type = self.return_type.as_ref().map(FFIType::from);
+        if self.is_async() {
+            self.ffi_func.make_async();
+        }
         Ok(())
     }
 }
@@ -518,6 +1065,8 @@ impl Hash for Function {
         //  - its `name` property includes a checksum derived from  the very
         //    hash value we're trying to calculate here, so excluding it
         //    avoids a weird circular depenendency in the calculation.
+        //
+        // Nor do we include the docstring, which doesn't change how the FFI works.
         self.name.hash(state);
         self.arguments.hash(state);
         self.return_type.hash(state);
@@ -536,25 +1085,23 @@ impl APIConverter<Function> for weedle::namespace::NamespaceMember<'_> {
 
 impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Function> {
-        let return_type = match &self.return_type {
-            weedle::types::ReturnType::Void(_) => None,
-            weedle::types::ReturnType::Type(t) => Some(ci.resolve_type_expression(t)?),
+        let identifier = match self.identifier {
+            None => bail!("anonymous functions are not supported {:?}", self),
+            Some(id) => id.0,
         };
-        if let Some(Type::Object(_)) = return_type {
-            bail!("Objects cannot currently be returned from functions");
-        }
+        let attributes = match &self.attributes {
+            Some(attr) => Attributes::try_from(attr)?,
+            None => Attributes(Vec::new()),
+        };
+        let return_type =
+            ci.resolve_operation_return_type(identifier, &self.return_type, &attributes)?;
         Ok(Function {
-            name: match self.identifier {
-                None => bail!("anonymous functions are not supported {:?}", self),
-                Some(id) => id.0.to_string(),
-            },
+            name: identifier.to_string(),
+            docstring: ci.take_docstring(identifier),
             return_type,
             arguments: self.args.body.list.convert(ci)?,
             ffi_func: Default::default(),
-            attributes: match &self.attributes {
-                Some(attr) => Attributes::try_from(attr)?,
-                None => Attributes(Vec::new()),
-            },
+            attributes,
         })
     }
 }
@@ -562,9 +1109,10 @@ impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_>
 /// Represents an argument to a function/constructor/method call.
 ///
 /// Each argument has a name and a type, along with some optional metadata.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct Argument {
     name: String,
+    #[serde(rename = "type")]
     type_: Type,
     by_ref: bool,
     optional: bool,
@@ -581,6 +1129,9 @@ impl Argument {
     pub fn by_ref(&self) -> bool {
         self.by_ref
     }
+    pub fn default_value(&self) -> Option<Literal> {
+        self.default.clone()
+    }
 }
 
 impl APIConverter<Argument> for weedle::argument::Argument<'_> {
@@ -595,60 +1146,248 @@ impl APIConverter<Argument> for weedle::argument::Argument<'_> {
 impl APIConverter<Argument> for weedle::argument::SingleArgument<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Argument> {
         let type_ = ci.resolve_type_expression(&self.type_)?;
-        if let Type::Object(_) = type_ {
-            bail!("Objects cannot currently be passed as arguments");
-        }
+        let by_ref = match &self.attributes {
+            None => false,
+            Some(attrs) => Attributes::try_from(attrs)?
+                .0
+                .iter()
+                .any(|attr| matches!(attr, Attribute::ByRef)),
+        };
         Ok(Argument {
             name: self.identifier.0.to_string(),
-            type_,
-            by_ref: match &self.attributes {
-                None => false,
-                Some(attrs) => Attributes::try_from(attrs)?
-                    .0
-                    .iter()
-                    .any(|attr| match attr {
-                        Attribute::ByRef => true,
-                        _ => false,
-                    }),
-            },
+            by_ref,
             optional: self.optional.is_some(),
             default: match self.default {
                 None => None,
-                Some(v) => Some(v.value.convert(ci)?),
+                Some(v) => Some(convert_default_value(&v.value, &type_)?),
             },
+            type_,
         })
     }
 }
 
-/// Represents a simple C-style enum, with named variants.
+/// Represents an enum with named variants, each of which may have named
+/// and typed fields.
+///
+/// Enums are passed across the FFI by serializing to a bytebuffer, with a
+/// u32 indicating the variant followed by the values of any fields.
 ///
-/// In the FFI these are turned into a simple u32.
-#[derive(Debug, Clone, Hash)]
+/// WebIDL `enum` declarations only support simple C-style enums without fields.
+/// To declare an enum whose variants carry data, use an `interface` with the
+/// special `[Enum]` attribute, and declare each variant as if it were an anonymous
+/// method taking the fields as arguments:
+///
+/// ```text
+/// [Enum]
+/// interface Event {
+///   Progress(u64 done, u64 total);
+///   Finished();
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enum {
     name: String,
-    variants: Vec<String>,
+    variants: Vec<Variant>,
+    // "Flat" enums do not have, and will never have, variants with associated data.
+    flat: bool,
+    docstring: Option<String>,
 }
 
 impl Enum {
     pub fn name(&self) -> &str {
         &self.name
     }
-    pub fn variants(&self) -> Vec<&str> {
-        self.variants.iter().map(|v| v.as_str()).collect()
+
+    pub fn variants(&self) -> Vec<&Variant> {
+        self.variants.iter().collect()
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.flat
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl Hash for Enum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring in the hash calculation,
+        // because it doesn't change how the FFI works.
+        self.name.hash(state);
+        self.variants.hash(state);
+        self.flat.hash(state);
     }
 }
 
+// Note that we have two `APIConverter` impls here - one for the `enum` case
+// and one for the `[Enum] interface` case.
+
 impl APIConverter<Enum> for weedle::EnumDefinition<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Enum> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Enum> {
         Ok(Enum {
             name: self.identifier.0.to_string(),
+            docstring: ci.take_docstring(self.identifier.0),
             variants: self
                 .values
                 .body
                 .list
                 .iter()
-                .map(|v| v.0.to_string())
+                .map(|v| Variant {
+                    name: v.0.to_string(),
+                    docstring: ci.take_docstring(v.0),
+                    ..Default::default()
+                })
                 .collect(),
+            flat: true,
+        })
+    }
+}
+
+impl APIConverter<Enum> for weedle::InterfaceDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Enum> {
+        if self.inheritance.is_some() {
+            bail!("interface inheritence is not supported for enum interfaces");
+        }
+        // We don't need to check `self.attributes` here; if calling code has dispatched
+        // to this impl then we already know there was an `[Enum]` attribute.
+        Ok(Enum {
+            name: self.identifier.0.to_string(),
+            docstring: ci.take_docstring(self.identifier.0),
+            variants: self
+                .members
+                .body
+                .iter()
+                .map::<Result<Variant>, _>(|member| match member {
+                    weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
+                    _ => bail!(
+                        "interface member type {:?} not supported in enum interface",
+                        member
+                    ),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            flat: false,
+        })
+    }
+}
+
+/// Represents an individual variant in an Enum.
+///
+/// Each `Variant` has a name and optionally some data fields.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Variant {
+    name: String,
+    fields: Vec<Field>,
+    deprecated: Option<String>,
+    docstring: Option<String>,
+}
+
+impl Variant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fields(&self) -> Vec<&Field> {
+        self.fields.iter().collect()
+    }
+
+    pub fn has_fields(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl Hash for Variant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring in the hash calculation,
+        // because it doesn't change how the FFI works.
+        self.name.hash(state);
+        self.fields.hash(state);
+        self.deprecated.hash(state);
+    }
+}
+
+impl APIConverter<Variant> for weedle::interface::OperationInterfaceMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Variant> {
+        if self.special.is_some() {
+            bail!("special operations not supported");
+        }
+        if self.modifier.is_some() {
+            bail!("method modifiers are not supported")
+        }
+        let attributes = match &self.attributes {
+            Some(attr) => Attributes::try_from(attr)?,
+            None => Attributes(Vec::new()),
+        };
+        if !attributes.only_deprecated_attr() {
+            bail!("enum variant attributes other than [Deprecated] are not supported yet");
+        }
+        // The syntax we use for enum interface members is `Name(type arg, ...);`, which parses
+        // as an anonymous operation where `Name` is the return type. We re-interpret it to
+        // use `Name` as the name of the variant.
+        if self.identifier.is_some() {
+            bail!("enum interface members must not have a method name");
+        }
+        let name: &str = {
+            use weedle::types::{
+                NonAnyType::Identifier, ReturnType, SingleType::NonAny, Type::Single,
+            };
+            match &self.return_type {
+                ReturnType::Type(Single(NonAny(Identifier(id)))) => id.type_.0,
+                _ => bail!("enum interface members must have plain identifiers as names"),
+            }
+        };
+        Ok(Variant {
+            name: name.to_string(),
+            deprecated: attributes.get_deprecated().map(String::from),
+            docstring: ci.take_docstring(name),
+            fields: self
+                .args
+                .body
+                .list
+                .iter()
+                .map(|arg| arg.convert(ci))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+impl APIConverter<Field> for weedle::argument::Argument<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Field> {
+        match self {
+            weedle::argument::Argument::Single(t) => t.convert(ci),
+            weedle::argument::Argument::Variadic(_) => bail!("variadic arguments not supported"),
+        }
+    }
+}
+
+impl APIConverter<Field> for weedle::argument::SingleArgument<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Field> {
+        if self.attributes.is_some() {
+            bail!("enum variant field attributes are not supported yet");
+        }
+        if self.optional.is_some() || self.default.is_some() {
+            bail!("enum variant fields cannot be optional or have default values");
+        }
+        let type_ = ci.resolve_type_expression(&self.type_)?;
+        if let Type::CallbackInterface(_) = type_ {
+            bail!("Callback interfaces cannot currently appear in enum variant fields");
+        }
+        Ok(Field {
+            name: self.identifier.0.to_string(),
+            type_,
+            required: true,
+            default: None,
+            deprecated: None,
+            docstring: None,
         })
     }
 }
@@ -667,12 +1406,24 @@ impl APIConverter<Enum> for weedle::EnumDefinition<'_> {
 ///
 /// TODO:
 ///  - maybe "Class" would be a better name than "Object" here?
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Object {
     name: String,
     constructors: Vec<Constructor>,
     methods: Vec<Method>,
+    properties: Vec<Property>,
+    constants: Vec<Constant>,
     ffi_func_free: FFIFunction,
+    // Threadsafe objects are shared between calls through an `Arc`, rather than each call
+    // taking a lock on them, so their methods can run in parallel.
+    threadsafe: bool,
+    // The interface that this one inherits from, if any.
+    parent: Option<String>,
+    // Interfaces that others inherit from are implemented as a rust trait, rather than a struct.
+    is_base: bool,
+    // How many interfaces this one inherits from, directly or indirectly.
+    depth: usize,
+    docstring: Option<String>,
 }
 
 impl Object {
@@ -681,7 +1432,14 @@ impl Object {
             name,
             constructors: Default::default(),
             methods: Default::default(),
+            properties: Default::default(),
+            constants: Default::default(),
             ffi_func_free: Default::default(),
+            threadsafe: false,
+            parent: None,
+            is_base: false,
+            depth: 0,
+            docstring: None,
         }
     }
 
@@ -693,14 +1451,61 @@ impl Object {
         self.constructors.iter().collect()
     }
 
+    /// The constructor without a `[Name=...]` attribute, if there is one.
+    ///
+    /// The bindings expose this as the foreign-language class's own constructor.
+    pub fn primary_constructor(&self) -> Option<&Constructor> {
+        self.constructors.iter().find(|cons| cons.is_primary())
+    }
+
+    /// The named constructors, which the bindings expose as static factory methods.
+    pub fn alternate_constructors(&self) -> Vec<&Constructor> {
+        self.constructors
+            .iter()
+            .filter(|cons| !cons.is_primary())
+            .collect()
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
 
+    pub fn properties(&self) -> Vec<&Property> {
+        self.properties.iter().collect()
+    }
+
+    pub fn constants(&self) -> Vec<&Constant> {
+        self.constants.iter().collect()
+    }
+
     pub fn ffi_object_free(&self) -> &FFIFunction {
         &self.ffi_func_free
     }
 
+    /// Whether the object is `[Threadsafe]`, so that its methods take `&self` and can be
+    /// called from several threads at once.
+    pub fn is_threadsafe(&self) -> bool {
+        self.threadsafe
+    }
+
+    /// The interface that this one inherits from, if any.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Whether other interfaces inherit from this one.
+    ///
+    /// Such interfaces are implemented in rust as a trait, rather than a struct, and the bindings
+    /// expose them as abstract classes. They don't have constructors or an FFI of their own, and
+    /// their methods are called through the interfaces that inherit them.
+    pub fn is_base(&self) -> bool {
+        self.is_base
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     fn derive_ffi_funcs(&mut self, ci_prefix: &str) -> Result<()> {
         self.ffi_func_free.name = format!("ffi_{}_{}_object_free", ci_prefix, self.name);
         self.ffi_func_free.arguments = vec![FFIArgument {
@@ -714,6 +1519,12 @@ impl Object {
         for meth in self.methods.iter_mut() {
             meth.derive_ffi_func(ci_prefix, &self.name)?
         }
+        for prop in self.properties.iter_mut() {
+            prop.getter.derive_ffi_func(ci_prefix, &self.name)?;
+            if let Some(setter) = prop.setter.as_mut() {
+                setter.derive_ffi_func(ci_prefix, &self.name)?
+            }
+        }
         Ok(())
     }
 }
@@ -726,37 +1537,135 @@ impl Hash for Object {
         //  - its `name` property includes a checksum derived from  the very
         //    hash value we're trying to calculate here, so excluding it
         //    avoids a weird circular depenendency in the calculation.
+        //
+        // Nor do we include the docstring, which doesn't change how the FFI works.
         self.name.hash(state);
         self.constructors.hash(state);
         self.methods.hash(state);
+        self.properties.hash(state);
+        self.constants.hash(state);
+        self.threadsafe.hash(state);
+        self.parent.hash(state);
     }
 }
 
 impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Object> {
-        if self.attributes.is_some() {
-            bail!("interface attributes are not supported yet");
-        }
-        if self.inheritance.is_some() {
-            bail!("interface inheritence is not supported");
+        let attributes = match &self.attributes {
+            Some(attrs) => Attributes::try_from(attrs)?,
+            None => Attributes(Vec::new()),
+        };
+        if !attributes.0.iter().all(|attr| attr.is_threadsafe()) {
+            bail!("interface attributes other than [Threadsafe] are not supported yet");
         }
         let mut object = Object::new(self.identifier.0.to_string());
+        object.threadsafe = attributes.contains_threadsafe_attr();
+        object.parent = self
+            .inheritance
+            .map(|inheritance| inheritance.identifier.0.to_string());
+        object.is_base = ci.base_interfaces.contains(&object.name);
+        object.docstring = ci.take_docstring(self.identifier.0);
         for member in &self.members.body {
+            // A base interface is only a trait in rust, so it can only declare the methods
+            // that the interfaces inheriting from it implement.
+            if object.is_base
+                && !matches!(member, weedle::interface::InterfaceMember::Operation(op) if op.modifier.is_none())
+            {
+                bail!(
+                    "Interface {} can only declare methods, since other interfaces inherit from it",
+                    object.name
+                );
+            }
             match member {
                 weedle::interface::InterfaceMember::Constructor(t) => {
                     object.constructors.push(t.convert(ci)?);
                 }
                 weedle::interface::InterfaceMember::Operation(t) => {
-                    let mut method = t.convert(ci)?;
+                    let mut method: Method = t.convert(ci)?;
+                    // The future for an async method holds on to the object until it completes,
+                    // which is only possible for objects that aren't locked for each call.
+                    if method.is_async() && !method.is_static() && !object.threadsafe {
+                        bail!(
+                            "Async method {} is only supported on [Threadsafe] interfaces",
+                            method.name()
+                        );
+                    }
+                    if method.is_async() && object.is_base {
+                        bail!(
+                            "Async method {} isn't supported on interfaces that others inherit from",
+                            method.name()
+                        );
+                    }
                     method.object_name.push_str(object.name.as_str());
                     object.methods.push(method);
                 }
+                weedle::interface::InterfaceMember::Attribute(t) => {
+                    let mut prop: Property = t.convert(ci)?;
+                    prop.getter.object_name.push_str(object.name.as_str());
+                    if let Some(setter) = prop.setter.as_mut() {
+                        setter.object_name.push_str(object.name.as_str());
+                    }
+                    object.properties.push(prop);
+                }
+                weedle::interface::InterfaceMember::Const(t) => {
+                    let constant: Constant = t.convert(ci)?;
+                    if object.constants.iter().any(|c| c.name() == constant.name()) {
+                        bail!(
+                            "Interface {} has more than one constant named {}",
+                            object.name,
+                            constant.name()
+                        );
+                    }
+                    object.constants.push(constant);
+                }
                 _ => bail!("no support for interface member type {:?} yet", member),
             }
         }
-        if object.constructors.is_empty() {
+        if object.constructors.is_empty() && !object.is_base {
             object.constructors.push(Default::default());
         }
+        let mut names = std::collections::HashSet::new();
+        for cons in &object.constructors {
+            if !names.insert(cons.name()) {
+                if cons.is_primary() {
+                    bail!(
+                        "Interface {} has more than one constructor without a [Name=...]",
+                        object.name
+                    );
+                }
+                bail!(
+                    "Interface {} has more than one constructor named {}",
+                    object.name,
+                    cons.name()
+                );
+            }
+        }
+        if let Some(meth) = object.methods.iter().find(|m| names.contains(m.name())) {
+            bail!(
+                "Interface {} has a constructor and a method both named {}",
+                object.name,
+                meth.name()
+            );
+        }
+        // Properties are accessed through methods named after them, which mustn't clash
+        // with anything else, and the property itself mustn't clash with a method in the bindings.
+        for prop in &object.properties {
+            let clashes = |name: &str| {
+                names.contains(name)
+                    || object.methods.iter().any(|m| m.name() == name)
+                    || object.properties.iter().any(|p| p.name() == name)
+            };
+            if clashes(prop.getter.name())
+                || prop.setter.iter().any(|s| clashes(s.name()))
+                || object.methods.iter().any(|m| m.name() == prop.name())
+            {
+                bail!(
+                    "Interface {} has an attribute {} that clashes with another member",
+                    object.name,
+                    prop.name()
+                );
+            }
+        }
         Ok(object)
     }
 }
@@ -765,7 +1674,7 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
 //
 // In the FFI, this will be a function that returns a handle for an instance
 // of the corresponding object type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Constructor {
     name: String,
     arguments: Vec<Argument>,
@@ -790,6 +1699,14 @@ impl Constructor {
         self.attributes.get_throws_err()
     }
 
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.name == "new"
+    }
+
     fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push_str("_");
@@ -829,14 +1746,23 @@ impl Default for Constructor {
 
 impl APIConverter<Constructor> for weedle::interface::ConstructorInterfaceMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Constructor> {
+        let attributes = match &self.attributes {
+            Some(attr) => Attributes::try_from(attr)?,
+            None => Attributes(Vec::new()),
+        };
+        if attributes.contains_async_attr() {
+            bail!("constructors cannot be [Async]");
+        }
+        if attributes.contains_iterator_attr() {
+            bail!("constructors cannot be [Iterator]");
+        }
         Ok(Constructor {
-            name: String::from("new"), // TODO: get the name from an attribute maybe?
+            // Constructors with a `[Name=...]` call the associated function of that name,
+            // everything else calls `new`.
+            name: attributes.get_name().unwrap_or("new").to_string(),
             arguments: self.args.body.list.convert(ci)?,
             ffi_func: Default::default(),
-            attributes: match &self.attributes {
-                Some(attr) => Attributes::try_from(attr)?,
-                None => Attributes(Vec::new()),
-            },
+            attributes,
         })
     }
 }
@@ -845,14 +1771,18 @@ impl APIConverter<Constructor> for weedle::interface::ConstructorInterfaceMember
 //
 // The in FFI, this will be a function whose first argument is a handle for an
 // instance of the corresponding object type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Method {
     name: String,
     object_name: String,
+    is_static: bool,
     return_type: Option<Type>,
     arguments: Vec<Argument>,
     ffi_func: FFIFunction,
     attributes: Attributes,
+    // The interface that declares the method, if it's inherited from another one.
+    inherited_from: Option<String>,
+    docstring: Option<String>,
 }
 
 impl Method {
@@ -872,6 +1802,11 @@ impl Method {
         &self.ffi_func
     }
 
+    /// Static methods don't take an instance of the object, so they're called without a handle.
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
     pub fn first_argument(&self) -> Argument {
         Argument {
             name: "handle".to_string(),
@@ -888,18 +1823,44 @@ impl Method {
         self.attributes.get_throws_err()
     }
 
+    /// Whether the method is `[Async]`, so that the Rust method returns a `Future`.
+    pub fn is_async(&self) -> bool {
+        self.attributes.contains_async_attr()
+    }
+
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
+    /// The interface that declares this method, if the object inherits it from another interface.
+    pub fn inherited_from(&self) -> Option<&str> {
+        self.inherited_from.as_deref()
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push_str("_");
         self.ffi_func.name.push_str(obj_prefix);
         self.ffi_func.name.push_str("_");
         self.ffi_func.name.push_str(&self.name);
-        self.ffi_func.arguments = vec![self.first_argument()]
+        let handle = if self.is_static {
+            None
+        } else {
+            Some(self.first_argument())
+        };
+        self.ffi_func.arguments = handle
             .iter()
             .chain(self.arguments.iter())
             .map(FFIArgument::from)
             .collect();
         self.ffi_func.return_type = self.return_type.as_ref().map(FFIType::from);
+        if self.is_async() {
+            self.ffi_func.make_async();
+        }
         Ok(())
     }
 }
@@ -912,11 +1873,15 @@ impl Hash for Method {
         //  - its `name` property includes a checksum derived from  the very
         //    hash value we're trying to calculate here, so excluding it
         //    avoids a weird circular depenendency in the calculation.
+        //
+        // Nor do we include the docstring, which doesn't change how the FFI works.
         self.name.hash(state);
         self.object_name.hash(state);
+        self.is_static.hash(state);
         self.arguments.hash(state);
         self.return_type.hash(state);
         self.attributes.hash(state);
+        self.inherited_from.hash(state);
     }
 }
 
@@ -925,66 +1890,378 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
         if self.special.is_some() {
             bail!("special operations not supported");
         }
-        if let Some(weedle::interface::StringifierOrStatic::Stringifier(_)) = self.modifier {
-            bail!("stringifiers are not supported");
-        }
-        let return_type = match &self.return_type {
-            weedle::types::ReturnType::Void(_) => None,
-            weedle::types::ReturnType::Type(t) => Some(ci.resolve_type_expression(t)?),
+        let is_static = match self.modifier {
+            Some(weedle::interface::StringifierOrStatic::Stringifier(_)) => {
+                bail!("stringifiers are not supported")
+            }
+            Some(weedle::interface::StringifierOrStatic::Static(_)) => true,
+            None => false,
         };
-        if let Some(Type::Object(_)) = return_type {
-            bail!("Objects cannot currently be returned from functions");
-        }
+        let identifier = match self.identifier {
+            None => bail!("anonymous methods are not supported {:?}", self),
+            Some(id) => id.0,
+        };
+        let attributes = match &self.attributes {
+            Some(attr) => Attributes::try_from(attr)?,
+            None => Attributes(Vec::new()),
+        };
+        let return_type =
+            ci.resolve_operation_return_type(identifier, &self.return_type, &attributes)?;
         Ok(Method {
-            name: match self.identifier {
-                None => bail!("anonymous methods are not supported {:?}", self),
-                Some(id) => id.0.to_string(),
-            },
+            name: identifier.to_string(),
+            docstring: ci.take_docstring(identifier),
             // We don't know the name of the containing `Object` at this point, fill it in later.
             object_name: Default::default(),
+            is_static,
             arguments: self.args.body.list.convert(ci)?,
             return_type,
             ffi_func: Default::default(),
-            attributes: match &self.attributes {
-                Some(attr) => Attributes::try_from(attr)?,
-                None => Attributes(Vec::new()),
-            },
+            attributes,
+            inherited_from: None,
+        })
+    }
+}
+
+// Represents an attribute of an object type, which the bindings expose as a property.
+//
+// In the FFI, reading and writing the attribute call a getter and setter method named after it.
+// A `readonly` attribute only has a getter.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct Property {
+    name: String,
+    #[serde(rename = "type")]
+    type_: Type,
+    getter: Method,
+    setter: Option<Method>,
+}
+
+impl Property {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> Type {
+        self.type_.clone()
+    }
+
+    pub fn getter(&self) -> &Method {
+        &self.getter
+    }
+
+    pub fn setter(&self) -> Option<&Method> {
+        self.setter.as_ref()
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.setter.is_none()
+    }
+
+    pub fn accessors(&self) -> Vec<&Method> {
+        std::iter::once(&self.getter)
+            .chain(self.setter.iter())
+            .collect()
+    }
+}
+
+impl APIConverter<Property> for weedle::interface::AttributeInterfaceMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Property> {
+        // Swift can't throw errors from property setters, so there's no [Throws] here.
+        if self.attributes.is_some() {
+            bail!("interface attribute attributes are not supported yet");
+        }
+        if self.modifier.is_some() {
+            bail!("stringifier, inherit and static attributes are not supported");
+        }
+        let name = self.identifier.0.to_string();
+        let type_ = ci.resolve_type_expression(&self.type_)?;
+        if let Type::CallbackInterface(_) = type_ {
+            bail!("Callback interfaces are not currently supported as attributes");
+        }
+        let accessor = |name: String, arguments: Vec<Argument>, return_type: Option<Type>| Method {
+            name,
+            // We don't know the name of the containing `Object` at this point, fill it in later.
+            object_name: Default::default(),
+            is_static: false,
+            return_type,
+            arguments,
+            ffi_func: Default::default(),
+            attributes: Attributes(Vec::new()),
+            inherited_from: None,
+            docstring: None,
+        };
+        let getter = accessor(format!("get_{}", name), Vec::new(), Some(type_.clone()));
+        let setter = if self.readonly.is_some() {
+            None
+        } else {
+            let value = Argument {
+                name: "value".to_string(),
+                type_: type_.clone(),
+                by_ref: false,
+                optional: false,
+                default: None,
+            };
+            Some(accessor(format!("set_{}", name), vec![value], None))
+        };
+        Ok(Property {
+            name,
+            type_,
+            getter,
+            setter,
         })
     }
 }
 
-/// An error marked in the WebIDL with an [Error]
-/// attribute. Used to define exceptions/errors in the bindings
-/// as well as defining the From<Error> for ExternError
-/// needed for the different errors to cross the FFI.
-#[derive(Debug, Clone, Default, Hash)]
+// Represents a constant on an object type, or in the namespace.
+//
+// Constants never cross the FFI. The bindings define them directly from the value in the IDL,
+// and the scaffolding checks that the Rust constant of the same name agrees, which is an
+// associated constant for an object, or a crate-level one for the namespace.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct Constant {
+    name: String,
+    #[serde(rename = "type")]
+    type_: Type,
+    value: Literal,
+}
+
+impl Constant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> Type {
+        self.type_.clone()
+    }
+
+    pub fn value(&self) -> Literal {
+        self.value.clone()
+    }
+}
+
+impl APIConverter<Constant> for weedle::interface::ConstMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Constant> {
+        if self.attributes.is_some() {
+            bail!("constant attributes are not supported yet");
+        }
+        let type_ = ci.resolve_type_expression(&self.const_type)?;
+        let is_valid_type = matches!(
+            type_,
+            Type::Int8
+                | Type::UInt8
+                | Type::Int16
+                | Type::UInt16
+                | Type::Int32
+                | Type::UInt32
+                | Type::Int64
+                | Type::UInt64
+                | Type::Float32
+                | Type::Float64
+                | Type::Boolean
+        );
+        if !is_valid_type {
+            bail!(
+                "Constants must be booleans, integers or floats, not {:?}",
+                type_
+            );
+        }
+        // Constant values are a subset of default values, so they're checked the same way.
+        let value = match self.const_value {
+            weedle::literal::ConstValue::Boolean(b) => weedle::literal::DefaultValue::Boolean(b),
+            weedle::literal::ConstValue::Float(f) => weedle::literal::DefaultValue::Float(f),
+            weedle::literal::ConstValue::Integer(i) => weedle::literal::DefaultValue::Integer(i),
+            weedle::literal::ConstValue::Null(n) => weedle::literal::DefaultValue::Null(n),
+        };
+        Ok(Constant {
+            name: self.identifier.0.to_string(),
+            value: convert_default_value(&value, &type_)?,
+            type_,
+        })
+    }
+}
+
+impl APIConverter<Vec<Constant>> for weedle::InterfaceDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Vec<Constant>> {
+        if self.inheritance.is_some() {
+            bail!("interface inheritence is not supported for [Constants] interfaces");
+        }
+        let attributes = match &self.attributes {
+            Some(attrs) => Attributes::try_from(attrs)?,
+            None => Attributes(Vec::new()),
+        };
+        if attributes.0.len() > 1 {
+            bail!("[Constants] interfaces can't have any other attributes");
+        }
+        let mut constants: Vec<Constant> = Vec::new();
+        for member in &self.members.body {
+            let constant: Constant = match member {
+                weedle::interface::InterfaceMember::Const(t) => t.convert(ci)?,
+                _ => bail!(
+                    "interface member type {:?} not supported in [Constants] interface",
+                    member
+                ),
+            };
+            if constants.iter().any(|c| c.name() == constant.name()) {
+                bail!("duplicate constant definition: {}", constant.name());
+            }
+            constants.push(constant);
+        }
+        Ok(constants)
+    }
+}
+
+/// A "callback interface" is an interface that is implemented by foreign-language code
+/// and consumed by the rust component, for example to receive notifications or to
+/// delegate some work back to the application.
+///
+/// In WebIDL these correspond to the `callback interface` keywords.
+///
+/// At the FFI layer, instances are represented by an opaque integer handle into a handle map
+/// maintained by the foreign language code. Rust calls back into the foreign language through
+/// a single function per callback interface, registered at load time via `ffi_init_callback`,
+/// passing the handle, the index of the method to invoke and its arguments serialized into a
+/// `RustBuffer`. Method index zero is reserved for telling the foreign language code that rust
+/// has dropped its reference to the handle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackInterface {
+    name: String,
+    methods: Vec<Method>,
+    ffi_init_callback: FFIFunction,
+}
+
+impl CallbackInterface {
+    fn new(name: String) -> CallbackInterface {
+        CallbackInterface {
+            name,
+            methods: Default::default(),
+            ffi_init_callback: Default::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn methods(&self) -> Vec<&Method> {
+        self.methods.iter().collect()
+    }
+
+    pub fn ffi_init_callback(&self) -> &FFIFunction {
+        &self.ffi_init_callback
+    }
+
+    fn derive_ffi_funcs(&mut self, ci_prefix: &str) {
+        self.ffi_init_callback.name = format!("ffi_{}_{}_init_callback", ci_prefix, self.name);
+        self.ffi_init_callback.arguments = vec![FFIArgument {
+            name: "callback_stub".to_string(),
+            type_: FFIType::ForeignCallback,
+        }];
+        self.ffi_init_callback.return_type = None;
+    }
+}
+
+impl Hash for CallbackInterface {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the FFIFunc in the hash calculation, because:
+        //  - it is entirely determined by the other fields,
+        //    so excluding it is safe.
+        //  - its `name` property includes a checksum derived from  the very
+        //    hash value we're trying to calculate here, so excluding it
+        //    avoids a weird circular depenendency in the calculation.
+        self.name.hash(state);
+        self.methods.hash(state);
+    }
+}
+
+impl APIConverter<CallbackInterface> for weedle::CallbackInterfaceDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<CallbackInterface> {
+        if self.attributes.is_some() {
+            bail!("callback interface attributes are not supported yet");
+        }
+        if self.inheritance.is_some() {
+            bail!("callback interface inheritence is not supported");
+        }
+        let mut object = CallbackInterface::new(self.identifier.0.to_string());
+        for member in &self.members.body {
+            match member {
+                weedle::interface::InterfaceMember::Operation(t) => {
+                    let mut method: Method = t.convert(ci)?;
+                    if method.throws().is_some() {
+                        bail!("callback interface methods cannot throw errors yet");
+                    }
+                    if method.is_static() {
+                        bail!("callback interface methods cannot be static");
+                    }
+                    if method.is_async() {
+                        bail!("callback interface methods cannot be [Async]");
+                    }
+                    if let Some(Type::Iterator(_)) = method.return_type() {
+                        bail!("callback interface methods cannot be [Iterator]");
+                    }
+                    method.object_name.push_str(object.name.as_str());
+                    object.methods.push(method);
+                }
+                _ => bail!(
+                    "no support for callback interface member type {:?} yet",
+                    member
+                ),
+            }
+        }
+        Ok(object)
+    }
+}
+
+/// Represents an Error that might be thrown by functions/methods in the component interface.
+///
+/// Errors are represented in the IDL as enums with the special `[Error]` attribute, either as
+/// a plain `enum` or as an `interface` whose variants carry fields, just like for `[Enum]`.
+/// When a rust function returns one of these errors, it is serialized into a `RustBuffer`
+/// as a u32 variant tag followed by the variant's data, and the foreign language bindings
+/// turn it into a corresponding typed exception.
+///
+/// For "flat" errors declared with a plain `enum`, the variant's data is the string
+/// obtained by formatting the rust error with `Display`. For errors whose variants have
+/// fields, the data is the values of those fields.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct Error {
     name: String,
-    values: Vec<String>,
+    #[serde(rename = "enum")]
+    enum_: Enum,
 }
 
 impl Error {
+    fn from_enum(enum_: Enum) -> Self {
+        Self {
+            name: enum_.name.clone(),
+            enum_,
+        }
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    pub fn values(&self) -> Vec<&str> {
-        self.values.iter().map(|v| v.as_str()).collect()
+    pub fn wrapped_enum(&self) -> &Enum {
+        &self.enum_
+    }
+
+    pub fn variants(&self) -> Vec<&Variant> {
+        self.enum_.variants()
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.enum_.is_flat()
     }
 }
 
 impl APIConverter<Error> for weedle::EnumDefinition<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Error> {
-        Ok(Error {
-            name: self.identifier.0.to_string(),
-            values: self
-                .values
-                .body
-                .list
-                .iter()
-                .map(|v| v.0.to_string())
-                .collect(),
-        })
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Error> {
+        Ok(Error::from_enum(APIConverter::<Enum>::convert(self, ci)?))
+    }
+}
+
+impl APIConverter<Error> for weedle::InterfaceDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Error> {
+        Ok(Error::from_enum(APIConverter::<Enum>::convert(self, ci)?))
     }
 }
 
@@ -993,10 +2270,11 @@ impl APIConverter<Error> for weedle::EnumDefinition<'_> {
 /// In the FFI these are represented as a byte buffer, which one side explicitly
 /// serializes the data into and the other serializes it out of. So I guess they're
 /// kind of like "pass by clone" values.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Record {
     name: String,
     fields: Vec<Field>,
+    docstring: Option<String>,
 }
 
 impl Record {
@@ -1006,6 +2284,18 @@ impl Record {
     pub fn fields(&self) -> Vec<&Field> {
         self.fields.iter().collect()
     }
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl Hash for Record {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring in the hash calculation,
+        // because it doesn't change how the FFI works.
+        self.name.hash(state);
+        self.fields.hash(state);
+    }
 }
 
 impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
@@ -1018,18 +2308,22 @@ impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
         }
         Ok(Record {
             name: self.identifier.0.to_string(),
+            docstring: ci.take_docstring(self.identifier.0),
             fields: self.members.body.convert(ci)?,
         })
     }
 }
 
 // Represents an individual field on a Record.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     name: String,
+    #[serde(rename = "type")]
     type_: Type,
     required: bool,
     default: Option<Literal>,
+    deprecated: Option<String>,
+    docstring: Option<String>,
 }
 
 impl Field {
@@ -1039,46 +2333,139 @@ impl Field {
     pub fn type_(&self) -> Type {
         self.type_.clone()
     }
+    pub fn default_value(&self) -> Option<Literal> {
+        self.default.clone()
+    }
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl Hash for Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring in the hash calculation,
+        // because it doesn't change how the FFI works.
+        self.name.hash(state);
+        self.type_.hash(state);
+        self.required.hash(state);
+        self.default.hash(state);
+        self.deprecated.hash(state);
+    }
 }
 
 impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Field> {
-        if self.attributes.is_some() {
-            bail!("dictionary member attributes are not supported yet");
+        let attributes = match &self.attributes {
+            Some(attr) => Attributes::try_from(attr)?,
+            None => Attributes(Vec::new()),
+        };
+        if !attributes.only_deprecated_attr() {
+            bail!("dictionary member attributes other than [Deprecated] are not supported yet");
         }
         let type_ = ci.resolve_type_expression(&self.type_)?;
-        if let Type::Object(_) = type_ {
-            bail!("Objects cannot currently appear in record fields");
+        if let Type::CallbackInterface(_) = type_ {
+            bail!("Callback interfaces cannot currently appear in record fields");
         }
         Ok(Field {
             name: self.identifier.0.to_string(),
-            type_,
+            docstring: ci.take_docstring(self.identifier.0),
+            deprecated: attributes.get_deprecated().map(String::from),
             required: self.required.is_some(),
             default: match self.default {
                 None => None,
-                Some(v) => Some(v.value.convert(ci)?),
+                Some(v) => Some(convert_default_value(&v.value, &type_)?),
             },
+            type_,
         })
     }
 }
 
 // Represents a literal value.
 // Used for e.g. default argument values.
-#[derive(Debug, Clone, Hash)]
+//
+// Literals are checked against the type they're a default for, so the bindings can
+// format them as whatever literal syntax matches that type in each language.
+#[derive(Debug, Clone, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Literal {
     Boolean(bool),
     String(String),
-    // TODO: more types of literal
+    Int(i64),
+    UInt(u64),
+    // Floats are kept as a string, normalized so that every language can parse it.
+    Float(String),
+    // The name of a variant of an enum without associated data.
+    Enum(String),
+    EmptySequence,
+    EmptyMap,
+    Null,
 }
 
-impl APIConverter<Literal> for weedle::literal::DefaultValue<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Literal> {
-        Ok(match self {
-            weedle::literal::DefaultValue::Boolean(b) => Literal::Boolean(b.0),
-            weedle::literal::DefaultValue::String(s) => Literal::String(s.0.to_string()),
-            _ => bail!("no support for {:?} literal yet", self),
-        })
+/// Convert a WebIDL default value into a [Literal], checking that it's valid for the given type.
+fn convert_default_value(v: &weedle::literal::DefaultValue<'_>, type_: &Type) -> Result<Literal> {
+    Ok(match (v, type_) {
+        (weedle::literal::DefaultValue::Null(_), Type::Optional(_)) => Literal::Null,
+        (_, Type::Optional(t)) => convert_default_value(v, t)?,
+        (weedle::literal::DefaultValue::Boolean(b), Type::Boolean) => Literal::Boolean(b.0),
+        (weedle::literal::DefaultValue::String(s), Type::String) => {
+            Literal::String(s.0.to_string())
+        }
+        // Enum variants are named by strings, as in WebIDL.
+        // We check that the variant exists once all the enums have been defined.
+        (weedle::literal::DefaultValue::String(s), Type::Enum(_)) => Literal::Enum(s.0.to_string()),
+        (weedle::literal::DefaultValue::Integer(i), _) => convert_integer_literal(i, type_)?,
+        (
+            weedle::literal::DefaultValue::Float(weedle::literal::FloatLit::Value(f)),
+            Type::Float32,
+        )
+        | (
+            weedle::literal::DefaultValue::Float(weedle::literal::FloatLit::Value(f)),
+            Type::Float64,
+        ) => Literal::Float(format!("{:?}", f.0.parse::<f64>()?)),
+        (weedle::literal::DefaultValue::EmptyArray(_), Type::Sequence(_)) => Literal::EmptySequence,
+        (weedle::literal::DefaultValue::EmptyDictionary(_), Type::Map(..)) => Literal::EmptyMap,
+        _ => bail!("Default value {:?} is not valid for type {:?}", v, type_),
+    })
+}
+
+fn convert_integer_literal(i: &weedle::literal::IntegerLit<'_>, type_: &Type) -> Result<Literal> {
+    let (digits, radix) = match i {
+        weedle::literal::IntegerLit::Dec(d) => (d.0.to_string(), 10),
+        weedle::literal::IntegerLit::Hex(h) => {
+            (h.0.replacen("0x", "", 1).replacen("0X", "", 1), 16)
+        }
+        // The leading zero makes this octal; a lone "0" still parses correctly.
+        weedle::literal::IntegerLit::Oct(o) => (o.0.to_string(), 8),
+    };
+    // Parse as something wider than any of our integer types, then check that the value fits.
+    let value = i128::from_str_radix(&digits, radix)?;
+    let fits = match type_ {
+        Type::Int8 => i8::try_from(value).is_ok(),
+        Type::Int16 => i16::try_from(value).is_ok(),
+        Type::Int32 => i32::try_from(value).is_ok(),
+        Type::Int64 => i64::try_from(value).is_ok(),
+        Type::UInt8 => u8::try_from(value).is_ok(),
+        Type::UInt16 => u16::try_from(value).is_ok(),
+        Type::UInt32 => u32::try_from(value).is_ok(),
+        Type::UInt64 => u64::try_from(value).is_ok(),
+        Type::Float32 | Type::Float64 => true,
+        _ => bail!("Integer default value is not valid for type {:?}", type_),
+    };
+    if !fits {
+        bail!(
+            "Default value {} is out of range for type {:?}",
+            value,
+            type_
+        );
     }
+    Ok(match type_ {
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => Literal::Int(value as i64),
+        Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => Literal::UInt(value as u64),
+        // Integers are fine as defaults for floats, but the bindings need to render them as floats.
+        _ => Literal::Float(format!("{:?}", value as f64)),
+    })
 }
 
 /// Represents an attribute parsed from WebIDL, like [ByRef] or [Throws].
@@ -1086,17 +2473,54 @@ impl APIConverter<Literal> for weedle::literal::DefaultValue<'_> {
 /// This is a convenience enum for parsing WebIDL attributes and erroring out if we encounter
 /// any unsupported ones. These don't convert directly into parts of a `ComponentInterface`, but
 /// may influence the properties of things like functions and arguments.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub enum Attribute {
     ByRef,
+    Enum,
     Throws(String),
     Error,
+    Custom,
+    Threadsafe,
+    Constants,
+    Async,
+    Iterator,
+    Name(String),
+    External(String),
+    Deprecated(String),
 }
 
 impl Attribute {
     fn is_error(&self) -> bool {
         matches!(self, Attribute::Error)
     }
+
+    fn is_enum(&self) -> bool {
+        matches!(self, Attribute::Enum)
+    }
+
+    fn is_custom(&self) -> bool {
+        matches!(self, Attribute::Custom)
+    }
+
+    fn is_threadsafe(&self) -> bool {
+        matches!(self, Attribute::Threadsafe)
+    }
+
+    fn is_constants(&self) -> bool {
+        matches!(self, Attribute::Constants)
+    }
+
+    fn is_async(&self) -> bool {
+        matches!(self, Attribute::Async)
+    }
+
+    fn is_iterator(&self) -> bool {
+        matches!(self, Attribute::Iterator)
+    }
+
+    fn is_deprecated(&self) -> bool {
+        matches!(self, Attribute::Deprecated(_))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
@@ -1107,24 +2531,31 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
         match weedle_attribute {
             weedle::attribute::ExtendedAttribute::NoArgs(attr) => match (attr.0).0 {
                 "ByRef" => Ok(Attribute::ByRef),
+                "Enum" => Ok(Attribute::Enum),
                 "Error" => Ok(Attribute::Error),
+                "Custom" => Ok(Attribute::Custom),
+                "Threadsafe" => Ok(Attribute::Threadsafe),
+                "Constants" => Ok(Attribute::Constants),
+                "Async" => Ok(Attribute::Async),
+                "Iterator" => Ok(Attribute::Iterator),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             weedle::attribute::ExtendedAttribute::Ident(identity) => {
-                if identity.lhs_identifier.0 == "Throws" {
-                    Ok(Attribute::Throws(match identity.rhs {
-                        weedle::attribute::IdentifierOrString::Identifier(identifier) => {
-                            identifier.0.to_string()
-                        }
-                        weedle::attribute::IdentifierOrString::String(str_lit) => {
-                            str_lit.0.to_string()
-                        }
-                    }))
-                } else {
-                    anyhow::bail!(
+                let rhs = match identity.rhs {
+                    weedle::attribute::IdentifierOrString::Identifier(identifier) => {
+                        identifier.0.to_string()
+                    }
+                    weedle::attribute::IdentifierOrString::String(str_lit) => str_lit.0.to_string(),
+                };
+                match identity.lhs_identifier.0 {
+                    "Throws" => Ok(Attribute::Throws(rhs)),
+                    "Name" => Ok(Attribute::Name(rhs)),
+                    "External" => Ok(Attribute::External(rhs)),
+                    "Deprecated" => Ok(Attribute::Deprecated(rhs)),
+                    _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
-                    )
+                    ),
                 }
             }
             _ => anyhow::bail!("Attribute not supported: {:?}", weedle_attribute),
@@ -1135,7 +2566,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
 /// Abstraction around a Vec<Attribute>.
 ///
 /// This is a convenience for parsing a weedle list of attributes.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct Attributes(Vec<Attribute>);
 
 impl Attributes {
@@ -1143,6 +2574,30 @@ impl Attributes {
         self.0.iter().any(|attr| attr.is_error())
     }
 
+    pub fn contains_enum_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_enum())
+    }
+
+    pub fn contains_custom_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_custom())
+    }
+
+    pub fn contains_threadsafe_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_threadsafe())
+    }
+
+    pub fn contains_constants_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_constants())
+    }
+
+    pub fn contains_async_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_async())
+    }
+
+    pub fn contains_iterator_attr(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_iterator())
+    }
+
     fn get_throws_err(&self) -> Option<&str> {
         self.0.iter().find_map(|attr| match attr {
             // This will hopefully return a helpful compilation error
@@ -1151,6 +2606,33 @@ impl Attributes {
             _ => None,
         })
     }
+
+    fn get_name(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Name(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn get_external_namespace(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::External(namespace) => Some(namespace.as_ref()),
+            _ => None,
+        })
+    }
+
+    fn get_deprecated(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Deprecated(message) => Some(message.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Whether `[Deprecated]` is the only kind of attribute in the list, for definitions
+    /// that don't support any others.
+    fn only_deprecated_attr(&self) -> bool {
+        self.0.iter().all(|attr| attr.is_deprecated())
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for Attributes {
@@ -1181,7 +2663,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for Attributes {
 /// from the high-level interface. Each callable thing in the component API will have a
 /// corresponding `FFIFunction` through which it can be invoked, and uniffi also provides
 /// some built-in `FFIFunction` helpers for use in the foreign language bindings.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct FFIFunction {
     name: String,
     arguments: Vec<FFIArgument>,
@@ -1198,14 +2680,31 @@ impl FFIFunction {
     pub fn return_type(&self) -> Option<&FFIType> {
         self.return_type.as_ref()
     }
+
+    /// Turn this into the FFI function for an `[Async]` function or method.
+    ///
+    /// Rather than returning a value, it takes a callback and some data to pass to it,
+    /// and Rust calls the callback with the serialized result once the future completes.
+    fn make_async(&mut self) {
+        self.arguments.push(FFIArgument {
+            name: "uniffi_callback".to_string(),
+            type_: FFIType::FutureCallback,
+        });
+        self.arguments.push(FFIArgument {
+            name: "uniffi_callback_data".to_string(),
+            type_: FFIType::UInt64,
+        });
+        self.return_type = None;
+    }
 }
 
 /// Represents an argument to an FFI function.
 ///
 /// Each argument has a name and a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FFIArgument {
     name: String,
+    #[serde(rename = "type")]
     type_: FFIType,
 }
 
@@ -1231,6 +2730,18 @@ impl From<&Argument> for FFIArgument {
 mod test {
     use super::*;
 
+    fn error_for(idl: &str) -> String {
+        ComponentInterface::from_webidl(idl)
+            .unwrap_err()
+            .to_string()
+    }
+
+    fn rust_source_error_for(source: &str) -> String {
+        ComponentInterface::from_rust_source(source)
+            .unwrap_err()
+            .to_string()
+    }
+
     const IDL1: &'static str = r#"
         namespace foobar{};
         enum Test {
@@ -1278,4 +2789,1341 @@ mod test {
             assert_ne!(ci1.checksum(), ci2.checksum());
         }
     }
+
+    #[test]
+    fn test_callback_interfaces_are_parsed() {
+        const IDL: &str = r#"
+            namespace test{};
+            callback interface Logger {
+                void log(string message);
+                u32 level();
+            };
+            interface Worker {
+                void run(Logger logger);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let callbacks = ci.iter_callback_interface_definitions();
+        assert_eq!(callbacks.len(), 1);
+        assert_eq!(callbacks[0].name(), "Logger");
+        assert_eq!(callbacks[0].methods().len(), 2);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == callbacks[0].ffi_init_callback().name()));
+        assert!(ci
+            .iter_types()
+            .contains(&Type::CallbackInterface("Logger".into())));
+
+        const IDL_RETURNING_CALLBACK: &str = r#"
+            namespace test{
+                Logger get_logger();
+            };
+            callback interface Logger {
+                void log(string message);
+            };
+        "#;
+        assert!(ComponentInterface::from_webidl(IDL_RETURNING_CALLBACK).is_err());
+    }
+
+    #[test]
+    fn test_enums_with_associated_data() {
+        const IDL: &str = r#"
+            namespace test{};
+            enum Flat {
+                "one",
+                "two",
+            };
+            [Enum]
+            interface Event {
+                Progress(u64 done, u64 total);
+                Message(string text);
+                Finished();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let enums = ci.iter_enum_definitions();
+        assert_eq!(enums.len(), 2);
+
+        let flat = enums.iter().find(|e| e.name() == "Flat").unwrap();
+        assert!(flat.is_flat());
+        assert_eq!(flat.variants().len(), 2);
+        assert!(!flat.variants()[0].has_fields());
+
+        let event = enums.iter().find(|e| e.name() == "Event").unwrap();
+        assert!(!event.is_flat());
+        let variants = event.variants();
+        assert_eq!(
+            variants.iter().map(|v| v.name()).collect::<Vec<_>>(),
+            vec!["Progress", "Message", "Finished"]
+        );
+        assert_eq!(
+            variants[0]
+                .fields()
+                .iter()
+                .map(|f| (f.name(), f.type_()))
+                .collect::<Vec<_>>(),
+            vec![("done", Type::UInt64), ("total", Type::UInt64)]
+        );
+        assert_eq!(variants[1].fields()[0].type_(), Type::String);
+        assert!(!variants[2].has_fields());
+        assert!(ci.iter_types().contains(&Type::Enum("Event".into())));
+
+        const IDL_NAMED_VARIANT: &str = r#"
+            namespace test{};
+            [Enum]
+            interface Event {
+                void Finished();
+            };
+        "#;
+        assert!(ComponentInterface::from_webidl(IDL_NAMED_VARIANT).is_err());
+    }
+
+    #[test]
+    fn test_errors_with_fields() {
+        const IDL: &str = r#"
+            namespace test{
+                [Throws=NetworkError]
+                void fetch(string url);
+            };
+            [Error]
+            enum Flat {
+                "one",
+            };
+            [Error]
+            interface NetworkError {
+                Offline();
+                Http(u16 status, u64? retry_after);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        assert_eq!(ci.iter_enum_definitions().len(), 0);
+        let errors = ci.iter_error_definitions();
+        assert_eq!(errors.len(), 2);
+
+        let flat = errors.iter().find(|e| e.name() == "Flat").unwrap();
+        assert!(flat.is_flat());
+
+        let err = errors.iter().find(|e| e.name() == "NetworkError").unwrap();
+        assert!(!err.is_flat());
+        let variants = err.variants();
+        assert!(!variants[0].has_fields());
+        assert_eq!(
+            variants[1]
+                .fields()
+                .iter()
+                .map(|f| (f.name(), f.type_()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("status", Type::UInt16),
+                ("retry_after", Type::Optional(Box::new(Type::UInt64)))
+            ]
+        );
+        assert!(ci
+            .iter_types()
+            .contains(&Type::Error("NetworkError".into())));
+        assert_eq!(
+            ci.iter_function_definitions()[0].throws(),
+            Some("NetworkError")
+        );
+    }
+
+    #[test]
+    fn test_union_types() {
+        const IDL: &str = r#"
+            namespace test{
+                (string or Point) describe((u32 or sequence<Point>)? thing);
+            };
+            dictionary Point {
+                u32 x;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let func = &ci.iter_function_definitions()[0];
+        assert_eq!(
+            func.return_type(),
+            Some(&Type::Union(
+                "StringOrPoint".into(),
+                vec![Type::String, Type::Record("Point".into())]
+            ))
+        );
+        let thing = Type::Union(
+            "U32OrSequencePoint".into(),
+            vec![
+                Type::UInt32,
+                Type::Sequence(Box::new(Type::Record("Point".into()))),
+            ],
+        );
+        assert_eq!(
+            func.arguments()[0].type_(),
+            Type::Optional(Box::new(thing.clone()))
+        );
+        assert!(ci.iter_types().contains(&thing));
+
+        const IDL_DUPLICATE: &str = r#"
+            namespace test{
+                void f((string or string) s);
+            };
+        "#;
+        assert!(ComponentInterface::from_webidl(IDL_DUPLICATE).is_err());
+
+        const IDL_OBJECT: &str = r#"
+            namespace test{
+                void f((string or Thing) s);
+            };
+            interface Thing {};
+        "#;
+        assert!(ComponentInterface::from_webidl(IDL_OBJECT).is_err());
+    }
+
+    #[test]
+    fn test_custom_types() {
+        const IDL: &str = r#"
+            namespace test{
+                Guid new_guid(Millis created);
+            };
+            [Custom]
+            typedef string Guid;
+            [Custom]
+            typedef i64 Millis;
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let guid = Type::Custom("Guid".into(), Box::new(Type::String));
+        let millis = Type::Custom("Millis".into(), Box::new(Type::Int64));
+        let func = &ci.iter_function_definitions()[0];
+        assert_eq!(func.return_type(), Some(&guid));
+        assert_eq!(func.arguments()[0].type_(), millis);
+        assert!(matches!(FFIType::from(&guid), FFIType::RustBuffer));
+        assert!(matches!(FFIType::from(&millis), FFIType::Int64));
+        // The wrapped builtin types must be available to the bindings.
+        assert!(ci.iter_types().contains(&Type::String));
+        assert!(ci.iter_types().contains(&Type::Int64));
+
+        const IDL_NOT_BUILTIN: &str = r#"
+            namespace test{};
+            dictionary Point {
+                u32 x;
+            };
+            [Custom]
+            typedef Point Location;
+        "#;
+        assert!(ComponentInterface::from_webidl(IDL_NOT_BUILTIN).is_err());
+    }
+
+    #[test]
+    fn test_external_types() {
+        const IDL: &str = r#"
+            namespace test{
+                AccountId? lookup(sequence<AccountId> ids, u32 index);
+            };
+            [External=accounts]
+            typedef extern AccountId;
+            [External=accounts]
+            typedef extern AccountKind;
+            dictionary Account {
+                AccountId id;
+                AccountKind kind;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let account_id = Type::External("AccountId".into(), "accounts".into());
+        let func = &ci.iter_function_definitions()[0];
+        assert_eq!(
+            func.return_type(),
+            Some(&Type::Optional(Box::new(account_id.clone())))
+        );
+        assert_eq!(
+            func.arguments()[0].type_(),
+            Type::Sequence(Box::new(account_id.clone()))
+        );
+        assert!(matches!(FFIType::from(&account_id), FFIType::RustBuffer));
+        let record = &ci.iter_record_definitions()[0];
+        assert_eq!(
+            record.fields()[1].type_(),
+            Type::External("AccountKind".into(), "accounts".into())
+        );
+        assert_eq!(ci.iter_external_namespaces(), vec!["accounts".to_string()]);
+
+        const IDL_NOT_EXTERN: &str = r#"
+            namespace test{};
+            [External=accounts]
+            typedef u64 AccountId;
+        "#;
+        let err = ComponentInterface::from_webidl(IDL_NOT_EXTERN).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "external type AccountId must be declared as `typedef extern AccountId`"
+        );
+    }
+
+    #[test]
+    fn test_docstrings() {
+        const IDL: &str = r#"
+            /// The test component.
+            namespace test{
+                /// Adds things up.
+                ///
+                /// It's very good at it.
+                u32 add(u32 a, u32 b);
+                // Just a comment, not documentation.
+                u32 subtract(u32 a, u32 b);
+            };
+
+            /// A point.
+            dictionary Point {
+                /// Across.
+                u32 x;
+
+                u32 y;
+            };
+
+            enum Shape {
+                /// Round.
+                "Circle",
+                "Square",
+            };
+
+            /// Something that happened.
+            [Enum]
+            interface Event {
+                /// It started.
+                Started(u32 at);
+            };
+
+            /// Counts things.
+            interface Counter {
+                constructor();
+                /// Counts another thing.
+                [Throws=CountError]
+                void count();
+            };
+
+            [Error]
+            enum CountError { "TooMany" };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        assert_eq!(ci.namespace_docstring(), Some("The test component."));
+        let funcs = ci.iter_function_definitions();
+        assert_eq!(
+            funcs[0].docstring(),
+            Some("Adds things up.\n\nIt's very good at it.")
+        );
+        assert_eq!(funcs[1].docstring(), None);
+
+        let record = &ci.iter_record_definitions()[0];
+        assert_eq!(record.docstring(), Some("A point."));
+        assert_eq!(record.fields()[0].docstring(), Some("Across."));
+        assert_eq!(record.fields()[1].docstring(), None);
+
+        let enums = ci.iter_enum_definitions();
+        let shape = enums.iter().find(|e| e.name() == "Shape").unwrap();
+        assert_eq!(shape.docstring(), None);
+        assert_eq!(shape.variants()[0].docstring(), Some("Round."));
+        assert_eq!(shape.variants()[1].docstring(), None);
+        let event = enums.iter().find(|e| e.name() == "Event").unwrap();
+        assert_eq!(event.docstring(), Some("Something that happened."));
+        assert_eq!(event.variants()[0].docstring(), Some("It started."));
+
+        let obj = &ci.iter_object_definitions()[0];
+        assert_eq!(obj.docstring(), Some("Counts things."));
+        assert_eq!(obj.methods()[0].docstring(), Some("Counts another thing."));
+
+        // Doc comments are documentation for humans, and don't change the FFI.
+        let undocumented = IDL
+            .lines()
+            .filter(|line| !line.trim().starts_with("///"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let undocumented_ci = ComponentInterface::from_webidl(&undocumented).unwrap();
+        assert_eq!(undocumented_ci.checksum(), ci.checksum());
+    }
+
+    #[test]
+    fn test_threadsafe_objects() {
+        const IDL: &str = r#"
+            namespace test{};
+            [Threadsafe]
+            interface Shared {
+                constructor();
+            };
+            interface Exclusive {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let objects = ci.iter_object_definitions();
+        let shared = objects.iter().find(|o| o.name() == "Shared").unwrap();
+        assert!(shared.is_threadsafe());
+        let exclusive = objects.iter().find(|o| o.name() == "Exclusive").unwrap();
+        assert!(!exclusive.is_threadsafe());
+
+        const IDL_BAD_ATTR: &str = r#"
+            namespace test{};
+            [Threadsafe, ByRef]
+            interface Shared {
+                constructor();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(IDL_BAD_ATTR).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "interface attributes other than [Threadsafe] are not supported yet"
+        );
+    }
+
+    #[test]
+    fn test_deprecated() {
+        const IDL: &str = r#"
+            namespace test{
+                [Deprecated="Use add_checked instead"]
+                u32 add(u32 a, u32 b);
+                u32 add_checked(u32 a, u32 b);
+            };
+
+            dictionary Point {
+                [Deprecated="Use coords instead"]
+                u32 x;
+                sequence<u32> coords;
+            };
+
+            [Enum]
+            interface Event {
+                [Deprecated="Nothing starts any more"]
+                Started(u32 at);
+                Stopped();
+            };
+
+            interface Counter {
+                [Deprecated="Counters should be shared"]
+                constructor();
+                [Name=shared]
+                constructor();
+                [Deprecated="Count more carefully", Throws=CountError]
+                void count();
+            };
+
+            [Error]
+            enum CountError { "TooMany" };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let funcs = ci.iter_function_definitions();
+        assert_eq!(funcs[0].deprecated(), Some("Use add_checked instead"));
+        assert_eq!(funcs[1].deprecated(), None);
+
+        let record = &ci.iter_record_definitions()[0];
+        assert_eq!(record.fields()[0].deprecated(), Some("Use coords instead"));
+        assert_eq!(record.fields()[1].deprecated(), None);
+
+        let event = &ci.iter_enum_definitions()[0];
+        assert_eq!(
+            event.variants()[0].deprecated(),
+            Some("Nothing starts any more")
+        );
+        assert_eq!(event.variants()[1].deprecated(), None);
+
+        let obj = &ci.iter_object_definitions()[0];
+        let constructors = obj.constructors();
+        assert_eq!(
+            constructors[0].deprecated(),
+            Some("Counters should be shared")
+        );
+        assert_eq!(constructors[1].deprecated(), None);
+        assert_eq!(obj.methods()[0].deprecated(), Some("Count more carefully"));
+        assert_eq!(obj.methods()[0].throws(), Some("CountError"));
+
+        const IDL_BAD_FIELD_ATTR: &str = r#"
+            namespace test{};
+            dictionary Point {
+                [ByRef]
+                u32 x;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(IDL_BAD_FIELD_ATTR).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dictionary member attributes other than [Deprecated] are not supported yet"
+        );
+    }
+
+    #[test]
+    fn test_bytes_type() {
+        const IDL: &str = r#"
+            namespace test{
+                bytes reverse(bytes data);
+                sequence<u8> as_sequence(bytes data);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let funcs = ci.iter_function_definitions();
+        let reverse = funcs.iter().find(|f| f.name() == "reverse").unwrap();
+        assert_eq!(reverse.return_type(), Some(&Type::Bytes));
+        assert_eq!(reverse.arguments()[0].type_(), Type::Bytes);
+        // Bytes are distinct from a sequence of u8, even though they're the same in rust.
+        let as_sequence = funcs.iter().find(|f| f.name() == "as_sequence").unwrap();
+        assert_eq!(
+            as_sequence.return_type(),
+            Some(&Type::Sequence(Box::new(Type::UInt8)))
+        );
+        assert_ne!(Type::Bytes.canonical_name(), "Sequenceu8");
+        assert!(matches!(FFIType::from(&Type::Bytes), FFIType::RustBuffer));
+    }
+
+    #[test]
+    fn test_timestamp_and_duration_types() {
+        const IDL: &str = r#"
+            namespace test{
+                timestamp add(timestamp a, duration b);
+                duration? elapsed(sequence<timestamp> times);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let funcs = ci.iter_function_definitions();
+        let add = funcs.iter().find(|f| f.name() == "add").unwrap();
+        assert_eq!(add.return_type(), Some(&Type::Timestamp));
+        assert_eq!(add.arguments()[1].type_(), Type::Duration);
+        let elapsed = funcs.iter().find(|f| f.name() == "elapsed").unwrap();
+        assert_eq!(
+            elapsed.return_type(),
+            Some(&Type::Optional(Box::new(Type::Duration)))
+        );
+        assert_eq!(
+            elapsed.arguments()[0].type_(),
+            Type::Sequence(Box::new(Type::Timestamp))
+        );
+        // Both are serialized into a buffer, since they have two parts.
+        assert!(matches!(
+            FFIType::from(&Type::Timestamp),
+            FFIType::RustBuffer
+        ));
+        assert!(matches!(
+            FFIType::from(&Type::Duration),
+            FFIType::RustBuffer
+        ));
+    }
+
+    #[test]
+    fn test_objects_can_be_passed_around() {
+        const IDL: &str = r#"
+            namespace test{
+                Crate pack(u32 weight);
+                u32 weigh([ByRef] Crate item);
+            };
+            [Threadsafe]
+            interface Crate {
+                constructor(u32 weight);
+            };
+            dictionary Shipment {
+                Crate item;
+            };
+            interface Truck {
+                void load(Crate item);
+                sequence<Crate> unload();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let crate_type = Type::Object("Crate".into());
+        let funcs = ci.iter_function_definitions();
+        assert_eq!(funcs[0].return_type(), Some(&crate_type));
+        assert!(funcs[1].arguments()[0].by_ref());
+        let records = ci.iter_record_definitions();
+        assert_eq!(records[0].fields()[0].type_(), crate_type);
+        let objects = ci.iter_object_definitions();
+        let truck = objects.iter().find(|o| o.name() == "Truck").unwrap();
+        assert_eq!(truck.methods()[0].arguments()[0].type_(), crate_type);
+        assert_eq!(
+            truck.methods()[1].return_type(),
+            Some(&Type::Sequence(Box::new(crate_type)))
+        );
+        assert!(matches!(
+            FFIType::from(&Type::Object("Crate".into())),
+            FFIType::UInt64
+        ));
+
+        // Rust and the foreign-language code share the objects passed between them.
+        const EXCLUSIVE_IDL: &str = r#"
+            namespace test{};
+            interface Crate {
+                constructor(u32 weight);
+            };
+            interface Truck {
+                sequence<Crate> unload();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(EXCLUSIVE_IDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Interface Crate can't be passed across the FFI, since it isn't [Threadsafe]"
+        );
+    }
+
+    #[test]
+    fn test_map_key_types() {
+        const IDL: &str = r#"
+            namespace test{
+                record<DOMString, u32> by_name();
+                record<u64, string> by_id();
+                record<Weekday, u32> by_day();
+            };
+            enum Weekday { "Monday", "Tuesday" };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let funcs = ci.iter_function_definitions();
+        let map_type = |name: &str| {
+            funcs
+                .iter()
+                .find(|f| f.name() == name)
+                .unwrap()
+                .return_type()
+                .cloned()
+        };
+        assert_eq!(
+            map_type("by_name"),
+            Some(Type::Map(Box::new(Type::String), Box::new(Type::UInt32)))
+        );
+        assert_eq!(
+            map_type("by_id"),
+            Some(Type::Map(Box::new(Type::UInt64), Box::new(Type::String)))
+        );
+        let by_day = map_type("by_day").unwrap();
+        assert_eq!(
+            by_day,
+            Type::Map(
+                Box::new(Type::Enum("Weekday".into())),
+                Box::new(Type::UInt32)
+            )
+        );
+        // The canonical name includes the key type, so maps with different keys get different helpers.
+        assert_eq!(by_day.canonical_name(), "MapEnumWeekdayu32");
+
+        const FLOAT_KEY_IDL: &str = r#"
+            namespace test{
+                record<f64, string> by_value();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(FLOAT_KEY_IDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Map keys must be strings, integers or enums, not Float64"
+        );
+
+        const ENUM_WITH_DATA_KEY_IDL: &str = r#"
+            namespace test{
+                record<Shape, string> names();
+            };
+            [Enum]
+            interface Shape {
+                Circle(f64 radius);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(ENUM_WITH_DATA_KEY_IDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Enum Shape has associated data, so it can't be a map key"
+        );
+    }
+
+    #[test]
+    fn test_default_values() {
+        const IDL: &str = r#"
+            namespace test{
+                void f(u8 a, optional u16 b = 0xff, optional i8 c = -010, optional f32 d = 1,
+                       optional Weekday e = "Tuesday", optional string? g = null,
+                       optional sequence<u8> h = [], optional record<DOMString, u8> i = {});
+            };
+            enum Weekday { "Monday", "Tuesday" };
+            dictionary Defaults {
+                u64 big = 18446744073709551615;
+                i64? maybe = -1;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let func = &ci.iter_function_definitions()[0];
+        let defaults: Vec<_> = func.arguments().iter().map(|a| a.default_value()).collect();
+        assert_eq!(
+            defaults,
+            vec![
+                None,
+                Some(Literal::UInt(255)),
+                Some(Literal::Int(-8)),
+                Some(Literal::Float("1.0".into())),
+                Some(Literal::Enum("Tuesday".into())),
+                Some(Literal::Null),
+                Some(Literal::EmptySequence),
+                Some(Literal::EmptyMap),
+            ]
+        );
+        let record = &ci.iter_record_definitions()[0];
+        let fields = record.fields();
+        assert_eq!(fields[0].default_value(), Some(Literal::UInt(u64::MAX)));
+        assert_eq!(fields[1].default_value(), Some(Literal::Int(-1)));
+
+        assert_eq!(
+            error_for("namespace test{ void f(optional u8 a = 256); };"),
+            "Default value 256 is out of range for type UInt8"
+        );
+        assert_eq!(
+            error_for("namespace test{ void f(optional u32 a = -1); };"),
+            "Default value -1 is out of range for type UInt32"
+        );
+        assert_eq!(
+            error_for(r#"namespace test{ void f(optional u32 a = "1"); };"#),
+            r#"Default value String(StringLit("1")) is not valid for type UInt32"#
+        );
+        assert_eq!(
+            error_for("namespace test{ void f(optional u32 a = null); };"),
+            "Default value Null(Null) is not valid for type UInt32"
+        );
+        assert_eq!(
+            error_for("namespace test{ void f(optional u32 a = 1, u32 b); };"),
+            "f: values with defaults must come after those without"
+        );
+        assert_eq!(
+            error_for(
+                r#"
+                namespace test{ void f(optional Weekday a = "Friday"); };
+                enum Weekday { "Monday", "Tuesday" };
+                "#
+            ),
+            "f: Friday is not a variant of enum Weekday"
+        );
+        assert_eq!(
+            error_for(
+                r#"
+                namespace test{};
+                callback interface Listener { void heard(optional u8 volume = 1); };
+                "#
+            ),
+            "Listener.heard: callback interface methods can't have default values"
+        );
+    }
+
+    #[test]
+    fn test_named_constructors() {
+        const IDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                constructor();
+                [Name=from_file]
+                constructor(string path);
+                [Name=from_bytes, Throws=Error]
+                constructor(bytes data);
+            };
+            [Error]
+            enum Error { "Invalid" };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let obj = &ci.iter_object_definitions()[0];
+        assert_eq!(obj.primary_constructor().unwrap().name(), "new");
+        let alternates: Vec<_> = obj
+            .alternate_constructors()
+            .iter()
+            .map(|cons| cons.name())
+            .collect();
+        assert_eq!(alternates, vec!["from_file", "from_bytes"]);
+        assert_eq!(obj.alternate_constructors()[1].throws(), Some("Error"));
+        // Each constructor gets its own FFI function.
+        let ffi_names: std::collections::HashSet<_> = obj
+            .constructors()
+            .iter()
+            .map(|cons| cons.ffi_func().name())
+            .collect();
+        assert_eq!(ffi_names.len(), 3);
+
+        const ONLY_NAMED_IDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                [Name=from_file]
+                constructor(string path);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(ONLY_NAMED_IDL).unwrap();
+        let obj = &ci.iter_object_definitions()[0];
+        assert!(obj.primary_constructor().is_none());
+        assert_eq!(obj.constructors().len(), 1);
+
+        assert_eq!(
+            error_for(
+                r#"
+                namespace test{};
+                interface Testing { constructor(); constructor(u32 value); };
+                "#
+            ),
+            "Interface Testing has more than one constructor without a [Name=...]"
+        );
+        assert_eq!(
+            error_for(
+                r#"
+                namespace test{};
+                interface Testing { [Name=make] constructor(); [Name=make] constructor(u32 value); };
+                "#
+            ),
+            "Interface Testing has more than one constructor named make"
+        );
+        assert_eq!(
+            error_for(
+                r#"
+                namespace test{};
+                interface Testing { [Name=reset] constructor(); void reset(); };
+                "#
+            ),
+            "Interface Testing has a constructor and a method both named reset"
+        );
+    }
+
+    #[test]
+    fn test_static_methods() {
+        const IDL: &str = r#"
+            namespace test{};
+            [Threadsafe]
+            interface Testing {
+                static Testing make(u32 value);
+                u32 value();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let obj = &ci.iter_object_definitions()[0];
+        let make = obj.methods()[0];
+        assert!(make.is_static());
+        // Static methods don't take a handle, so the FFI function only has the declared arguments.
+        let ffi_args: Vec<_> = make
+            .ffi_func()
+            .arguments()
+            .iter()
+            .map(|arg| arg.name().to_string())
+            .collect();
+        assert_eq!(ffi_args, vec!["value"]);
+        let value = obj.methods()[1];
+        assert!(!value.is_static());
+        assert_eq!(value.ffi_func().arguments()[0].name(), "handle");
+
+        const CALLBACK_IDL: &str = r#"
+            namespace test{};
+            callback interface Testing {
+                static void notify();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(CALLBACK_IDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "callback interface methods cannot be static"
+        );
+    }
+
+    #[test]
+    fn test_interface_attributes() {
+        const IDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                attribute string name;
+                readonly attribute u32 size;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let obj = &ci.iter_object_definitions()[0];
+        let props = obj.properties();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].name(), "name");
+        assert_eq!(props[0].type_(), Type::String);
+        assert!(!props[0].is_readonly());
+        assert_eq!(props[0].getter().name(), "get_name");
+        assert_eq!(props[0].getter().return_type(), Some(&Type::String));
+        let setter = props[0].setter().unwrap();
+        assert_eq!(setter.name(), "set_name");
+        assert_eq!(setter.arguments()[0].type_(), Type::String);
+        assert!(setter.return_type().is_none());
+        assert!(props[1].is_readonly());
+        assert_eq!(props[1].accessors().len(), 1);
+        // Each accessor gets an FFI function, which takes the object handle.
+        let ffi_funcs = ci.iter_ffi_function_definitions();
+        let getter = ffi_funcs
+            .iter()
+            .find(|f| f.name() == props[1].getter().ffi_func().name())
+            .unwrap();
+        assert_eq!(getter.arguments()[0].name(), "handle");
+        assert!(ffi_funcs
+            .iter()
+            .any(|f| f.name() == setter.ffi_func().name()));
+
+        const CLASHING_IDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                attribute u32 size;
+                u32 get_size();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(CLASHING_IDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Interface Testing has an attribute size that clashes with another member"
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        const IDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                const u32 VERSION = 3;
+                const i8 OFFSET = -0x10;
+                const double RATIO = 0.5;
+                const boolean ENABLED = false;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let obj = &ci.iter_object_definitions()[0];
+        let constants: Vec<_> = obj
+            .constants()
+            .iter()
+            .map(|c| (c.name().to_string(), c.type_(), c.value()))
+            .collect();
+        assert_eq!(
+            constants,
+            vec![
+                ("VERSION".to_string(), Type::UInt32, Literal::UInt(3)),
+                ("OFFSET".to_string(), Type::Int8, Literal::Int(-16)),
+                (
+                    "RATIO".to_string(),
+                    Type::Float64,
+                    Literal::Float("0.5".into())
+                ),
+                (
+                    "ENABLED".to_string(),
+                    Type::Boolean,
+                    Literal::Boolean(false)
+                ),
+            ]
+        );
+        // Constants don't need any FFI functions.
+        assert_eq!(
+            ci.iter_ffi_function_definitions().len(),
+            ComponentInterface::from_webidl("namespace test{}; interface Testing {};")
+                .unwrap()
+                .iter_ffi_function_definitions()
+                .len()
+        );
+
+        assert_eq!(
+            error_for("namespace test{}; interface Testing { const u8 BIG = 256; };"),
+            "Default value 256 is out of range for type UInt8"
+        );
+        assert_eq!(
+            error_for("namespace test{}; interface Testing { const u8? MAYBE = null; };"),
+            "Constants must be booleans, integers or floats, not Optional(UInt8)"
+        );
+        assert_eq!(
+            error_for("namespace test{}; interface Testing { const u8 A = 1; const u8 A = 2; };"),
+            "Interface Testing has more than one constant named A"
+        );
+    }
+
+    #[test]
+    fn test_namespace_constants() {
+        const IDL: &str = r#"
+            namespace test{
+                u32 get_version();
+            };
+            [Constants]
+            interface test {
+                const u32 VERSION = 3;
+                const boolean ENABLED = true;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let constants: Vec<_> = ci
+            .iter_constant_definitions()
+            .iter()
+            .map(|c| (c.name().to_string(), c.type_(), c.value()))
+            .collect();
+        assert_eq!(
+            constants,
+            vec![
+                ("VERSION".to_string(), Type::UInt32, Literal::UInt(3)),
+                ("ENABLED".to_string(), Type::Boolean, Literal::Boolean(true)),
+            ]
+        );
+        // The `[Constants]` interface isn't an object, and doesn't need any FFI functions.
+        assert!(ci.iter_object_definitions().is_empty());
+        assert_eq!(
+            ci.iter_ffi_function_definitions().len(),
+            ComponentInterface::from_webidl("namespace test{ u32 get_version(); };")
+                .unwrap()
+                .iter_ffi_function_definitions()
+                .len()
+        );
+        // The constants are part of the checksum.
+        assert_ne!(
+            ci.checksum(),
+            ComponentInterface::from_webidl("namespace test{ u32 get_version(); };")
+                .unwrap()
+                .checksum()
+        );
+        let roundtripped = ComponentInterface::from_json(&ci.to_json().unwrap()).unwrap();
+        assert_eq!(roundtripped.iter_constant_definitions().len(), 2);
+
+        assert_eq!(
+            error_for("namespace test{}; [Constants] interface other { const u8 A = 1; };"),
+            "[Constants] interface other must be named after the namespace test"
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; [Constants] interface test {}; [Constants] interface test {};"
+            ),
+            "duplicate [Constants] interface: test and test"
+        );
+        assert!(
+            error_for("namespace test{}; [Constants] interface test { u32 get(); };")
+                .ends_with("not supported in [Constants] interface")
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; [Constants] interface test { const u8 A = 1; const u8 A = 2; };"
+            ),
+            "duplicate constant definition: A"
+        );
+    }
+
+    #[test]
+    fn test_async_functions() {
+        const IDL: &str = r#"
+            namespace test{
+                [Async]
+                string fetch(u32 id);
+                u32 now();
+            };
+            [Threadsafe]
+            interface Client {
+                constructor();
+                [Async]
+                void send(string message);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        assert!(ci.has_async_functions());
+        let functions = ci.iter_function_definitions();
+        let fetch = functions.iter().find(|f| f.name() == "fetch").unwrap();
+        assert!(fetch.is_async());
+        let now = functions.iter().find(|f| f.name() == "now").unwrap();
+        assert!(!now.is_async());
+        // The FFI function reports its result through a callback rather than returning it.
+        let ffi_func = fetch.ffi_func();
+        let ffi_args = ffi_func.arguments();
+        assert_eq!(ffi_args.len(), 3);
+        assert_eq!(ffi_args[1].name(), "uniffi_callback");
+        assert!(matches!(ffi_args[1].type_(), FFIType::FutureCallback));
+        assert_eq!(ffi_args[2].name(), "uniffi_callback_data");
+        assert!(matches!(ffi_args[2].type_(), FFIType::UInt64));
+        assert!(ffi_func.return_type().is_none());
+        let send = ci.iter_object_definitions()[0].methods()[0].clone();
+        assert!(send.is_async());
+        assert_eq!(send.ffi_func().arguments().len(), 4);
+
+        assert_eq!(
+            error_for("namespace test{}; interface Client { [Async] void send(); };"),
+            "Async method send is only supported on [Threadsafe] interfaces"
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; [Threadsafe] interface Client { [Async] constructor(); };"
+            ),
+            "constructors cannot be [Async]"
+        );
+        assert_eq!(
+            error_for("namespace test{}; callback interface Listener { [Async] void heard(); };"),
+            "callback interface methods cannot be [Async]"
+        );
+    }
+
+    #[test]
+    fn test_iterator_functions() {
+        const IDL: &str = r#"
+            namespace test{
+                [Iterator]
+                sequence<string> lines(string path);
+                [Iterator]
+                sequence<string> words(string text);
+            };
+            interface Reader {
+                constructor();
+                [Iterator]
+                sequence<u32> offsets();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let functions = ci.iter_function_definitions();
+        let lines = functions.iter().find(|f| f.name() == "lines").unwrap();
+        assert_eq!(
+            lines.return_type(),
+            Some(&Type::Iterator(Box::new(Type::String)))
+        );
+        // The function returns a handle to the iterator.
+        assert!(matches!(
+            lines.ffi_func().return_type(),
+            Some(FFIType::UInt64)
+        ));
+        // Iterators with the same item type share their FFI functions.
+        assert_eq!(
+            ci.iter_iterator_item_types(),
+            vec![Type::String, Type::UInt32]
+        );
+        let next = ci.ffi_iterator_next(&Type::String);
+        assert!(next.name().ends_with("_iterator_string_next"));
+        assert_eq!(next.arguments().len(), 2);
+        assert!(matches!(next.return_type(), Some(FFIType::RustBuffer)));
+        let ffi_funcs = ci.iter_ffi_function_definitions();
+        assert!(ffi_funcs
+            .iter()
+            .any(|f| f.name().ends_with("_iterator_u32_next")));
+        assert!(ffi_funcs
+            .iter()
+            .any(|f| f.name().ends_with("_iterator_u32_free")));
+
+        assert_eq!(
+            error_for("namespace test{ [Iterator] string name(); };"),
+            "[Iterator] name must return a sequence of the items"
+        );
+        assert_eq!(
+            error_for("namespace test{ [Async, Iterator] sequence<u8> bytes(); };"),
+            "bytes cannot be both [Async] and [Iterator]"
+        );
+        assert_eq!(
+            error_for("namespace test{}; interface Reader { [Iterator] constructor(); };"),
+            "constructors cannot be [Iterator]"
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; callback interface Listener { [Iterator] sequence<u8> heard(); };"
+            ),
+            "callback interface methods cannot be [Iterator]"
+        );
+    }
+
+    #[test]
+    fn test_interface_inheritance() {
+        const IDL: &str = r#"
+            namespace test{};
+            interface Animal {
+                string name();
+            };
+            interface Pet : Animal {
+                void adopt(string owner);
+            };
+            interface Dog : Pet {
+                constructor();
+                u32 fetch();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        // Objects are listed after the interfaces they inherit from.
+        let names: Vec<_> = ci
+            .iter_object_definitions()
+            .iter()
+            .map(|o| o.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Animal", "Pet", "Dog"]);
+        let objects = ci.iter_object_definitions();
+        let animal = &objects[0];
+        assert!(animal.is_base());
+        assert_eq!(animal.parent(), None);
+        assert!(animal.constructors().is_empty());
+        let dog = &objects[2];
+        assert!(!dog.is_base());
+        assert_eq!(dog.parent(), Some("Pet"));
+        // The inherited methods are called through the inheriting object's handles.
+        let inherited: Vec<_> = dog
+            .methods()
+            .iter()
+            .map(|m| (m.name().to_string(), m.inherited_from().map(String::from)))
+            .collect();
+        assert_eq!(
+            inherited,
+            vec![
+                ("fetch".to_string(), None),
+                ("adopt".to_string(), Some("Pet".to_string())),
+                ("name".to_string(), Some("Animal".to_string())),
+            ]
+        );
+        let ffi_funcs = ci.iter_ffi_function_definitions();
+        assert!(ffi_funcs.iter().any(|f| f.name().ends_with("_Dog_name")));
+        assert!(!ffi_funcs.iter().any(|f| f.name().contains("_Animal_")));
+
+        assert_eq!(
+            error_for("namespace test{}; interface Dog : Animal { constructor(); };"),
+            "Interface Dog inherits from Animal, which isn't an interface"
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; interface A : B { void a(); }; interface B : A { void b(); };"
+            ),
+            "Interface A inherits from itself"
+        );
+        assert_eq!(
+            error_for(
+                "namespace test{}; interface Animal { constructor(); }; interface Dog : Animal {};"
+            ),
+            "Interface Animal can only declare methods, since other interfaces inherit from it"
+        );
+        assert_eq!(
+            error_for("namespace test{}; interface Animal { string name(); }; interface Dog : Animal { constructor(); string name(); };"),
+            "Interface Dog has a member named name that clashes with a method it inherits from Animal"
+        );
+        assert_eq!(
+            error_for("namespace test{}; interface Animal { void eat(); }; [Threadsafe] interface Dog : Animal {};"),
+            "[Threadsafe] interface Dog can't inherit from Animal, which isn't [Threadsafe]"
+        );
+        assert_eq!(
+            error_for("namespace test{ Animal adopt(); }; interface Animal { void eat(); }; interface Dog : Animal {};"),
+            "Interface Animal can't be passed across the FFI, since other interfaces inherit from it"
+        );
+        assert_eq!(
+            error_for("namespace test{ void feed(sequence<Animal> animals); }; interface Animal { void eat(); }; interface Dog : Animal {};"),
+            "Interface Animal can't be passed across the FFI, since other interfaces inherit from it"
+        );
+    }
+
+    #[test]
+    fn test_rust_source() {
+        const SOURCE: &str = r#"
+            //! A component for testing.
+
+            #[uniffi_macros::export]
+            enum Shape { Circle, Square }
+
+            #[uniffi_macros::export]
+            enum Event { Progress { done: u64, total: u64 }, Finished }
+
+            #[uniffi_macros::export(error)]
+            enum CounterError { Overflow(u64), Empty }
+
+            /// A point.
+            #[derive(Debug)]
+            #[uniffi_macros::export]
+            struct Point { x: f64, y: Option<f64>, tags: HashMap<String, u32>, data: Vec<u8> }
+
+            type Result<T, E = CounterError> = std::result::Result<T, E>;
+
+            #[uniffi_macros::export]
+            fn distance(a: &Point, b: Point) -> f64 { unimplemented!() }
+
+            #[uniffi_macros::export]
+            fn count(counter: Arc<Counter>) -> u64 { unimplemented!() }
+
+            #[uniffi_macros::export]
+            fn lines(text: &str) -> impl Iterator<Item = String> + Send { unimplemented!() }
+
+            fn not_exported() -> u32 { unimplemented!() }
+
+            #[uniffi_macros::export(threadsafe)]
+            impl Counter {
+                fn new() -> Self { unimplemented!() }
+                fn starting_at(start: u64) -> Result<Counter> { unimplemented!() }
+                fn max_count() -> u64 { unimplemented!() }
+                fn increment(&self, by: u64) -> Result<u64> { unimplemented!() }
+                async fn wait_for<S: Into<String>>(&self, names: Vec<S>) { unimplemented!() }
+            }
+
+            uniffi_macros::include_scaffolding!("test");
+        "#;
+        const IDL: &str = r#"
+            namespace test {
+                double distance([ByRef] Point a, Point b);
+                u64 count(Counter counter);
+                [Iterator] sequence<string> lines([ByRef] string text);
+            };
+            enum Shape { "Circle", "Square" };
+            [Enum] interface Event { Progress(u64 done, u64 total); Finished(); };
+            [Error] enum CounterError { "Overflow", "Empty" };
+            dictionary Point { double x; double? y; record<string, u32> tags; bytes data; };
+            [Threadsafe]
+            interface Counter {
+                constructor();
+                [Name=starting_at, Throws=CounterError] constructor(u64 start);
+                static u64 max_count();
+                [Throws=CounterError] u64 increment(u64 by);
+                [Async] void wait_for(sequence<string> names);
+            };
+        "#;
+        let ci = ComponentInterface::from_rust_source(SOURCE).unwrap();
+        // The exported items make exactly the same component interface as the IDL.
+        assert_eq!(
+            ci.checksum(),
+            ComponentInterface::from_webidl(IDL).unwrap().checksum()
+        );
+        assert_eq!(ci.namespace_docstring(), Some("A component for testing."));
+        assert_eq!(
+            ci.iter_record_definitions()[0].docstring(),
+            Some("A point.")
+        );
+        assert_eq!(ci.iter_function_definitions().len(), 3);
+
+        assert!(rust_source_error_for("#[uniffi_macros::export] fn f() {}")
+            .starts_with("missing namespace definition"));
+        assert_eq!(
+            rust_source_error_for("#[uniffi_macros::export(error)] struct S { x: u8 }"),
+            "S can't be exported with the `error` flag"
+        );
+        assert!(
+            rust_source_error_for("#[uniffi_macros::export] fn f(x: Box<u8>) {}")
+                .ends_with("can't be passed across the FFI")
+        );
+        assert_eq!(
+            rust_source_error_for("#[uniffi_macros::export] impl Thing { fn consume(self) {} }"),
+            "Method consume can't take `self` by value"
+        );
+        assert_eq!(
+            rust_source_error_for("mod inner { mod deeper { #[uniffi_macros::export] fn f() {} } }"),
+            "f is exported from `mod deeper`, but only the items at the top level of lib.rs can be exported"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        const IDL: &str = r#"
+            /// A component for testing.
+            namespace test {
+                [Throws=CounterError]
+                record<string, u32> tally(sequence<string> words);
+            };
+            [Error]
+            enum CounterError { "Overflow" };
+            [Threadsafe]
+            interface Counter {
+                constructor(optional u64 start = 0);
+                [Async] u64 increment();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let json = ci.to_json().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["namespace"], "test");
+        assert_eq!(
+            parsed["functions"][0]["arguments"][0]["type"]["Sequence"],
+            "String"
+        );
+        assert!(parsed["types"]
+            .as_array()
+            .unwrap()
+            .contains(&"UInt32".into()));
+
+        // Reading the JSON back gives the same component interface.
+        let ci2 = ComponentInterface::from_json(&json).unwrap();
+        assert_eq!(ci2.checksum(), ci.checksum());
+        assert_eq!(ci2.iter_types(), ci.iter_types());
+        assert_eq!(ci2.namespace_docstring(), Some("A component for testing."));
+        let ffi_names = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_ffi_function_definitions()
+                .iter()
+                .map(|f| f.name().to_string())
+                .collect()
+        };
+        assert_eq!(ffi_names(&ci2), ffi_names(&ci));
+
+        let json = json.replacen(env!("CARGO_PKG_VERSION"), "0.0.0-old", 1);
+        assert_eq!(
+            ComponentInterface::from_json(&json).unwrap_err().to_string(),
+            format!(
+                "The component interface was dumped by uniffi-bindgen 0.0.0-old, but this is version {}",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
 }
@@ -0,0 +1,563 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Component interfaces defined by annotated Rust code.
+//!
+//! Instead of writing an IDL file, a crate can mark the definitions in its `lib.rs` that make up
+//! its component interface with the `#[uniffi_macros::export]` attribute, and name the namespace
+//! of the component when it includes the generated scaffolding:
+//!
+//! ```text
+//! #[uniffi_macros::export]
+//! struct TodoEntry {
+//!     text: String,
+//! }
+//!
+//! #[uniffi_macros::export]
+//! impl TodoList {
+//!     fn new() -> Self { ... }
+//!     fn add_entry(&mut self, entry: TodoEntry) -> Result<(), TodoError> { ... }
+//! }
+//!
+//! uniffi_macros::include_scaffolding!("todolist");
+//! ```
+//!
+//! The attribute itself leaves the code alone. Instead, we parse the source file here and write
+//! out the IDL that declares the same definitions, which then goes through the same parser as
+//! a hand-written IDL file. That way there's only one way to build a [`ComponentInterface`],
+//! and a crate gets exactly the same scaffolding and bindings whichever way it declares them.
+//!
+//! Only the items at the top level of the file can be exported, since that's where the
+//! scaffolding expects to find them, and exporting anything from an inline module is an error.
+//! The `export` attribute itself fails to compile on items in other modules, since we never see
+//! those.
+//!
+//! The IDL features that don't have an obvious Rust equivalent, such as default values, callback
+//! interfaces and errors with fields, still need an IDL file.
+//!
+//! [`ComponentInterface`]: super::ComponentInterface
+
+use anyhow::{anyhow, bail, Result};
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::punctuated::Punctuated;
+
+/// Write the IDL for the definitions exported from the given Rust source.
+pub(crate) fn idl_from_rust_source(source: &str) -> Result<String> {
+    let file = syn::parse_file(source).map_err(|e| anyhow!("Failed to parse rust code: {}", e))?;
+    // Crates often declare their own `Result` alias with the error type filled in,
+    // so we need to know what that is to tell which functions throw it.
+    let default_error = file.items.iter().find_map(|item| match item {
+        syn::Item::Type(alias) if alias.ident == "Result" => {
+            alias.generics.type_params().nth(1)?.default.clone()
+        }
+        _ => None,
+    });
+    let translator = Translator { default_error };
+    let mut namespace = None;
+    let mut functions = String::new();
+    let mut definitions = String::new();
+    for item in &file.items {
+        match item {
+            syn::Item::Macro(m)
+                if m.mac.path.segments.last().unwrap().ident == "include_scaffolding" =>
+            {
+                let name: syn::LitStr = m.mac.parse_body()?;
+                namespace = Some(name.value());
+            }
+            syn::Item::Fn(f) => {
+                if let Some(flags) = export_flags(&f.attrs)? {
+                    check_flags(&flags, &[], &f.sig.ident)?;
+                    translator.write_function(&mut functions, &f.attrs, &f.sig)?;
+                }
+            }
+            syn::Item::Impl(i) => {
+                if let Some(flags) = export_flags(&i.attrs)? {
+                    let name = match &*i.self_ty {
+                        syn::Type::Path(p) if p.qself.is_none() => {
+                            p.path.segments.last().unwrap().ident.clone()
+                        }
+                        t => bail!("Can't export an impl block for {}", t.to_token_stream()),
+                    };
+                    check_flags(&flags, &["threadsafe"], &name)?;
+                    translator.write_interface(
+                        &mut definitions,
+                        i,
+                        &name,
+                        flags.contains(&"threadsafe".to_string()),
+                    )?;
+                }
+            }
+            syn::Item::Struct(s) => {
+                if let Some(flags) = export_flags(&s.attrs)? {
+                    check_flags(&flags, &[], &s.ident)?;
+                    translator.write_dictionary(&mut definitions, s)?;
+                }
+            }
+            syn::Item::Enum(e) => {
+                if let Some(flags) = export_flags(&e.attrs)? {
+                    check_flags(&flags, &["error"], &e.ident)?;
+                    translator.write_enum(
+                        &mut definitions,
+                        e,
+                        flags.contains(&"error".to_string()),
+                    )?;
+                }
+            }
+            syn::Item::Mod(m) => check_no_exports(m)?,
+            _ => {}
+        }
+    }
+    let namespace = namespace.ok_or_else(|| {
+        anyhow!(
+            "missing namespace definition; name it with \
+             `uniffi_macros::include_scaffolding!(\"<namespace>\")`"
+        )
+    })?;
+    let mut idl = String::new();
+    write_docs(&mut idl, 0, &file.attrs);
+    idl.push_str(&format!("namespace {} {{\n{}}};\n", namespace, functions));
+    idl.push_str(&definitions);
+    Ok(idl)
+}
+
+/// Get the flags given to the `export` attribute, if the item has one.
+fn export_flags(attrs: &[syn::Attribute]) -> Result<Option<Vec<String>>> {
+    for attr in attrs {
+        let segments: Vec<_> = attr
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let is_export = match segments.as_slice() {
+            [name] => name == "export",
+            [krate, name] => krate == "uniffi_macros" && name == "export",
+            _ => false,
+        };
+        if is_export {
+            if attr.tokens.is_empty() {
+                return Ok(Some(Vec::new()));
+            }
+            let flags =
+                attr.parse_args_with(Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated)?;
+            return Ok(Some(flags.iter().map(|f| f.to_string()).collect()));
+        }
+    }
+    Ok(None)
+}
+
+/// Fail if anything inside an inline module is exported, rather than silently leaving it out.
+fn check_no_exports(module: &syn::ItemMod) -> Result<()> {
+    for item in module.content.iter().flat_map(|(_, items)| items) {
+        let (attrs, name) = match item {
+            syn::Item::Fn(f) => (&f.attrs, f.sig.ident.to_string()),
+            syn::Item::Struct(s) => (&s.attrs, s.ident.to_string()),
+            syn::Item::Enum(e) => (&e.attrs, e.ident.to_string()),
+            syn::Item::Impl(i) => (&i.attrs, i.self_ty.to_token_stream().to_string()),
+            syn::Item::Mod(m) => {
+                check_no_exports(m)?;
+                continue;
+            }
+            _ => continue,
+        };
+        if export_flags(attrs)?.is_some() {
+            bail!(
+                "{} is exported from `mod {}`, but only the items at the top level of lib.rs \
+                 can be exported",
+                name,
+                module.ident
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_flags(flags: &[String], allowed: &[&str], name: &syn::Ident) -> Result<()> {
+    for flag in flags {
+        if !allowed.contains(&flag.as_str()) {
+            bail!("{} can't be exported with the `{}` flag", name, flag);
+        }
+    }
+    Ok(())
+}
+
+/// Write the `///` doc comments for an item, from the `#[doc]` attributes rust turns them into.
+fn write_docs(out: &mut String, indent: usize, attrs: &[syn::Attribute]) {
+    for attr in attrs.iter().filter(|a| a.path.is_ident("doc")) {
+        if let Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(text),
+            ..
+        })) = attr.parse_meta()
+        {
+            for line in text.value().lines() {
+                let line = line.strip_prefix(' ').unwrap_or(line);
+                out.push_str(&format!("{:indent$}/// {}\n", "", line, indent = indent));
+            }
+        }
+    }
+}
+
+/// Write an item's `[Attributes]`, if it has any, on a line of their own.
+fn write_attributes(out: &mut String, indent: usize, attributes: &[String]) {
+    if !attributes.is_empty() {
+        out.push_str(&format!(
+            "{:indent$}[{}]\n",
+            "",
+            attributes.join(", "),
+            indent = indent
+        ));
+    }
+}
+
+/// The last segment of a type's path, if it's a plain path.
+fn last_segment(type_: &syn::Type) -> Option<&syn::PathSegment> {
+    match type_ {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+        _ => None,
+    }
+}
+
+/// The type arguments of a path segment, such as the `T` of `Vec<T>`.
+fn type_args(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+struct Translator {
+    // The error type of the crate's own `Result` alias, if it has one.
+    default_error: Option<syn::Type>,
+}
+
+/// What a function or method returns, as far as the IDL is concerned.
+struct ReturnType {
+    // The IDL type, or `None` for `void`.
+    type_: Option<String>,
+    throws: Option<String>,
+    iterator: bool,
+}
+
+/// The concrete types that the generic type parameters of a function stand for.
+///
+/// Functions that take `S: Into<String>` rather than a `String` still get called with a `String`
+/// by the scaffolding, so that's what the IDL declares.
+type Generics = HashMap<syn::Ident, syn::Type>;
+
+impl Translator {
+    fn write_function(
+        &self,
+        out: &mut String,
+        attrs: &[syn::Attribute],
+        sig: &syn::Signature,
+    ) -> Result<()> {
+        let return_type = self.return_type(sig, None)?;
+        write_docs(out, 4, attrs);
+        write_attributes(out, 4, &self.signature_attributes(sig, &return_type));
+        out.push_str(&format!(
+            "    {} {}({});\n",
+            return_type.type_.as_deref().unwrap_or("void"),
+            sig.ident,
+            self.arguments(sig)?.join(", ")
+        ));
+        Ok(())
+    }
+
+    fn write_interface(
+        &self,
+        out: &mut String,
+        item: &syn::ItemImpl,
+        name: &syn::Ident,
+        threadsafe: bool,
+    ) -> Result<()> {
+        let mut members = String::new();
+        for method in item.items.iter().filter_map(|i| match i {
+            syn::ImplItem::Method(m) => Some(m),
+            _ => None,
+        }) {
+            let sig = &method.sig;
+            let return_type = self.return_type(sig, Some(name))?;
+            let mut attributes = self.signature_attributes(sig, &return_type);
+            let arguments = self.arguments(sig)?.join(", ");
+            let is_method = sig
+                .inputs
+                .iter()
+                .any(|arg| matches!(arg, syn::FnArg::Receiver(_)));
+            let is_constructor =
+                !is_method && return_type.type_.as_deref() == Some(&name.to_string());
+            write_docs(&mut members, 4, &method.attrs);
+            if is_constructor {
+                if sig.ident != "new" {
+                    attributes.insert(0, format!("Name={}", sig.ident));
+                }
+                write_attributes(&mut members, 4, &attributes);
+                members.push_str(&format!("    constructor({});\n", arguments));
+            } else {
+                write_attributes(&mut members, 4, &attributes);
+                members.push_str(&format!(
+                    "    {}{} {}({});\n",
+                    if is_method { "" } else { "static " },
+                    return_type.type_.as_deref().unwrap_or("void"),
+                    sig.ident,
+                    arguments
+                ));
+            }
+        }
+        write_docs(out, 0, &item.attrs);
+        if threadsafe {
+            write_attributes(out, 0, &["Threadsafe".to_string()]);
+        }
+        out.push_str(&format!("interface {} {{\n{}}};\n", name, members));
+        Ok(())
+    }
+
+    fn write_dictionary(&self, out: &mut String, item: &syn::ItemStruct) -> Result<()> {
+        let fields = match &item.fields {
+            syn::Fields::Named(fields) => fields,
+            _ => bail!("Record {} must have named fields", item.ident),
+        };
+        write_docs(out, 0, &item.attrs);
+        out.push_str(&format!("dictionary {} {{\n", item.ident));
+        for field in &fields.named {
+            write_docs(out, 4, &field.attrs);
+            out.push_str(&format!(
+                "    {} {};\n",
+                self.idl_type(&field.ty, &Generics::new())?,
+                field.ident.as_ref().unwrap()
+            ));
+        }
+        out.push_str("};\n");
+        Ok(())
+    }
+
+    fn write_enum(&self, out: &mut String, item: &syn::ItemEnum, is_error: bool) -> Result<()> {
+        write_docs(out, 0, &item.attrs);
+        // Errors only expose their variants, and whatever data they have goes into their message.
+        if is_error
+            || item
+                .variants
+                .iter()
+                .all(|v| matches!(v.fields, syn::Fields::Unit))
+        {
+            if is_error {
+                write_attributes(out, 0, &["Error".to_string()]);
+            }
+            out.push_str(&format!("enum {} {{\n", item.ident));
+            for variant in &item.variants {
+                write_docs(out, 4, &variant.attrs);
+                out.push_str(&format!("    \"{}\",\n", variant.ident));
+            }
+            out.push_str("};\n");
+            return Ok(());
+        }
+        write_attributes(out, 0, &["Enum".to_string()]);
+        out.push_str(&format!("interface {} {{\n", item.ident));
+        for variant in &item.variants {
+            let fields = match &variant.fields {
+                syn::Fields::Named(fields) => fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        Ok(format!(
+                            "{} {}",
+                            self.idl_type(&f.ty, &Generics::new())?,
+                            f.ident.as_ref().unwrap()
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                syn::Fields::Unit => Vec::new(),
+                syn::Fields::Unnamed(_) => {
+                    bail!(
+                        "Variant {}::{} must have named fields",
+                        item.ident,
+                        variant.ident
+                    )
+                }
+            };
+            write_docs(out, 4, &variant.attrs);
+            out.push_str(&format!("    {}({});\n", variant.ident, fields.join(", ")));
+        }
+        out.push_str("};\n");
+        Ok(())
+    }
+
+    fn signature_attributes(&self, sig: &syn::Signature, return_type: &ReturnType) -> Vec<String> {
+        let mut attributes = Vec::new();
+        if sig.asyncness.is_some() {
+            attributes.push("Async".to_string());
+        }
+        if return_type.iterator {
+            attributes.push("Iterator".to_string());
+        }
+        if let Some(error) = &return_type.throws {
+            attributes.push(format!("Throws={}", error));
+        }
+        attributes
+    }
+
+    fn arguments(&self, sig: &syn::Signature) -> Result<Vec<String>> {
+        let generics = generic_types(&sig.generics);
+        let mut arguments = Vec::new();
+        for arg in &sig.inputs {
+            let arg = match arg {
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    bail!("Method {} can't take `self` by value", sig.ident)
+                }
+                syn::FnArg::Receiver(_) => continue,
+                syn::FnArg::Typed(arg) => arg,
+            };
+            let name = match &*arg.pat {
+                syn::Pat::Ident(p) => &p.ident,
+                _ => bail!("The arguments of {} must be plain names", sig.ident),
+            };
+            let type_ = match &*arg.ty {
+                syn::Type::Reference(r) => {
+                    format!("[ByRef] {}", self.idl_type(&r.elem, &generics)?)
+                }
+                t => self.idl_type(t, &generics)?,
+            };
+            arguments.push(format!("{} {}", type_, name));
+        }
+        Ok(arguments)
+    }
+
+    fn return_type(
+        &self,
+        sig: &syn::Signature,
+        self_name: Option<&syn::Ident>,
+    ) -> Result<ReturnType> {
+        let generics = generic_types(&sig.generics);
+        let mut return_type = ReturnType {
+            type_: None,
+            throws: None,
+            iterator: false,
+        };
+        let mut type_ = match &sig.output {
+            syn::ReturnType::Default => return Ok(return_type),
+            syn::ReturnType::Type(_, t) => &**t,
+        };
+        if let Some(segment) = last_segment(type_).filter(|s| s.ident == "Result") {
+            let args = type_args(segment);
+            let error = match args.get(1).copied().or(self.default_error.as_ref()) {
+                Some(error) => error,
+                None => bail!("Can't tell which error {} returns", sig.ident),
+            };
+            return_type.throws = Some(self.idl_type(error, &generics)?);
+            type_ = args[0];
+        }
+        return_type.type_ = match type_ {
+            syn::Type::Tuple(t) if t.elems.is_empty() => None,
+            syn::Type::ImplTrait(t) => {
+                let item = t.bounds.iter().find_map(|bound| match bound {
+                    syn::TypeParamBound::Trait(t) => iterator_item(&t.path),
+                    _ => None,
+                });
+                match item {
+                    Some(item) => {
+                        return_type.iterator = true;
+                        Some(format!("sequence<{}>", self.idl_type(item, &generics)?))
+                    }
+                    None => bail!("{} must return an `impl Iterator<Item = T>`", sig.ident),
+                }
+            }
+            t if matches!(last_segment(t), Some(s) if s.ident == "Self") => match self_name {
+                Some(name) => Some(name.to_string()),
+                None => bail!("{} can't return `Self`", sig.ident),
+            },
+            t => Some(self.idl_type(t, &generics)?),
+        };
+        Ok(return_type)
+    }
+
+    /// Work out the IDL type that corresponds to a rust type.
+    fn idl_type(&self, type_: &syn::Type, generics: &Generics) -> Result<String> {
+        let unsupported = || {
+            anyhow!(
+                "The type {} can't be passed across the FFI",
+                type_.to_token_stream()
+            )
+        };
+        if let syn::Type::Slice(s) = type_ {
+            return Ok(format!("sequence<{}>", self.idl_type(&s.elem, generics)?));
+        }
+        let segment = last_segment(type_).ok_or_else(unsupported)?;
+        let args = type_args(segment);
+        let arg = |i: usize| -> Result<String> {
+            self.idl_type(args.get(i).ok_or_else(unsupported)?, generics)
+        };
+        Ok(match segment.ident.to_string().as_str() {
+            "bool" => "boolean".into(),
+            "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" => {
+                segment.ident.to_string()
+            }
+            "f32" => "float".into(),
+            "f64" => "double".into(),
+            "String" | "str" => "string".into(),
+            "SystemTime" => "timestamp".into(),
+            "Duration" => "duration".into(),
+            "Option" => format!("{}?", arg(0)?),
+            // Objects are passed around as an `Arc`.
+            "Arc" => arg(0)?,
+            "Vec" if arg(0)? == "u8" => "bytes".into(),
+            "Vec" => format!("sequence<{}>", arg(0)?),
+            "HashMap" => format!("record<{}, {}>", arg(0)?, arg(1)?),
+            _ if !args.is_empty() => return Err(unsupported()),
+            _ => match generics.get(&segment.ident) {
+                Some(concrete) => self.idl_type(concrete, generics)?,
+                // Anything else should be one of the types that the component defines.
+                None => segment.ident.to_string(),
+            },
+        })
+    }
+}
+
+/// Find the item type of an `Iterator<Item = T>` bound.
+fn iterator_item(path: &syn::Path) -> Option<&syn::Type> {
+    let segment = path.segments.last().filter(|s| s.ident == "Iterator")?;
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Binding(b) if b.ident == "Item" => Some(&b.ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Find the concrete types behind a function's `T: Into<U>` type parameters.
+fn generic_types(generics: &syn::Generics) -> Generics {
+    let mut types = Generics::new();
+    let mut add_bounds =
+        |param: &syn::Ident, bounds: &Punctuated<syn::TypeParamBound, syn::Token![+]>| {
+            for bound in bounds {
+                if let syn::TypeParamBound::Trait(t) = bound {
+                    if let Some(segment) = t.path.segments.last().filter(|s| s.ident == "Into") {
+                        if let Some(concrete) = type_args(segment).first() {
+                            types.insert(param.clone(), (*concrete).clone());
+                        }
+                    }
+                }
+            }
+        };
+    for param in generics.type_params() {
+        add_bounds(&param.ident, &param.bounds);
+    }
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(p) = predicate {
+                if let Some(segment) = last_segment(&p.bounded_ty) {
+                    add_bounds(&segment.ident, &p.bounds);
+                }
+            }
+        }
+    }
+    types
+}
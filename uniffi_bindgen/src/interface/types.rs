@@ -25,6 +25,7 @@
 
 use anyhow::bail;
 use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::TryFrom;
 use std::{collections::hash_map::Entry, collections::HashMap, collections::HashSet};
 
@@ -36,7 +37,7 @@ use super::Attributes;
 /// For the types that involve memory allocation, we make a distinction between
 /// "owned" types (the recipient must free it, or pass it to someone else) and
 /// "borrowed" types (the sender must keep it alive for the duration of the call).
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub enum FFIType {
     // N.B. there are no booleans at this layer, since they cause problems for JNA.
     UInt8,
@@ -49,10 +50,6 @@ pub enum FFIType {
     Int64,
     Float32,
     Float64,
-    /// A `char*` pointer belonging to a rust-owned CString.
-    /// If you've got one of these, you must call the appropriate rust function to free it.
-    /// This is currently only used for error messages, and may go away in future.
-    RustCString,
     /// A byte buffer allocated by rust, and owned by whoever currently holds it.
     /// If you've got one of these, you must either call the appropriate rust function to free it
     /// or pass it to someone that will.
@@ -60,10 +57,13 @@ pub enum FFIType {
     /// A borrowed reference to some raw bytes owned by foreign language code.
     /// The provider of this reference must keep it alive for the duration of the receiving call.
     ForeignBytes,
-    /// An error struct, containing a numberic error code and char* pointer to error string.
-    /// The string is owned by rust and allocated on the rust heap, and must be freed by
-    /// passing it to the appropriate `string_free` FFI function.
-    RustError,
+    /// A pointer to a single function in the foreign language code, through which rust
+    /// code can call back into foreign-language implementations of a callback interface.
+    /// There is one such function per callback interface, which dispatches on a method index.
+    ForeignCallback,
+    /// A pointer to a function in the foreign language code, which rust calls with the result
+    /// of an `[Async]` function or method once its future has completed.
+    FutureCallback,
     // TODO: you can imagine a richer structural typesystem here, e.g. `Ref<String>` or something.
     // We don't need that yet and it's possible we never will, so it isn't here for now.
 }
@@ -71,7 +71,7 @@ pub enum FFIType {
 /// Represents all the different high-level types that can be used in a component interface.
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Type {
     // Primitive types.
     UInt8,
@@ -86,15 +86,27 @@ pub enum Type {
     Float64,
     Boolean,
     String,
+    Bytes,
+    Timestamp,
+    Duration,
     // Types defined in the component API, each of which has a string name.
     Object(String),
     Record(String),
     Enum(String),
     Error(String),
+    CallbackInterface(String),
     // Structurally recursive types.
     Optional(Box<Type>),
     Sequence(Box<Type>),
-    Map(/* String, */ Box<Type>),
+    Map(/* key */ Box<Type>, /* value */ Box<Type>),
+    // A rust iterator over items of the given type. Only functions and methods can return these.
+    Iterator(Box<Type>),
+    // A value that may be any one of several types, with a name derived from those types.
+    Union(String, Vec<Type>),
+    // A user-defined type that is passed over the FFI as one of the builtin types.
+    Custom(String, Box<Type>),
+    // A type defined by another component, named along with the namespace of that component.
+    External(/* name */ String, /* namespace */ String),
 }
 
 /// When passing data across the FFI, each `Type` value will be lowered into a corresponding
@@ -118,16 +130,29 @@ impl From<&Type> for FFIType {
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FFIType::RustBuffer,
+            // Bytes are passed as the raw contents of a buffer, without any per-item encoding.
+            Type::Bytes => FFIType::RustBuffer,
+            // Timestamps and durations are serialized as a count of seconds followed by nanoseconds.
+            Type::Timestamp | Type::Duration => FFIType::RustBuffer,
             // Objects are passed as opaque integer handles.
             Type::Object(_) => FFIType::UInt64,
-            // Enums are passed as integers.
-            Type::Enum(_) => FFIType::UInt32,
-            // Errors have their own special type.
-            Type::Error(_) => FFIType::RustError,
+            // Callback interfaces are passed as opaque integer handles, held by the foreign language.
+            Type::CallbackInterface(_) => FFIType::UInt64,
+            // Iterators are passed as opaque integer handles, from which the items are pulled in batches.
+            Type::Iterator(_) => FFIType::UInt64,
             // Other types are serialized into a bytebuffer and deserialized on the other side.
-            Type::Record(_) | Type::Optional(_) | Type::Sequence(_) | Type::Map(_) => {
-                FFIType::RustBuffer
-            }
+            // For enums, errors and unions, that's a u32 tag identifying the variant followed by its data.
+            Type::Enum(_)
+            | Type::Error(_)
+            | Type::Record(_)
+            | Type::Optional(_)
+            | Type::Sequence(_)
+            | Type::Map(..)
+            | Type::Union(..) => FFIType::RustBuffer,
+            // External types are records or enums from another component, so they're serialized too.
+            Type::External(..) => FFIType::RustBuffer,
+            // Custom types use exactly the same representation as the builtin type they wrap.
+            Type::Custom(_, builtin) => FFIType::from(builtin.as_ref()),
         }
     }
 }
@@ -154,6 +179,9 @@ impl Type {
             Type::Float64 => "f64".into(),
             Type::String => "string".into(),
             Type::Boolean => "bool".into(),
+            Type::Bytes => "bytes".into(),
+            Type::Timestamp => "timestamp".into(),
+            Type::Duration => "duration".into(),
             // API defined types.
             // Note that these all get unique names, and the parser ensures that the names do not
             // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -162,6 +190,9 @@ impl Type {
             Type::Error(nm) => format!("Error{}", nm),
             Type::Enum(nm) => format!("Enum{}", nm),
             Type::Record(nm) => format!("Record{}", nm),
+            Type::CallbackInterface(nm) => format!("CallbackInterface{}", nm),
+            Type::Custom(nm, _) => format!("Custom{}", nm),
+            Type::External(nm, _) => format!("External{}", nm),
             // Recursive types.
             // These add a prefix to the name of the underlying type.
             // The component API definition cannot give names to recursive types, so as long as the
@@ -169,7 +200,46 @@ impl Type {
             // acccidentally generating name collisions.
             Type::Optional(t) => format!("Optional{}", t.canonical_name()),
             Type::Sequence(t) => format!("Sequence{}", t.canonical_name()),
-            Type::Map(t) => format!("Map{}", t.canonical_name()),
+            Type::Map(k, v) => format!("Map{}{}", k.canonical_name(), v.canonical_name()),
+            Type::Iterator(t) => format!("Iterator{}", t.canonical_name()),
+            Type::Union(nm, _) => format!("Union{}", nm),
+        }
+    }
+
+    /// Get the name by which this type is known when it's a member of a union type.
+    ///
+    /// The sum types generated for a union have one variant per member type, and this is
+    /// the name of that variant. It's also used to build the name of the union itself,
+    /// by joining the names of its members, e.g. "StringOrPoint" for `(string or Point)`.
+    pub fn union_member_name(&self) -> String {
+        match self {
+            Type::Int8 => "I8".into(),
+            Type::UInt8 => "U8".into(),
+            Type::Int16 => "I16".into(),
+            Type::UInt16 => "U16".into(),
+            Type::Int32 => "I32".into(),
+            Type::UInt32 => "U32".into(),
+            Type::Int64 => "I64".into(),
+            Type::UInt64 => "U64".into(),
+            Type::Float32 => "F32".into(),
+            Type::Float64 => "F64".into(),
+            Type::String => "String".into(),
+            Type::Boolean => "Bool".into(),
+            Type::Bytes => "Bytes".into(),
+            Type::Timestamp => "Timestamp".into(),
+            Type::Duration => "Duration".into(),
+            Type::Object(nm)
+            | Type::Error(nm)
+            | Type::Enum(nm)
+            | Type::Record(nm)
+            | Type::CallbackInterface(nm)
+            | Type::Union(nm, _)
+            | Type::Custom(nm, _)
+            | Type::External(nm, _) => nm.clone(),
+            Type::Optional(t) => format!("Optional{}", t.union_member_name()),
+            Type::Sequence(t) => format!("Sequence{}", t.union_member_name()),
+            Type::Map(k, v) => format!("Map{}{}", k.union_member_name(), v.union_member_name()),
+            Type::Iterator(t) => format!("Iterator{}", t.union_member_name()),
         }
     }
 }
@@ -252,6 +322,27 @@ impl TypeUniverse {
     }
 }
 
+// The named type definitions are only needed while resolving the types in the IDL,
+// so a serialized universe is just the list of all the known types.
+impl Serialize for TypeUniverse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut types: Vec<&Type> = self.all_known_types.iter().collect();
+        types.sort_by_key(|t| t.canonical_name());
+        types.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeUniverse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(TypeUniverse {
+            type_definitions: HashMap::new(),
+            all_known_types: Vec::<Type>::deserialize(deserializer)?
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
 /// Trait to help with an early "type discovery" phase when processing the IDL.
 ///
 /// Ths trait does structural matching against weedle AST nodes from a parsed
@@ -279,6 +370,7 @@ impl TypeFinder for weedle::Definition<'_> {
             weedle::Definition::Dictionary(d) => d.add_type_definitions_to(types),
             weedle::Definition::Enum(d) => d.add_type_definitions_to(types),
             weedle::Definition::Typedef(d) => d.add_type_definitions_to(types),
+            weedle::Definition::CallbackInterface(d) => d.add_type_definitions_to(types),
             _ => Ok(()),
         }
     }
@@ -287,10 +379,34 @@ impl TypeFinder for weedle::Definition<'_> {
 impl TypeFinder for weedle::InterfaceDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         let name = self.identifier.0.to_string();
+        // Some enum and error types are defined using an `interface` with a special attribute.
+        if let Some(attrs) = &self.attributes {
+            let attrs = Attributes::try_from(attrs)?;
+            if attrs.contains_error_attr() {
+                return types.add_type_definition(self.identifier.0, Type::Error(name));
+            }
+            if attrs.contains_enum_attr() {
+                return types.add_type_definition(self.identifier.0, Type::Enum(name));
+            }
+            // The `[Constants]` interface only holds the namespace's constants, so it isn't a type.
+            if attrs.contains_constants_attr() {
+                return Ok(());
+            }
+        }
         types.add_type_definition(self.identifier.0, Type::Object(name))
     }
 }
 
+impl TypeFinder for weedle::CallbackInterfaceDefinition<'_> {
+    fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
+        if self.attributes.is_some() {
+            bail!("no callback interface attributes are currently supported");
+        }
+        let name = self.identifier.0.to_string();
+        types.add_type_definition(self.identifier.0, Type::CallbackInterface(name))
+    }
+}
+
 impl TypeFinder for weedle::DictionaryDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         let name = self.identifier.0.to_string();
@@ -305,6 +421,8 @@ impl TypeFinder for weedle::EnumDefinition<'_> {
         if let Some(attrs) = &self.attributes {
             let attrs = Attributes::try_from(attrs)?;
             if attrs.contains_error_attr() {
+                // Flat errors carry their message across the FFI as a string.
+                types.add_known_type(Type::String)?;
                 return types.add_type_definition(self.identifier.0, Type::Error(name));
             }
         }
@@ -314,17 +432,61 @@ impl TypeFinder for weedle::EnumDefinition<'_> {
 
 impl TypeFinder for weedle::TypedefDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
-        if self.attributes.is_some() {
-            bail!("no typedef attributes are currently supported");
+        let name = self.identifier.0;
+        // Custom and external types are defined using a `typedef` with a special attribute.
+        if let Some(attrs) = &self.attributes {
+            let attrs = Attributes::try_from(attrs)?;
+            if let Some(namespace) = attrs.get_external_namespace() {
+                if !is_extern_placeholder(&self.type_) {
+                    bail!(
+                        "external type {} must be declared as `typedef extern {}`",
+                        name,
+                        name
+                    );
+                }
+                return types.add_type_definition(
+                    name,
+                    Type::External(name.to_string(), namespace.to_string()),
+                );
+            }
+            if !attrs.contains_custom_attr() {
+                bail!("no typedef attributes other than [Custom] and [External] are currently supported");
+            }
+            let builtin = types.resolve_type_expression(&self.type_)?;
+            if !is_builtin_primitive(&builtin) {
+                bail!(
+                    "custom type {} must wrap a builtin primitive type, not {:?}",
+                    name,
+                    builtin
+                );
+            }
+            return types
+                .add_type_definition(name, Type::Custom(name.to_string(), Box::new(builtin)));
         }
         // For now, we assume that the typedef must refer to an already-defined type, which means
         // we can look it up in the TypeUniverse. This should suffice for our needs for
         // a good long while before we consider implementing a more complex delayed resolution strategy.
         let t = types.resolve_type_expression(&self.type_)?;
-        types.add_type_definition(self.identifier.0, t)
+        types.add_type_definition(name, t)
     }
 }
 
+/// Check whether a typedef'd type is the `extern` placeholder used to declare external types.
+///
+/// The definition of an external type lives in another component, so there's nothing
+/// for us to resolve here; we just want the IDL to say clearly that it's defined elsewhere.
+fn is_extern_placeholder(type_: &weedle::types::AttributedType<'_>) -> bool {
+    matches!(
+        &type_.type_,
+        weedle::types::Type::Single(weedle::types::SingleType::NonAny(
+            weedle::types::NonAnyType::Identifier(weedle::types::MayBeNull {
+                type_: weedle::common::Identifier("extern"),
+                q_mark: None,
+            })
+        ))
+    ) && type_.attributes.is_none()
+}
+
 /// Trait to help resolving an IDL type node to a [Type].
 ///
 /// Ths trait does structural matching against type-related weedle AST nodes from
@@ -351,8 +513,39 @@ impl TypeResolver for weedle::types::Type<'_> {
                 weedle::types::SingleType::Any(_) => bail!("no support for `any` types"),
                 weedle::types::SingleType::NonAny(t) => t.resolve_type_expression(types),
             },
-            weedle::types::Type::Union(_) => bail!("no support for union types yet"),
+            weedle::types::Type::Union(t) => t.resolve_type_expression(types),
+        }
+    }
+}
+
+impl TypeResolver for weedle::types::UnionType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        let mut members: Vec<Type> = Vec::new();
+        for member in &self.body.list {
+            let t = match member {
+                weedle::types::UnionMemberType::Single(t) => t.resolve_type_expression(types)?,
+                weedle::types::UnionMemberType::Union(_) => {
+                    bail!("nested union types are not supported")
+                }
+            };
+            match t {
+                // Callback interfaces can only be passed from foreign-language code and errors can
+                // only be thrown, so neither can be part of a union. Objects aren't supported yet.
+                Type::Object(_) | Type::CallbackInterface(_) | Type::Error(_) => {
+                    bail!("{:?} cannot be a member of a union type", t)
+                }
+                _ if members.contains(&t) => {
+                    bail!("Duplicate member {:?} in union type", t)
+                }
+                _ => members.push(t),
+            }
         }
+        let name = members
+            .iter()
+            .map(|t| t.union_member_name())
+            .collect::<Vec<_>>()
+            .join("Or");
+        types.add_known_type(Type::Union(name, members))
     }
 }
 
@@ -370,6 +563,18 @@ impl TypeResolver for weedle::types::NonAnyType<'_> {
     }
 }
 
+impl TypeResolver for &weedle::types::ConstType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        match self {
+            weedle::types::ConstType::Boolean(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::Identifier(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::Integer(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::FloatingPoint(t) => t.resolve_type_expression(types),
+            _ => bail!("no support for type {:?}", self),
+        }
+    }
+}
+
 impl TypeResolver for &weedle::types::AttributedNonAnyType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
         if self.attributes.is_some() {
@@ -425,10 +630,17 @@ impl TypeResolver for weedle::types::SequenceType<'_> {
 
 impl TypeResolver for weedle::types::RecordType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
-        let t = (&self.generics.body.2).resolve_type_expression(types)?;
-        // Maps always have string keys, make sure the `String` type is known.
-        types.add_known_type(Type::String)?;
-        types.add_known_type(Type::Map(Box::new(t)))
+        let k = match self.generics.body.0.as_ref() {
+            weedle::types::RecordKeyType::Byte(_)
+            | weedle::types::RecordKeyType::DOM(_)
+            | weedle::types::RecordKeyType::USV(_) => types.add_known_type(Type::String)?,
+            weedle::types::RecordKeyType::NonAny(t) => t.resolve_type_expression(types)?,
+        };
+        if !is_valid_map_key(&k) {
+            bail!("Map keys must be strings, integers or enums, not {:?}", k);
+        }
+        let v = self.generics.body.2.resolve_type_expression(types)?;
+        types.add_known_type(Type::Map(Box::new(k), Box::new(v)))
     }
 }
 
@@ -468,6 +680,45 @@ impl TypeResolver for weedle::types::DoubleType {
     }
 }
 
+/// Check whether a [Type] is one of the builtin primitive types, which custom types can wrap.
+fn is_builtin_primitive(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Float32
+            | Type::Float64
+            | Type::Boolean
+            | Type::String
+    )
+}
+
+/// Check whether a [Type] can be used as the key of a map.
+///
+/// Keys need to be hashable and comparable in every language, which rules out
+/// floats and anything that contains them.
+fn is_valid_map_key(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::String
+            | Type::Enum(_)
+    )
+}
+
 /// Resolve built-in API types by name.
 ///
 /// Given an identifier from the IDL, this will return `Some(Type)` if it names one of the
@@ -485,6 +736,9 @@ fn resolve_builtin_type(name: &str) -> Option<Type> {
         "i64" => Some(Type::Int64),
         "f32" => Some(Type::Float32),
         "f64" => Some(Type::Float64),
+        "bytes" => Some(Type::Bytes),
+        "timestamp" => Some(Type::Timestamp),
+        "duration" => Some(Type::Duration),
         _ => None,
     }
 }
@@ -38,6 +38,10 @@
 //! }
 //! ```
 //!
+//! Alternatively, you can mark the functions, structs, enums and `impl` blocks in your crate's
+//! `lib.rs` with `#[uniffi_macros::export]`, and use the path to `lib.rs` in place of the path to
+//! the `.idl` file in the steps below.
+//!
 //! ### 2) Implement the Component Interface as a Rust crate
 //!
 //! With the interface, defined, provide a corresponding implementation of that interface
@@ -95,6 +99,7 @@
 const BINDGEN_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
 use std::convert::TryInto;
 use std::io::prelude::*;
 use std::{
@@ -105,10 +110,12 @@ use std::{
 };
 
 pub mod bindings;
+pub mod diff;
 pub mod interface;
+pub mod lint;
 pub mod scaffolding;
 
-use bindings::TargetLanguage;
+use bindings::{BindingsConfig, TargetLanguage};
 use interface::ComponentInterface;
 use scaffolding::RustScaffolding;
 
@@ -125,10 +132,16 @@ pub fn generate_component_scaffolding<P: AsRef<Path>>(
     let idl_file = idl_file.as_ref();
     let component = parse_idl(&idl_file)?;
     ensure_versions_compatibility(&idl_file, manifest_path_override)?;
-    let mut filename = Path::new(&idl_file)
-        .file_stem()
-        .ok_or_else(|| anyhow!("not a file"))?
-        .to_os_string();
+    // The scaffolding for a component defined in its rust source is named after its namespace,
+    // since that's how `uniffi_macros::include_scaffolding!` finds it.
+    let mut filename = if is_rust_source(idl_file) {
+        component.namespace().into()
+    } else {
+        Path::new(&idl_file)
+            .file_stem()
+            .ok_or_else(|| anyhow!("not a file"))?
+            .to_os_string()
+    };
     filename.push(".uniffi.rs");
     let mut out_dir = get_out_dir(&idl_file, out_dir_override)?;
     out_dir.push(filename);
@@ -188,33 +201,118 @@ pub fn generate_bindings<P: AsRef<Path>>(
     idl_file: P,
     target_languages: Vec<&str>,
     out_dir_override: Option<P>,
+    config_file_override: Option<P>,
     try_format_code: bool,
 ) -> Result<()> {
     let out_dir_override = out_dir_override.as_ref().map(|p| p.as_ref());
+    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
     let idl_file = PathBuf::from(idl_file.as_ref())
         .canonicalize()
         .map_err(|e| anyhow!("Failed to find idl file: {:?}", e))?;
     let component = parse_idl(&idl_file)?;
+    let config = load_bindings_config(&idl_file, config_file_override)?;
     let out_dir = get_out_dir(&idl_file, out_dir_override)?;
     for language in target_languages {
-        bindings::write_bindings(&component, &out_dir, language.try_into()?, try_format_code)?;
+        bindings::write_bindings(
+            &component,
+            &config,
+            &out_dir,
+            language.try_into()?,
+            try_format_code,
+        )?;
+    }
+    Ok(())
+}
+
+// Write the parsed component interface as JSON, for tools that want to consume it without parsing
+// the IDL themselves. It's written to stdout unless an output file is given.
+pub fn dump_component_interface<P: AsRef<Path>>(idl_file: P, out_file: Option<P>) -> Result<()> {
+    let component = parse_idl(idl_file.as_ref())?;
+    let json = component.to_json()?;
+    match out_file {
+        Some(out_file) => {
+            let mut f = File::create(out_file.as_ref())
+                .map_err(|e| anyhow!("Failed to create output file: {:?}", e))?;
+            writeln!(f, "{}", json).map_err(|e| anyhow!("Failed to write output file: {:?}", e))?;
+        }
+        None => println!("{}", json),
     }
     Ok(())
 }
 
+// Print the changes between two versions of a component interface, classifying each of them as
+// breaking or compatible for the given target languages (or for all of them, if none are given).
+// Returns whether any of the changes were breaking.
+pub fn diff_component_interfaces<P: AsRef<Path>>(
+    old_idl_file: P,
+    new_idl_file: P,
+    target_languages: Vec<&str>,
+) -> Result<bool> {
+    let old = parse_idl(old_idl_file.as_ref())?;
+    let new = parse_idl(new_idl_file.as_ref())?;
+    let languages = if target_languages.is_empty() {
+        vec![
+            TargetLanguage::Kotlin,
+            TargetLanguage::Swift,
+            TargetLanguage::Python,
+        ]
+    } else {
+        target_languages
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>>>()?
+    };
+    let mut found_breaking = false;
+    for change in diff::diff(&old, &new) {
+        let change = change.for_languages(&languages);
+        found_breaking |= change.is_breaking();
+        println!("{}", change);
+    }
+    Ok(found_breaking)
+}
+
+// Check a component interface for names that won't work well in some target language, types and
+// errors that are never used, and objects that are likely to leak, skipping the allowed lints.
+// Returns whether any problems were found.
+pub fn lint_component_interface<P: AsRef<Path>>(
+    idl_file: P,
+    allowed_lints: Vec<&str>,
+) -> Result<bool> {
+    let component = parse_idl(idl_file.as_ref())?;
+    let allowed_lints = allowed_lints
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<lint::Lint>>>()?;
+    let warnings: Vec<_> = lint::lint(&component)
+        .into_iter()
+        .filter(|warning| !allowed_lints.contains(&warning.lint()))
+        .collect();
+    for warning in &warnings {
+        println!("{}", warning);
+    }
+    Ok(!warnings.is_empty())
+}
+
 // Run tests against the foreign language bindings (generated and compiled at the same time).
 // Note that the cdylib we're testing against must be built already.
+// Any external idl files are for components that define external types used by this one,
+// whose bindings are generated and compiled first so that the scripts can use them too.
 pub fn run_tests<P: AsRef<Path>>(
     cdylib_dir: P,
     idl_file: P,
+    external_idl_files: Vec<P>,
     test_scripts: Vec<&str>,
 ) -> Result<()> {
     let cdylib_dir = cdylib_dir.as_ref();
-    let idl_file = idl_file.as_ref();
-    let idl_file = PathBuf::from(idl_file)
-        .canonicalize()
-        .map_err(|e| anyhow!("Failed to find idl file: {:?}", e))?;
-    let component = parse_idl(&idl_file)?;
+    let mut components = Vec::new();
+    for idl_file in external_idl_files.iter().chain(std::iter::once(&idl_file)) {
+        let idl_file = PathBuf::from(idl_file.as_ref())
+            .canonicalize()
+            .map_err(|e| anyhow!("Failed to find idl file: {:?}", e))?;
+        let component = parse_idl(&idl_file)?;
+        let config = load_bindings_config(&idl_file, None)?;
+        components.push((component, config));
+    }
 
     // Group the test scripts by language first.
     let mut language_tests: HashMap<TargetLanguage, Vec<String>> = HashMap::new();
@@ -230,8 +328,10 @@ pub fn run_tests<P: AsRef<Path>>(
     }
 
     for (lang, test_scripts) in language_tests {
-        bindings::write_bindings(&component, &cdylib_dir, lang, true)?;
-        bindings::compile_bindings(&component, &cdylib_dir, lang)?;
+        for (component, config) in &components {
+            bindings::write_bindings(component, config, cdylib_dir, lang, true)?;
+            bindings::compile_bindings(component, cdylib_dir, lang)?;
+        }
         for test_script in test_scripts {
             bindings::run_script(cdylib_dir, &test_script, lang)?;
         }
@@ -254,9 +354,67 @@ fn get_out_dir(idl_file: &Path, out_dir_override: Option<&Path>) -> Result<PathB
     })
 }
 
+/// The contents of a component's `uniffi.toml` file.
+#[derive(Debug, Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    bindings: BindingsConfig,
+}
+
+// Load the config for the foreign language bindings. If no config file is specified, we use the
+// `uniffi.toml` next to the crate's Cargo.toml if there is one (assuming the IDL file is under src/).
+fn load_bindings_config(
+    idl_file: &Path,
+    config_file_override: Option<&Path>,
+) -> Result<BindingsConfig> {
+    let config_file = match config_file_override {
+        Some(p) => p.to_owned(),
+        None => {
+            let default_file = idl_file
+                .parent()
+                .and_then(Path::parent)
+                .ok_or_else(|| anyhow!("IDL file has no crate directory"))?
+                .join("uniffi.toml");
+            if !default_file.exists() {
+                return Ok(BindingsConfig::default());
+            }
+            default_file
+        }
+    };
+    let contents = slurp_file(&config_file)
+        .map_err(|_| anyhow!("Failed to read config file from {:?}", &config_file))?;
+    let config: Config = toml::from_str(&contents)
+        .map_err(|e| anyhow!("Failed to parse config file {:?}: {}", &config_file, e))?;
+    Ok(config.bindings)
+}
+
+// Components can also be defined by the items exported from their rust source, in place of an IDL file.
+fn is_rust_source(idl_file: &Path) -> bool {
+    matches!(idl_file.extension(), Some(ext) if ext == "rs")
+}
+
 fn parse_idl(idl_file: &Path) -> Result<ComponentInterface> {
     let idl =
         slurp_file(idl_file).map_err(|_| anyhow!("Failed to read IDL from {:?}", &idl_file))?;
+    // Or by the JSON that `dump` produces from another component interface.
+    if matches!(idl_file.extension(), Some(ext) if ext == "json") {
+        return ComponentInterface::from_json(&idl).map_err(|e| {
+            anyhow!(
+                "Failed to read the component interface from {:?}: {}",
+                &idl_file,
+                e
+            )
+        });
+    }
+    if is_rust_source(idl_file) {
+        return ComponentInterface::from_rust_source(&idl).map_err(|e| {
+            anyhow!(
+                "Failed to read the component interface from {:?}: {}",
+                &idl_file,
+                e
+            )
+        });
+    }
     idl.parse::<interface::ComponentInterface>()
         .map_err(|e| anyhow!("Failed to parse IDL: {}", e))
 }
@@ -0,0 +1,957 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Check a component interface for cross-language pitfalls.
+//!
+//! A component interface can be perfectly valid, and its scaffolding compile just fine, while
+//! still producing bindings that are broken or awkward to use in some target language. This
+//! module looks for the most common ways that happens:
+//!
+//!  * names that become a reserved word of a target language,
+//!  * names that become the same identifier once the bindings convert their case, such as
+//!    `minusculeMAJUSCULE` and `minuscule_majuscule`,
+//!  * types that no function or method ever uses,
+//!  * errors that no function or method ever throws, and
+//!  * objects that rust hands to foreign code somewhere consumers are unlikely to destroy
+//!    them, so that they leak in Kotlin, which has no finalizers to fall back on.
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use anyhow::{bail, Result};
+use heck::{CamelCase, MixedCase, ShoutySnakeCase, SnakeCase};
+
+use crate::bindings::TargetLanguage;
+use crate::interface::*;
+
+const ALL_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+];
+
+/// The checks that the linter performs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Lint {
+    ReservedWord,
+    CaseCollision,
+    UnusedType,
+    ErrorNeverThrown,
+    LeakedObject,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Lint::ReservedWord => "reserved-word",
+            Lint::CaseCollision => "case-collision",
+            Lint::UnusedType => "unused-type",
+            Lint::ErrorNeverThrown => "error-never-thrown",
+            Lint::LeakedObject => "leaked-object",
+        })
+    }
+}
+
+impl TryFrom<&str> for Lint {
+    type Error = anyhow::Error;
+    fn try_from(value: &str) -> Result<Self> {
+        Ok(match value {
+            "reserved-word" => Lint::ReservedWord,
+            "case-collision" => Lint::CaseCollision,
+            "unused-type" => Lint::UnusedType,
+            "error-never-thrown" => Lint::ErrorNeverThrown,
+            "leaked-object" => Lint::LeakedObject,
+            _ => bail!("Unknown lint: \"{}\"", value),
+        })
+    }
+}
+
+/// A problem found by one of the checks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    lint: Lint,
+    message: String,
+}
+
+impl Warning {
+    pub fn lint(&self) -> Lint {
+        self.lint
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning[{}]: {}", self.lint, self.message)
+    }
+}
+
+/// Check a component interface for cross-language pitfalls.
+pub fn lint(ci: &ComponentInterface) -> Vec<Warning> {
+    let mut linter = Linter::default();
+    linter.check_names(ci);
+    linter.check_usage(ci);
+    linter.check_leaks(ci);
+    linter.warnings
+}
+
+// The ways in which the bindings convert the case of names from the IDL. These mirror the
+// case-conversion filters that the templates for each language apply.
+#[derive(Copy, Clone)]
+enum Role {
+    // Classes, and the variants of errors.
+    Type,
+    Function,
+    // Arguments, fields and attributes.
+    Variable,
+    // Interface constants, and the variants of flat enums.
+    Constant,
+    // The variants of enums that have fields.
+    DataVariant,
+}
+
+fn identifier(language: TargetLanguage, role: Role, name: &str) -> String {
+    match (language, role) {
+        (_, Role::Type) => name.to_camel_case(),
+        (TargetLanguage::Python, Role::Function) | (TargetLanguage::Python, Role::Variable) => {
+            name.to_snake_case()
+        }
+        (_, Role::Function) | (_, Role::Variable) => name.to_mixed_case(),
+        (TargetLanguage::Swift, Role::Constant) | (TargetLanguage::Swift, Role::DataVariant) => {
+            name.to_mixed_case()
+        }
+        (TargetLanguage::Kotlin, Role::DataVariant) => name.to_camel_case(),
+        (_, Role::Constant) | (_, Role::DataVariant) => name.to_shouty_snake_case(),
+    }
+}
+
+fn is_reserved(language: TargetLanguage, ident: &str) -> bool {
+    let reserved: &[&str] = match language {
+        TargetLanguage::Kotlin => &[
+            "as",
+            "break",
+            "class",
+            "continue",
+            "do",
+            "else",
+            "false",
+            "for",
+            "fun",
+            "if",
+            "in",
+            "interface",
+            "is",
+            "null",
+            "object",
+            "package",
+            "return",
+            "super",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typealias",
+            "typeof",
+            "val",
+            "var",
+            "when",
+            "while",
+        ],
+        TargetLanguage::Swift => &[
+            "Any",
+            "Protocol",
+            "Self",
+            "Type",
+            "as",
+            "associatedtype",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "continue",
+            "default",
+            "defer",
+            "deinit",
+            "do",
+            "else",
+            "enum",
+            "extension",
+            "fallthrough",
+            "false",
+            "fileprivate",
+            "for",
+            "func",
+            "guard",
+            "if",
+            "import",
+            "in",
+            "init",
+            "inout",
+            "internal",
+            "is",
+            "let",
+            "nil",
+            "open",
+            "operator",
+            "private",
+            "protocol",
+            "public",
+            "repeat",
+            "rethrows",
+            "return",
+            "self",
+            "static",
+            "struct",
+            "subscript",
+            "super",
+            "switch",
+            "throw",
+            "throws",
+            "true",
+            "try",
+            "typealias",
+            "var",
+            "where",
+            "while",
+        ],
+        TargetLanguage::Python => &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+    };
+    reserved.contains(&ident)
+}
+
+// Describe the identifiers that a name becomes, e.g. "`fooBar` in kotlin, `foo_bar` in python".
+fn in_languages(idents: &[(TargetLanguage, String)]) -> String {
+    idents
+        .iter()
+        .map(|(language, ident)| format!("`{}` in {}", ident, language))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// A name from the IDL, together with how the bindings use it and a description for messages.
+struct Name<'a> {
+    role: Role,
+    name: &'a str,
+    what: String,
+}
+
+impl<'a> Name<'a> {
+    fn new(role: Role, name: &'a str, what: String) -> Self {
+        Self { role, name, what }
+    }
+}
+
+#[derive(Default)]
+struct Linter {
+    warnings: Vec<Warning>,
+}
+
+impl Linter {
+    fn warn(&mut self, lint: Lint, message: String) {
+        self.warnings.push(Warning { lint, message })
+    }
+
+    fn check_names(&mut self, ci: &ComponentInterface) {
+        let records = ci.iter_record_definitions();
+        let enums = ci.iter_enum_definitions();
+        let errors = ci.iter_error_definitions();
+        let objects = ci.iter_object_definitions();
+        let callback_interfaces = ci.iter_callback_interface_definitions();
+
+        // Every type is a class in the same module of the bindings.
+        let mut types = Vec::new();
+        for rec in &records {
+            types.push(Name::new(
+                Role::Type,
+                rec.name(),
+                format!("dictionary `{}`", rec.name()),
+            ));
+        }
+        for e in &enums {
+            types.push(Name::new(
+                Role::Type,
+                e.name(),
+                format!("enum `{}`", e.name()),
+            ));
+        }
+        for e in &errors {
+            types.push(Name::new(
+                Role::Type,
+                e.name(),
+                format!("error `{}`", e.name()),
+            ));
+        }
+        for obj in &objects {
+            types.push(Name::new(
+                Role::Type,
+                obj.name(),
+                format!("interface `{}`", obj.name()),
+            ));
+        }
+        for cbi in &callback_interfaces {
+            types.push(Name::new(
+                Role::Type,
+                cbi.name(),
+                format!("callback interface `{}`", cbi.name()),
+            ));
+        }
+        self.check_scope(types);
+
+        // Functions and the namespace's constants are both top-level members of the module.
+        let functions = ci.iter_function_definitions();
+        let constants = ci.iter_constant_definitions();
+        let mut members: Vec<_> = functions
+            .iter()
+            .map(|f| Name::new(Role::Function, f.name(), format!("function `{}`", f.name())))
+            .collect();
+        for c in &constants {
+            members.push(Name::new(
+                Role::Constant,
+                c.name(),
+                format!("constant `{}`", c.name()),
+            ));
+        }
+        self.check_scope(members);
+        for f in &functions {
+            self.check_arguments(&format!("function `{}`", f.name()), &f.arguments());
+        }
+
+        for obj in &objects {
+            let of_obj = format!("of interface `{}`", obj.name());
+            let mut members = Vec::new();
+            for cons in obj.alternate_constructors() {
+                members.push(Name::new(
+                    Role::Function,
+                    cons.name(),
+                    format!("constructor `{}` {}", cons.name(), of_obj),
+                ));
+            }
+            // Inherited methods were already checked along with the interface they come from.
+            let methods: Vec<_> = obj
+                .methods()
+                .into_iter()
+                .filter(|m| m.inherited_from().is_none())
+                .collect();
+            for meth in &methods {
+                members.push(Name::new(
+                    Role::Function,
+                    meth.name(),
+                    format!("method `{}` {}", meth.name(), of_obj),
+                ));
+            }
+            for prop in obj.properties() {
+                members.push(Name::new(
+                    Role::Variable,
+                    prop.name(),
+                    format!("attribute `{}` {}", prop.name(), of_obj),
+                ));
+            }
+            for c in obj.constants() {
+                members.push(Name::new(
+                    Role::Constant,
+                    c.name(),
+                    format!("constant `{}` {}", c.name(), of_obj),
+                ));
+            }
+            self.check_scope(members);
+            for cons in obj.constructors() {
+                self.check_arguments(
+                    &format!("constructor `{}` {}", cons.name(), of_obj),
+                    &cons.arguments(),
+                );
+            }
+            for meth in methods {
+                self.check_arguments(
+                    &format!("method `{}` {}", meth.name(), of_obj),
+                    &meth.arguments(),
+                );
+            }
+        }
+
+        for cbi in &callback_interfaces {
+            let of_cbi = format!("of callback interface `{}`", cbi.name());
+            self.check_scope(
+                cbi.methods()
+                    .iter()
+                    .map(|m| {
+                        Name::new(
+                            Role::Function,
+                            m.name(),
+                            format!("method `{}` {}", m.name(), of_cbi),
+                        )
+                    })
+                    .collect(),
+            );
+            for meth in cbi.methods() {
+                self.check_arguments(
+                    &format!("method `{}` {}", meth.name(), of_cbi),
+                    &meth.arguments(),
+                );
+            }
+        }
+
+        for rec in &records {
+            self.check_fields(&format!("dictionary `{}`", rec.name()), &rec.fields());
+        }
+        for e in &enums {
+            let role = if e.is_flat() {
+                Role::Constant
+            } else {
+                Role::DataVariant
+            };
+            self.check_variants(&format!("enum `{}`", e.name()), role, &e.variants());
+        }
+        for e in &errors {
+            self.check_variants(&format!("error `{}`", e.name()), Role::Type, &e.variants());
+        }
+    }
+
+    fn check_arguments(&mut self, of: &str, args: &[&Argument]) {
+        self.check_scope(
+            args.iter()
+                .map(|a| {
+                    Name::new(
+                        Role::Variable,
+                        a.name(),
+                        format!("argument `{}` of {}", a.name(), of),
+                    )
+                })
+                .collect(),
+        );
+    }
+
+    fn check_fields(&mut self, of: &str, fields: &[&Field]) {
+        self.check_scope(
+            fields
+                .iter()
+                .map(|f| {
+                    Name::new(
+                        Role::Variable,
+                        f.name(),
+                        format!("field `{}` of {}", f.name(), of),
+                    )
+                })
+                .collect(),
+        );
+    }
+
+    fn check_variants(&mut self, of: &str, role: Role, variants: &[&Variant]) {
+        self.check_scope(
+            variants
+                .iter()
+                .map(|v| Name::new(role, v.name(), format!("variant `{}` of {}", v.name(), of)))
+                .collect(),
+        );
+        for v in variants {
+            self.check_fields(&format!("variant `{}` of {}", v.name(), of), &v.fields());
+        }
+    }
+
+    // Check a group of names that end up side by side in the bindings, such as the arguments of
+    // a function, for ones that are reserved words or that collide with each other.
+    fn check_scope(&mut self, names: Vec<Name<'_>>) {
+        let idents = |n: &Name<'_>| -> Vec<(TargetLanguage, String)> {
+            ALL_LANGUAGES
+                .iter()
+                .map(|&language| (language, identifier(language, n.role, n.name)))
+                .collect()
+        };
+        for n in &names {
+            let reserved: Vec<_> = idents(n)
+                .into_iter()
+                .filter(|(language, ident)| is_reserved(*language, ident))
+                .collect();
+            if !reserved.is_empty() {
+                self.warn(
+                    Lint::ReservedWord,
+                    format!(
+                        "{} becomes a reserved word: {}",
+                        n.what,
+                        in_languages(&reserved)
+                    ),
+                );
+            }
+        }
+        for (i, a) in names.iter().enumerate() {
+            for b in &names[i + 1..] {
+                let collisions: Vec<_> = idents(a)
+                    .into_iter()
+                    .zip(idents(b))
+                    .filter(|(ia, ib)| ia == ib)
+                    .map(|(ia, _)| ia)
+                    .collect();
+                if !collisions.is_empty() {
+                    self.warn(
+                        Lint::CaseCollision,
+                        format!(
+                            "{} and {} become the same identifier: {}",
+                            a.what,
+                            b.what,
+                            in_languages(&collisions)
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // Foreign code can call functions, and construct any interface (or one that inherits from it,
+    // in the case of base interfaces). Other types are only usable if they're reachable from the
+    // signatures of those.
+    fn check_usage(&mut self, ci: &ComponentInterface) {
+        let objects = ci.iter_object_definitions();
+        let records = ci.iter_record_definitions();
+        let enums = ci.iter_enum_definitions();
+        let callback_interfaces = ci.iter_callback_interface_definitions();
+        let mut used = HashSet::new();
+        let mut pending = Vec::new();
+        let mut thrown = HashSet::new();
+        for f in ci.iter_function_definitions() {
+            pending.extend(f.arguments().iter().map(|a| a.type_()));
+            pending.extend(f.return_type().cloned());
+        }
+        pending.extend(
+            objects
+                .iter()
+                .map(|obj| Type::Object(obj.name().to_string())),
+        );
+        while let Some(type_) = pending.pop() {
+            if !used.insert(type_.clone()) {
+                continue;
+            }
+            match &type_ {
+                Type::Object(name) => {
+                    if let Some(obj) = objects.iter().find(|o| o.name() == name) {
+                        for cons in obj.constructors() {
+                            pending.extend(cons.arguments().iter().map(|a| a.type_()));
+                        }
+                        for meth in obj.methods() {
+                            pending.extend(meth.arguments().iter().map(|a| a.type_()));
+                            pending.extend(meth.return_type().cloned());
+                        }
+                        pending.extend(obj.properties().iter().map(|p| p.type_()));
+                        pending.extend(obj.constants().iter().map(|c| c.type_()));
+                    }
+                }
+                Type::Record(name) => {
+                    if let Some(rec) = records.iter().find(|r| r.name() == name) {
+                        pending.extend(rec.fields().iter().map(|f| f.type_()));
+                    }
+                }
+                Type::Enum(name) => {
+                    if let Some(e) = enums.iter().find(|e| e.name() == name) {
+                        for v in e.variants() {
+                            pending.extend(v.fields().iter().map(|f| f.type_()));
+                        }
+                    }
+                }
+                Type::CallbackInterface(name) => {
+                    if let Some(cbi) = callback_interfaces.iter().find(|c| c.name() == name) {
+                        for meth in cbi.methods() {
+                            pending.extend(meth.arguments().iter().map(|a| a.type_()));
+                            pending.extend(meth.return_type().cloned());
+                        }
+                    }
+                }
+                Type::Optional(t) | Type::Sequence(t) | Type::Iterator(t) | Type::Custom(_, t) => {
+                    pending.push(*t.clone())
+                }
+                Type::Map(k, v) => {
+                    pending.push(*k.clone());
+                    pending.push(*v.clone());
+                }
+                Type::Union(_, members) => pending.extend(members.iter().cloned()),
+                _ => (),
+            }
+        }
+        // Errors don't appear in signatures as types, only as what a function or method throws.
+        for f in ci.iter_function_definitions() {
+            thrown.extend(f.throws().map(ToString::to_string));
+        }
+        for obj in &objects {
+            for cons in obj.constructors() {
+                thrown.extend(cons.throws().map(ToString::to_string));
+            }
+            for meth in obj.methods() {
+                thrown.extend(meth.throws().map(ToString::to_string));
+            }
+        }
+
+        for rec in &records {
+            if !used.contains(&Type::Record(rec.name().to_string())) {
+                self.warn(
+                    Lint::UnusedType,
+                    format!("dictionary `{}` is never used", rec.name()),
+                );
+            }
+        }
+        for e in &enums {
+            if !used.contains(&Type::Enum(e.name().to_string())) {
+                self.warn(
+                    Lint::UnusedType,
+                    format!("enum `{}` is never used", e.name()),
+                );
+            }
+        }
+        for cbi in &callback_interfaces {
+            if !used.contains(&Type::CallbackInterface(cbi.name().to_string())) {
+                self.warn(
+                    Lint::UnusedType,
+                    format!("callback interface `{}` is never used", cbi.name()),
+                );
+            }
+        }
+        for e in ci.iter_error_definitions() {
+            if !thrown.contains(e.name()) {
+                self.warn(
+                    Lint::ErrorNeverThrown,
+                    format!("error `{}` is never thrown", e.name()),
+                );
+            }
+        }
+    }
+
+    // Objects are freed when the foreign object that owns them is destroyed. Kotlin consumers
+    // have to call `destroy()` themselves, which they'll do for objects that rust hands them
+    // directly but not for objects tucked away inside the other values that rust returns, since
+    // nothing tells them to. Nor will they do it for objects that rust passes to their callback
+    // interface implementations. Objects that foreign code passes to rust are already its own
+    // to destroy, so they can't leak this way.
+    fn check_leaks(&mut self, ci: &ComponentInterface) {
+        for f in ci.iter_function_definitions() {
+            self.check_returned(
+                ci,
+                &format!("function `{}`", f.name()),
+                f.return_type(),
+                f.throws(),
+            );
+        }
+        for obj in ci.iter_object_definitions() {
+            for cons in obj.constructors() {
+                self.check_returned(
+                    ci,
+                    &format!(
+                        "constructor `{}` of interface `{}`",
+                        cons.name(),
+                        obj.name()
+                    ),
+                    None,
+                    cons.throws(),
+                );
+            }
+            // Inherited methods were already checked along with the interface they come from.
+            for meth in obj.methods() {
+                if meth.inherited_from().is_none() {
+                    self.check_returned(
+                        ci,
+                        &format!("method `{}` of interface `{}`", meth.name(), obj.name()),
+                        meth.return_type(),
+                        meth.throws(),
+                    );
+                }
+            }
+            for prop in obj.properties() {
+                self.check_returned(
+                    ci,
+                    &format!("attribute `{}` of interface `{}`", prop.name(), obj.name()),
+                    Some(&prop.type_()),
+                    None,
+                );
+            }
+        }
+        for cbi in ci.iter_callback_interface_definitions() {
+            for meth in cbi.methods() {
+                for a in meth.arguments() {
+                    for name in objects_in(ci, &a.type_()) {
+                        self.warn_leak(
+                            &name,
+                            &format!(
+                                "through argument `{}` of method `{}` of callback interface `{}`",
+                                a.name(),
+                                meth.name(),
+                                cbi.name()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_returned(
+        &mut self,
+        ci: &ComponentInterface,
+        what: &str,
+        return_type: Option<&Type>,
+        throws: Option<&str>,
+    ) {
+        if let Some(type_) = return_type {
+            for name in nested_objects_in(ci, type_) {
+                self.warn_leak(&name, &format!("inside the return value of {}", what));
+            }
+        }
+        if let Some(error) = throws {
+            for name in objects_in(ci, &Type::Error(error.to_string())) {
+                self.warn_leak(
+                    &name,
+                    &format!("inside error `{}` thrown by {}", error, what),
+                );
+            }
+        }
+    }
+
+    fn warn_leak(&mut self, name: &str, place: &str) {
+        self.warn(
+            Lint::LeakedObject,
+            format!(
+                "interface `{}` reaches foreign code {}, where nothing destroys it, so it leaks in kotlin",
+                name, place
+            ),
+        );
+    }
+}
+
+// The names of the objects that a value of the given type can hold, including those in the
+// fields of the dictionaries, enums and errors that it refers to.
+fn objects_in(ci: &ComponentInterface, type_: &Type) -> Vec<String> {
+    let mut objects = Vec::new();
+    collect_objects(ci, type_, &mut HashSet::new(), &mut objects);
+    objects
+}
+
+fn collect_objects(
+    ci: &ComponentInterface,
+    type_: &Type,
+    seen: &mut HashSet<Type>,
+    objects: &mut Vec<String>,
+) {
+    if !seen.insert(type_.clone()) {
+        return;
+    }
+    let mut inner = Vec::new();
+    match type_ {
+        Type::Object(name) => objects.push(name.clone()),
+        Type::Record(name) => {
+            for rec in ci
+                .iter_record_definitions()
+                .iter()
+                .filter(|r| r.name() == name)
+            {
+                inner.extend(rec.fields().iter().map(|f| f.type_()));
+            }
+        }
+        Type::Enum(name) => {
+            for e in ci
+                .iter_enum_definitions()
+                .iter()
+                .filter(|e| e.name() == name)
+            {
+                for v in e.variants() {
+                    inner.extend(v.fields().iter().map(|f| f.type_()));
+                }
+            }
+        }
+        Type::Error(name) => {
+            for e in ci
+                .iter_error_definitions()
+                .iter()
+                .filter(|e| e.name() == name)
+            {
+                for v in e.variants() {
+                    inner.extend(v.fields().iter().map(|f| f.type_()));
+                }
+            }
+        }
+        Type::Optional(t) | Type::Sequence(t) | Type::Iterator(t) | Type::Custom(_, t) => {
+            inner.push(*t.clone())
+        }
+        Type::Map(_, v) => inner.push(*v.clone()),
+        Type::Union(_, members) => inner.extend(members.iter().cloned()),
+        _ => (),
+    }
+    for t in inner {
+        collect_objects(ci, &t, seen, objects);
+    }
+}
+
+// The names of the objects that a value of the given type holds inside some other value,
+// rather than being the value itself (or the item of an optional or an iterator).
+fn nested_objects_in(ci: &ComponentInterface, type_: &Type) -> Vec<String> {
+    match type_ {
+        Type::Object(_) => Vec::new(),
+        Type::Optional(t) | Type::Iterator(t) | Type::Custom(_, t) => nested_objects_in(ci, t),
+        _ => objects_in(ci, type_),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lint_lines(idl: &str) -> Vec<String> {
+        let ci = ComponentInterface::from_webidl(idl).unwrap();
+        lint(&ci).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        const IDL: &str = r#"
+            namespace test {
+                void fun(u32 fooBar, u32 foo_bar);
+                void consume(Holder holder);
+            };
+            dictionary Unused { i32 x; };
+            [Error]
+            enum NeverThrown { "Oops" };
+            [Threadsafe]
+            interface Holder {
+                void listen(Listener listener);
+            };
+            callback interface Listener {
+                void received(Holder holder);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(IDL).unwrap();
+        let warnings = lint(&ci);
+        let lines: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "warning[reserved-word]: function `fun` becomes a reserved word: `fun` in kotlin",
+                "warning[case-collision]: argument `fooBar` of function `fun` and argument `foo_bar` of function `fun` become the same identifier: `fooBar` in kotlin, `fooBar` in swift, `foo_bar` in python",
+                "warning[unused-type]: dictionary `Unused` is never used",
+                "warning[error-never-thrown]: error `NeverThrown` is never thrown",
+                "warning[leaked-object]: interface `Holder` reaches foreign code through argument `holder` of method `received` of callback interface `Listener`, where nothing destroys it, so it leaks in kotlin",
+            ]
+        );
+        assert_eq!(warnings[2].lint(), Lint::UnusedType);
+    }
+
+    #[test]
+    fn test_lint_variant_names() {
+        const IDL: &str = r#"
+            namespace test {
+                [Throws=LookupError]
+                void configure(Setting setting);
+            };
+            [Enum]
+            interface Setting {
+                Default();
+                Custom(u32 value);
+            };
+            [Error]
+            interface LookupError {
+                NotFound();
+                not_found(string key);
+            };
+        "#;
+        assert_eq!(
+            lint_lines(IDL),
+            vec![
+                "warning[reserved-word]: variant `Default` of enum `Setting` becomes a reserved word: `default` in swift",
+                "warning[case-collision]: variant `NotFound` of error `LookupError` and variant `not_found` of error `LookupError` become the same identifier: `NotFound` in kotlin, `NotFound` in swift, `NotFound` in python",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_namespace_constants() {
+        const IDL: &str = r#"
+            namespace test {
+                u32 max_items();
+            };
+            [Constants]
+            interface test {
+                const u32 MAX_ITEMS = 10;
+                const boolean IN = true;
+            };
+        "#;
+        assert_eq!(
+            lint_lines(IDL),
+            vec![
+                "warning[reserved-word]: constant `IN` becomes a reserved word: `in` in swift",
+                "warning[case-collision]: function `max_items` and constant `MAX_ITEMS` become the same identifier: `maxItems` in swift",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_leaks_only_objects_returned_inside_other_values() {
+        const IDL: &str = r#"
+            namespace test {
+                Crate fetch();
+                sequence<Crate> fetch_all();
+                void store(Shelf shelf);
+                Shelf? find_shelf();
+                [Throws=StoreError]
+                void check();
+            };
+            dictionary Shelf { Crate top; sequence<Crate> rest; };
+            [Error]
+            interface StoreError {
+                Broken(Crate culprit);
+            };
+            [Threadsafe]
+            interface Crate {
+                constructor();
+                Crate? next();
+                readonly attribute record<DOMString, Crate> neighbours;
+            };
+        "#;
+        assert_eq!(
+            lint_lines(IDL),
+            vec![
+                "warning[leaked-object]: interface `Crate` reaches foreign code inside the return value of function `fetch_all`, where nothing destroys it, so it leaks in kotlin",
+                "warning[leaked-object]: interface `Crate` reaches foreign code inside the return value of function `find_shelf`, where nothing destroys it, so it leaks in kotlin",
+                "warning[leaked-object]: interface `Crate` reaches foreign code inside error `StoreError` thrown by function `check`, where nothing destroys it, so it leaks in kotlin",
+                "warning[leaked-object]: interface `Crate` reaches foreign code inside the return value of attribute `neighbours` of interface `Crate`, where nothing destroys it, so it leaks in kotlin",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_clean_interface() {
+        const IDL: &str = r#"
+            namespace test {
+                [Throws=ArithmeticError]
+                u64 add(u64 left, u64 right);
+                Counter make_counter(Point start);
+            };
+            dictionary Point { i32 x; i32 y; };
+            enum Direction { "up", "down" };
+            [Error]
+            enum ArithmeticError { "IntegerOverflow" };
+            [Threadsafe]
+            interface Counter {
+                constructor(u32 start);
+                void step(Direction direction);
+                u32 value();
+            };
+        "#;
+        assert!(lint_lines(IDL).is_empty());
+    }
+
+    #[test]
+    fn test_allowed_lint_names_round_trip() {
+        for lint in &[
+            Lint::ReservedWord,
+            Lint::CaseCollision,
+            Lint::UnusedType,
+            Lint::ErrorNeverThrown,
+            Lint::LeakedObject,
+        ] {
+            assert_eq!(Lint::try_from(lint.to_string().as_str()).unwrap(), *lint);
+        }
+        assert!(Lint::try_from("everything").is_err());
+    }
+}
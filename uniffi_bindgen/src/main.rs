@@ -5,6 +5,13 @@
 use anyhow::{bail, Result};
 
 const POSSIBLE_LANGUAGES: &[&str] = &["kotlin", "python", "swift"];
+const POSSIBLE_LINTS: &[&str] = &[
+    "reserved-word",
+    "case-collision",
+    "unused-type",
+    "error-never-thrown",
+    "leaked-object",
+];
 
 fn main() -> Result<()> {
     let matches = clap::App::new("uniffi-bindgen")
@@ -30,12 +37,22 @@ fn main() -> Result<()> {
                         .takes_value(true)
                         .help("Directory in which to write generated files. Default is same folder as .idl file."),
                 )
+                .arg(
+                    clap::Arg::with_name("config")
+                        .long("--config-path")
+                        .takes_value(true)
+                        .help("Path to the bindings config file. Default is uniffi.toml in the crate root, if present."),
+                )
                 .arg(
                     clap::Arg::with_name("no_format")
                         .long("--no-format")
                         .help("Do not try to format the generated bindings"),
                 )
-                .arg(clap::Arg::with_name("idl_file").required(true)),
+                .arg(
+                    clap::Arg::with_name("idl_file")
+                        .required(true)
+                        .help("The .idl file, or the lib.rs of a crate that exports its interface, or JSON from the `dump` command"),
+                ),
         )
         .subcommand(
             clap::SubCommand::with_name("scaffolding")
@@ -60,12 +77,63 @@ fn main() -> Result<()> {
                 )
                 .arg(clap::Arg::with_name("idl_file").required(true)),
         )
+        .subcommand(
+            clap::SubCommand::with_name("dump")
+                .about("Write the parsed component interface as JSON")
+                .arg(
+                    clap::Arg::with_name("out_file")
+                        .long("--out-file")
+                        .short("-o")
+                        .takes_value(true)
+                        .help("File in which to write the JSON. Default is to write it to stdout."),
+                )
+                .arg(clap::Arg::with_name("idl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("diff")
+                .about("List the changes between two versions of a component interface, failing if any of them are breaking")
+                .arg(
+                    clap::Arg::with_name("language")
+                        .takes_value(true)
+                        .long("--language")
+                        .short("-l")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .possible_values(POSSIBLE_LANGUAGES)
+                        .help("Foreign language(s) whose consumers must not be broken. Default is all of them."),
+                )
+                .arg(clap::Arg::with_name("old_idl_file").required(true))
+                .arg(clap::Arg::with_name("new_idl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("lint")
+                .about("Check a component interface for cross-language pitfalls, failing if any are found")
+                .arg(
+                    clap::Arg::with_name("allow")
+                        .takes_value(true)
+                        .long("--allow")
+                        .short("-A")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .possible_values(POSSIBLE_LINTS)
+                        .help("Lint(s) not to check for"),
+                )
+                .arg(clap::Arg::with_name("idl_file").required(true)),
+        )
         .subcommand(
             clap::SubCommand::with_name("test")
             .about("Run test scripts against foreign language bindings")
             .arg(clap::Arg::with_name("cdylib_dir").required(true).help("Path to the directory containing the cdylib the scripts will be testing against."))
             .arg(clap::Arg::with_name("idl_file").required(true))
             .arg(clap::Arg::with_name("test_scripts").required(true).multiple(true).help("Foreign language(s) test scripts to run"))
+            .arg(
+                clap::Arg::with_name("external_idl")
+                .long("--external-idl")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("IDL file of another component that defines external types used by this one; may be repeated")
+            )
         )
         .get_matches();
     match matches.subcommand() {
@@ -73,6 +141,7 @@ fn main() -> Result<()> {
             m.value_of_os("idl_file").unwrap(),         // Required
             m.values_of("language").unwrap().collect(), // Required
             m.value_of_os("out_dir"),
+            m.value_of_os("config"),
             !m.is_present("no_format"),
         )?,
         ("scaffolding", Some(m)) => uniffi_bindgen::generate_component_scaffolding(
@@ -81,9 +150,39 @@ fn main() -> Result<()> {
             m.value_of_os("manifest"),
             !m.is_present("no_format"),
         )?,
+        ("dump", Some(m)) => uniffi_bindgen::dump_component_interface(
+            m.value_of_os("idl_file").unwrap(), // Required
+            m.value_of_os("out_file"),
+        )?,
+        ("diff", Some(m)) => {
+            let found_breaking = uniffi_bindgen::diff_component_interfaces(
+                m.value_of_os("old_idl_file").unwrap(), // Required
+                m.value_of_os("new_idl_file").unwrap(), // Required
+                m.values_of("language")
+                    .map(|v| v.collect())
+                    .unwrap_or_default(),
+            )?;
+            if found_breaking {
+                std::process::exit(1);
+            }
+        }
+        ("lint", Some(m)) => {
+            let found_problems = uniffi_bindgen::lint_component_interface(
+                m.value_of_os("idl_file").unwrap(), // Required
+                m.values_of("allow")
+                    .map(|v| v.collect())
+                    .unwrap_or_default(),
+            )?;
+            if found_problems {
+                std::process::exit(1);
+            }
+        }
         ("test", Some(m)) => uniffi_bindgen::run_tests(
-            m.value_of_os("cdylib_dir").unwrap(),           // Required
-            m.value_of_os("idl_file").unwrap(),             // Required
+            m.value_of_os("cdylib_dir").unwrap(), // Required
+            m.value_of_os("idl_file").unwrap(),   // Required
+            m.values_of_os("external_idl")
+                .map(|v| v.collect())
+                .unwrap_or_default(),
             m.values_of("test_scripts").unwrap().collect(), // Required
         )?,
         _ => bail!("No command specified; try `--help` for some help."),
@@ -36,12 +36,25 @@ mod filters {
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
             Type::String => "String".into(),
-            Type::Enum(name) | Type::Record(name) | Type::Object(name) | Type::Error(name) => {
-                name.clone()
-            }
+            Type::Bytes => "Vec<u8>".into(),
+            Type::Timestamp => "std::time::SystemTime".into(),
+            Type::Duration => "std::time::Duration".into(),
+            Type::Enum(name)
+            | Type::Record(name)
+            | Type::Error(name)
+            | Type::Union(name, _)
+            | Type::Custom(name, _)
+            | Type::External(name, _) => name.clone(),
+            Type::Object(name) => format!("std::sync::Arc<{}>", name),
+            Type::CallbackInterface(name) => format!("Box<dyn {}>", name),
             Type::Optional(t) => format!("Option<{}>", type_rs(t)?),
             Type::Sequence(t) => format!("Vec<{}>", type_rs(t)?),
-            Type::Map(t) => format!("std::collections::HashMap<String, {}>", type_rs(t)?),
+            Type::Iterator(t) => format!("uniffi::BoxedIterator<{}>", type_rs(t)?),
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                type_rs(k)?,
+                type_rs(v)?
+            ),
         })
     }
 
@@ -57,31 +70,52 @@ mod filters {
             FFIType::UInt64 => "u64".into(),
             FFIType::Float32 => "f32".into(),
             FFIType::Float64 => "f64".into(),
-            FFIType::RustCString => "*mut std::os::raw::c_char".into(),
             FFIType::RustBuffer => "uniffi::RustBuffer".into(),
-            FFIType::RustError => "uniffi::deps::ffi_support::ExternError".into(),
             FFIType::ForeignBytes => "uniffi::ForeignBytes".into(),
+            FFIType::ForeignCallback => "uniffi::ForeignCallback".into(),
+            FFIType::FutureCallback => "uniffi::FutureCallback".into(),
+        })
+    }
+
+    /// Get the Rust syntax for a constant's value.
+    pub fn literal_rs(literal: &Literal) -> Result<String, askama::Error> {
+        Ok(match literal {
+            Literal::Boolean(v) => v.to_string(),
+            Literal::Int(v) => v.to_string(),
+            Literal::UInt(v) => v.to_string(),
+            Literal::Float(v) => v.clone(),
+            _ => unreachable!("constants are only booleans, integers or floats"),
         })
     }
 
     pub fn lower_rs(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         // By explicitly naming the type here, we help the rust compiler to type-check the user-provided
         // implementations of the functions that we're wrapping (and also to type-check our generated code).
-        Ok(format!(
-            "<{} as uniffi::ViaFfi>::lower({})",
-            type_rs(type_)?,
-            nm
-        ))
+        Ok(match type_ {
+            // Bytes are a `Vec<u8>`, but aren't lowered like a `sequence<u8>`.
+            Type::Bytes => format!("uniffi::lower_bytes({})", nm),
+            // Iterators are moved into the handle map for their item type, which accepts anything
+            // that can be turned into an iterator over the right items.
+            Type::Iterator(t) => format!(
+                "UNIFFI_ITERATORS_{}.insert({})",
+                t.canonical_name().to_uppercase(),
+                nm
+            ),
+            _ => format!("<{} as uniffi::ViaFfi>::lower({})", type_rs(type_)?, nm),
+        })
     }
 
     pub fn lift_rs(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         // By explicitly naming the type here, we help the rust compiler to type-check the user-provided
         // implementations of the functions that we're wrapping (and also to type-check our generated code).
         // This will panic if the bindings provide an invalid value over the FFI.
-        Ok(format!(
-            "<{} as uniffi::ViaFfi>::try_lift({}).unwrap()",
-            type_rs(type_)?,
-            nm
-        ))
+        Ok(match type_ {
+            Type::Bytes => format!("uniffi::try_lift_bytes({}).unwrap()", nm),
+            _ => format!(
+                "<{} as uniffi::ViaFfi>::try_lift({}).unwrap()",
+                type_rs(type_)?,
+                nm
+            ),
+        })
     }
 }
@@ -0,0 +1,18 @@
+{#
+// For each interface that other interfaces inherit from, we generate a rust trait with the methods
+// that it declares, which the structs for the inheriting interfaces must implement. An interface that
+// inherits from another one, and is also inherited from itself, gets a trait with the other as a supertrait.
+#}
+pub trait {{ obj.name() }}{% match obj.parent() %}{% when Some with (parent) %}: {{ parent }}{% else %}{% endmatch %} {
+    {%- for meth in obj.methods() %}
+    {%- if meth.inherited_from().is_none() %}
+    fn {{ meth.name() }}(&{% if !obj.is_threadsafe() %}mut {% endif %}self
+        {%- for arg in meth.arguments() %}, {{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% endfor -%}
+    )
+    {%- match meth.throws() %}
+    {%- when Some with (e) %} -> Result<{% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_rs }}{% else %}(){% endmatch %}, {{ e }}>
+    {%- else %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_rs }}{% else %}{% endmatch %}
+    {%- endmatch %};
+    {%- endif %}
+    {%- endfor %}
+}
@@ -0,0 +1,95 @@
+{#
+// For each Callback Interface definition, we assume that there is a corresponding trait defined in Rust.
+// It is implemented by foreign-language code, so rather than wrapping a rust implementation we generate
+// a proxy struct that forwards each method call across the FFI and into the foreign language.
+#}
+{% let trait_name = cbi.name() -%}
+{% let trait_impl = format!("{}Proxy", trait_name) -%}
+{% let foreign_callback_internals = format!("FOREIGN_CALLBACK_{}_INTERNALS", trait_name)|upper -%}
+
+// Register a foreign callback for getting across the FFI.
+#[doc(hidden)]
+static {{ foreign_callback_internals }}: uniffi::ForeignCallbackInternals = uniffi::ForeignCallbackInternals::new();
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ cbi.ffi_init_callback().name() }}(callback: uniffi::ForeignCallback, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || {
+        {{ foreign_callback_internals }}.set_callback(callback)
+    })
+}
+
+// The trait that the foreign language code implements.
+// If the caller has already declared a trait with the same name, the rust
+// compiler will complain about the conflicting definitions.
+pub trait {{ trait_name }}: Send + std::fmt::Debug {
+    {%- for meth in cbi.methods() %}
+    fn {{ meth.name() }}(&self
+        {%- for arg in meth.arguments() %}, {{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% endfor -%}
+    ){% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_rs }}{% else %}{% endmatch %};
+    {%- endfor %}
+}
+
+// The proxy through which rust code calls the foreign language implementation.
+// It owns a handle to the foreign object, which is released when the proxy is dropped.
+#[doc(hidden)]
+#[derive(Debug)]
+struct {{ trait_impl }} {
+    handle: u64,
+}
+
+impl Drop for {{ trait_impl }} {
+    fn drop(&mut self) {
+        let ret_rbuf = {{ foreign_callback_internals }}.invoke_callback(
+            self.handle, uniffi::IDX_CALLBACK_FREE, uniffi::RustBuffer::new()
+        );
+        ret_rbuf.destroy();
+    }
+}
+
+impl {{ trait_name }} for {{ trait_impl }} {
+    {%- for meth in cbi.methods() %}
+    fn {{ meth.name() }}(&self
+        {%- for arg in meth.arguments() %}, {{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% endfor -%}
+    ){% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_rs }}{% else %}{% endmatch %} {
+        uniffi::deps::log::debug!("{{ cbi.name() }}.{{ meth.name() }}");
+        #[allow(unused_mut)]
+        let mut args_buf = Vec::new();
+        {%- for arg in meth.arguments() %}
+        uniffi::ViaFfi::write({% if !arg.by_ref() %}&{% endif %}{{ arg.name() }}, &mut args_buf);
+        {%- endfor %}
+        let args_rbuf = uniffi::RustBuffer::from_vec(args_buf);
+        let ret_rbuf = {{ foreign_callback_internals }}.invoke_callback(self.handle, {{ loop.index }}, args_rbuf);
+        {% match meth.return_type() -%}
+        {%- when Some with (return_type) -%}
+        // This will panic if the foreign language code returns an invalid value.
+        uniffi::try_lift_from_buffer::<{{ return_type|type_rs }}>(ret_rbuf).unwrap()
+        {%- else -%}
+        ret_rbuf.destroy();
+        {%- endmatch %}
+    }
+    {%- endfor %}
+}
+
+unsafe impl uniffi::ViaFfi for Box<dyn {{ trait_name }}> {
+    type FfiType = u64;
+
+    // Lowering callback interfaces is not supported, since foreign language code
+    // only ever hands them to rust, and never receives them back.
+    fn lower(self) -> Self::FfiType {
+        panic!("Lowering CallbackInterface not supported")
+    }
+
+    fn write<B: uniffi::deps::bytes::BufMut>(&self, _buf: &mut B) {
+        panic!("Writing CallbackInterface not supported")
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        Ok(Box::new({{ trait_impl }} { handle: v }))
+    }
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        uniffi::check_remaining(buf, 8)?;
+        <Self as uniffi::ViaFfi>::try_lift(buf.get_u64())
+    }
+}
@@ -0,0 +1,25 @@
+{#
+// For each custom type, we lift and lower using the `ViaFfi` implementation of the builtin type
+// that it wraps, and then use the caller's `CustomTypeWrapper` implementation to convert between
+// the builtin type and their own rust type. If the caller's type wraps a different builtin type
+// than the one declared in the IDL then the rust compiler will complain with a type error.
+#}
+unsafe impl uniffi::ViaFfi for {{ name }} {
+    type FfiType = <{{ builtin|type_rs }} as uniffi::ViaFfi>::FfiType;
+
+    fn lower(self) -> Self::FfiType {
+        <{{ builtin|type_rs }} as uniffi::ViaFfi>::lower(<{{ name }} as uniffi::CustomTypeWrapper>::unwrap(&self))
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        <{{ name }} as uniffi::CustomTypeWrapper>::wrap(<{{ builtin|type_rs }} as uniffi::ViaFfi>::try_lift(v)?)
+    }
+
+    fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
+        <{{ builtin|type_rs }} as uniffi::ViaFfi>::write(&<{{ name }} as uniffi::CustomTypeWrapper>::unwrap(self), buf);
+    }
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        <{{ name }} as uniffi::CustomTypeWrapper>::wrap(<{{ builtin|type_rs }} as uniffi::ViaFfi>::try_read(buf)?)
+    }
+}
@@ -3,42 +3,59 @@
 // rust `enum`. We provide the traits for sending it across the FFI, which will fail to
 // compile if the provided struct has a different shape to the one declared in the IDL.
 //
-// The enum will be sent over the FFI as a u32, with values assigned according to the
-// order of items *as declared in the IDL file*. This might be different to the order
-// of items as declared in the rust code, but no harm will come from it.
+// The enum will be serialized into a RustBuffer as a u32 tag, with values assigned according
+// to the order of variants *as declared in the IDL file*, followed by the fields of that
+// variant (if any). The order of variants might be different to the order as declared in
+// the rust code, but no harm will come from it.
+//
+// Variants with fields are expected to use named fields in the rust code, matching the
+// names declared in the IDL.
 #}
 unsafe impl uniffi::ViaFfi for {{ e.name() }} {
-    type FfiType = u32;
+    type FfiType = uniffi::RustBuffer;
 
     fn lower(self) -> Self::FfiType {
-        match self {
-            // If the provided enum doesn't match the options defined in the IDL then
-            // this match will fail to compile, with a type error to guide the way.
-            {%- for variant in e.variants() %}
-            {{ e.name() }}::{{ variant }} => {{ loop.index }},
-            {%- endfor %}
-        }
+        uniffi::lower_into_buffer(self)
     }
 
     fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
-        Ok(match v {
-            {%- for variant in e.variants() %}
-            {{ loop.index }} => {{ e.name() }}::{{ variant }},
-            {%- endfor %}
-            _ => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} enum value: {}", v),
-        })
+        uniffi::try_lift_from_buffer(v)
     }
 
     fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
-        buf.put_u32(match self {
+        match self {
+            // If the provided enum doesn't match the options defined in the IDL then
+            // this match will fail to compile, with a type error to guide the way.
             {%- for variant in e.variants() %}
-            {{ e.name() }}::{{ variant }} => {{ loop.index }},
+            {%- if variant.has_fields() %}
+            {{ e.name() }}::{{ variant.name() }} { {% for field in variant.fields() %}{{ field.name() }}, {% endfor %}} => {
+                buf.put_u32({{ loop.index }});
+                {%- for field in variant.fields() %}
+                uniffi::ViaFfi::write({{ field.name() }}, buf);
+                {%- endfor %}
+            },
+            {%- else %}
+            {{ e.name() }}::{{ variant.name() }} => buf.put_u32({{ loop.index }}),
+            {%- endif %}
             {%- endfor %}
-        });
+        };
     }
 
     fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
         uniffi::check_remaining(buf, 4)?;
-        <Self as uniffi::ViaFfi>::try_lift(buf.get_u32())
+        Ok(match buf.get_u32() {
+            {%- for variant in e.variants() %}
+            {%- if variant.has_fields() %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }} {
+                {%- for field in variant.fields() %}
+                {{ field.name() }}: <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::try_read(buf)?,
+                {%- endfor %}
+            },
+            {%- else %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }},
+            {%- endif %}
+            {%- endfor %}
+            v => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} enum value: {}", v),
+        })
     }
 }
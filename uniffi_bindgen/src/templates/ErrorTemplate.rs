@@ -1,19 +1,78 @@
 {#
     // For each error declared in the IDL, using the [Error] attribute, we assume the caller has provided a corresponding
     // rust Error enum with the same name. We provide the traits for sending it across the FFI, which will fail to
-    // compile if the provided enum has a different shape to the one declared in the IDL. 
-    // Here we define the neccessary converstion to allow the error to propegate through the FFI as an error.
+    // compile if the provided enum has a different shape to the one declared in the IDL.
+    //
+    // The error is serialized into a RustBuffer as a u32 tag, with values assigned according to the order
+    // of variants *as declared in the IDL file*, followed by the variant's data. For "flat" errors declared
+    // using an `enum`, that data is the error's `Display` string, since the rust enum may hold data that isn't
+    // exposed in the IDL. Otherwise, it's the values of the variant's fields.
+    //
+    // The IDL doesn't say which variants of a flat error hold data on the rust side, so we match them all
+    // with `{..}`, which clippy would flag on the ones that don't.
 #}
-impl From<{{e.name()}}> for uniffi::deps::ffi_support::ExternError {
-    fn from(err: {{e.name()}}) -> uniffi::deps::ffi_support::ExternError {
-        // Errno just differentiate between the errors.
-        // They are in-order, i.e the first variant of the enum has code 1
-        // As we add support for generic errors (e.g panics) 
-        // we might find that we need to reserve some codes.
-        match err {
-            {%- for value in e.values() %}
-            {{ e.name()}}::{{value}}{..} => uniffi::deps::ffi_support::ExternError::new_error(uniffi::deps::ffi_support::ErrorCode::new({{ loop.index }}), err.to_string()),
+unsafe impl uniffi::ViaFfi for {{ e.name() }} {
+    type FfiType = uniffi::RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        uniffi::lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        uniffi::try_lift_from_buffer(v)
+    }
+
+{% if e.is_flat() %}
+    #[allow(clippy::all)]
+{%- endif %}
+    fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
+        match self {
+            // If the provided error doesn't match the variants defined in the IDL then
+            // this match will fail to compile, with a type error to guide the way.
+            {%- for variant in e.variants() %}
+            {%- if e.is_flat() %}
+            {{ e.name() }}::{{ variant.name() }}{..} => {
+                buf.put_u32({{ loop.index }});
+                uniffi::ViaFfi::write(&self.to_string(), buf);
+            },
+            {%- else if variant.has_fields() %}
+            {{ e.name() }}::{{ variant.name() }} { {% for field in variant.fields() %}{{ field.name() }}, {% endfor %}} => {
+                buf.put_u32({{ loop.index }});
+                {%- for field in variant.fields() %}
+                uniffi::ViaFfi::write({{ field.name() }}, buf);
+                {%- endfor %}
+            },
+            {%- else %}
+            {{ e.name() }}::{{ variant.name() }} => buf.put_u32({{ loop.index }}),
+            {%- endif %}
+            {%- endfor %}
+        };
+    }
+
+    {%- if e.is_flat() %}
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(_buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        // Flat errors only carry their message across the FFI, so we can't reconstruct them.
+        uniffi::deps::anyhow::bail!("The {{ e.name() }} error cannot be lifted from the foreign language")
+    }
+    {%- else %}
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        uniffi::check_remaining(buf, 4)?;
+        Ok(match buf.get_u32() {
+            {%- for variant in e.variants() %}
+            {%- if variant.has_fields() %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }} {
+                {%- for field in variant.fields() %}
+                {{ field.name() }}: <{{ field.type_()|type_rs }} as uniffi::ViaFfi>::try_read(buf)?,
+                {%- endfor %}
+            },
+            {%- else %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }},
+            {%- endif %}
             {%- endfor %}
-        }
+            v => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} error value: {}", v),
+        })
     }
-}
\ No newline at end of file
+    {%- endif %}
+}
@@ -0,0 +1,18 @@
+{% let handle_map = format!("UNIFFI_ITERATORS_{}", item_type.canonical_name().to_uppercase()) %}
+uniffi::deps::lazy_static::lazy_static! {
+    static ref {{ handle_map }}: uniffi::IteratorHandleMap<{{ item_type|type_rs }}> = uniffi::IteratorHandleMap::new();
+}
+
+{% let ffi_next = ci.ffi_iterator_next(item_type) -%}
+#[no_mangle]
+pub extern "C" fn {{ ffi_next.name() }}(handle: u64, max_items: u32, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+    uniffi::call_with_output(call_status, || {{ handle_map }}.next_batch(handle, max_items))
+}
+
+{% let ffi_free = ci.ffi_iterator_free(item_type) -%}
+#[no_mangle]
+pub extern "C" fn {{ ffi_free.name() }}(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || {
+        let _ = {{ handle_map }}.delete_u64(handle);
+    })
+}
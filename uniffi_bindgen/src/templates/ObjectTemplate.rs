@@ -3,20 +3,50 @@
 // out references to these structs to foreign language code, and we provide a `pub extern "C"` function
 // corresponding to each method.
 //
+// `[Threadsafe]` objects go in an `ArcHandleMap` instead, which doesn't lock the object for each
+// method call. Their methods must take `&self`, and the struct must be `Send + Sync`.
+//
 // If the caller's implementation of the struct does not match with the methods or types specified
 // in the IDL, then the rust compiler will complain with a (hopefully at least somewhat helpful!)
 // error message when processing this generated code.
 {% let handle_map = format!("UNIFFI_HANDLE_MAP_{}", obj.name().to_uppercase()) %}
 uniffi::deps::lazy_static::lazy_static! {
+    {%- if obj.is_threadsafe() %}
+    static ref {{ handle_map }}: uniffi::ArcHandleMap<{{ obj.name() }}> = uniffi::ArcHandleMap::new();
+    {%- else %}
     static ref {{ handle_map }}: uniffi::deps::ffi_support::ConcurrentHandleMap<{{ obj.name() }}> = uniffi::deps::ffi_support::ConcurrentHandleMap::new();
+    {%- endif %}
 }
 
+{%- if obj.is_threadsafe() %}
+
+// `[Threadsafe]` objects can be passed over the FFI as an `Arc<{{ obj.name() }}>`, by handle into this map.
+// Rust and the foreign-language code each hold their own reference to the object.
+impl uniffi::FfiObject for {{ obj.name() }} {
+    fn handle_map() -> &'static uniffi::ArcHandleMap<Self> {
+        &{{ handle_map }}
+    }
+}
+{%- endif %}
+
     {% let ffi_free = obj.ffi_object_free() -%}
     #[no_mangle]
-    pub extern "C" fn {{ ffi_free.name() }}(handle: u64) {
-        let _ = {{ handle_map }}.delete_u64(handle);
+    pub extern "C" fn {{ ffi_free.name() }}(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+        uniffi::call_with_output(call_status, || {
+            let _ = {{ handle_map }}.delete_u64(handle);
+        })
     }
 
+{%- for c in obj.constants() %}
+// Constants are defined in the bindings straight from the IDL, so check here that the
+// Rust side agrees. A mismatched type or value will fail to compile.
+#[allow(clippy::all)]
+const _: () = {
+    const VALUE: {{ c.type_()|type_rs }} = {{ obj.name() }}::{{ c.name() }};
+    assert!(VALUE == {{ c.value()|literal_rs }}, "{{ obj.name() }}::{{ c.name() }} doesn't match the value in the IDL");
+};
+{%- endfor %}
+
 {%- for cons in obj.constructors() %}
     #[allow(clippy::all)]
     #[no_mangle]
@@ -30,14 +60,11 @@ uniffi::deps::lazy_static::lazy_static! {
 {%- endfor %}
 
 {%- for meth in obj.methods() %}
-    #[allow(clippy::all)]
-    #[no_mangle]
-    pub extern "C" fn {{ meth.ffi_func().name() }}(
-        {%- call rs::arg_list_ffi_decl(meth.ffi_func()) %}
-    ) -> {% call rs::return_type_func(meth) %} {
-        uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
-        // If the method does not have the same signature as declared in the IDL, then
-        // this attempt to call it will fail with a (somewhat) helpful compiler error.
-        {% call rs::to_rs_method_call(obj, meth) %}
-    }
+    {% call rs::method_ffi_func(obj, meth) %}
 {% endfor %}
+
+{%- for prop in obj.properties() %}
+{%- for meth in prop.accessors() %}
+    {% call rs::method_ffi_func(obj, meth) %}
+{% endfor %}
+{%- endfor %}
@@ -0,0 +1,46 @@
+{#
+// For each union type, we generate a rust enum with a variant for each of its member types.
+// Unlike other types, this one is defined by the scaffolding rather than by the caller, since
+// the IDL doesn't give it a name; the rust code that implements the component can use it
+// by the name generated from its member types.
+//
+// The tag values are assigned according to the order of members *as declared in the IDL file*.
+#}
+pub enum {{ name }} {
+    {%- for member in members %}
+    {{ member.union_member_name() }}({{ member|type_rs }}),
+    {%- endfor %}
+}
+
+unsafe impl uniffi::ViaFfi for {{ name }} {
+    type FfiType = uniffi::RustBuffer;
+
+    fn lower(self) -> Self::FfiType {
+        uniffi::lower_into_buffer(self)
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self> {
+        uniffi::try_lift_from_buffer(v)
+    }
+
+    fn write<B: uniffi::deps::bytes::BufMut>(&self, buf: &mut B) {
+        match self {
+            {%- for member in members %}
+            {{ name }}::{{ member.union_member_name() }}(v) => {
+                buf.put_u32({{ loop.index }});
+                uniffi::ViaFfi::write(v, buf);
+            },
+            {%- endfor %}
+        };
+    }
+
+    fn try_read<B: uniffi::deps::bytes::Buf>(buf: &mut B) -> uniffi::deps::anyhow::Result<Self> {
+        uniffi::check_remaining(buf, 4)?;
+        Ok(match buf.get_u32() {
+            {%- for member in members %}
+            {{ loop.index }} => {{ name }}::{{ member.union_member_name() }}(<{{ member|type_rs }} as uniffi::ViaFfi>::try_read(buf)?),
+            {%- endfor %}
+            v => uniffi::deps::anyhow::bail!("Invalid {{ name }} union tag: {}", v),
+        })
+    }
+}
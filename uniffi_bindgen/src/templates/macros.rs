@@ -6,12 +6,6 @@
 {{ func.name() }}({% call _arg_list_rs_call(func) -%})
 {%- endmacro -%}
 
-{%- macro to_rs_call_with_prefix(prefix, func) -%}
-    {{ func.name() }}(
-    {{- prefix }}{% if func.arguments().len() > 0 %}, {% call _arg_list_rs_call(func) -%}{% endif -%}
-)
-{%- endmacro -%}
-
 {%- macro _arg_list_rs_call(func) %}
     {%- for arg in func.arguments() %}
         {%- if arg.by_ref() %}&{% endif %}
@@ -20,6 +14,23 @@
     {%- endfor %}
 {%- endmacro -%}
 
+{#-
+// Methods lift their arguments before locking the object, so that invalid arguments
+// can't poison it with a panic.
+-#}
+{%- macro _lift_args_rs(func) %}
+    {%- for arg in func.arguments() %}
+    let {{ arg.name() }} = {{ arg.name()|lift_rs(arg.type_()) }};
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro to_rs_call_with_prefix(prefix, func) %}
+    {{- func.name() }}(
+    {{- prefix }}
+    {%- for arg in func.arguments() %}, {% if arg.by_ref() %}&{% endif %}{{ arg.name() }}{% endfor -%}
+)
+{%- endmacro -%}
+
 {#-
 // Arglist as used in the _UniFFILib function declations.
 // Note unfiltered name but type_ffi filters.
@@ -28,7 +39,7 @@
     {%- for arg in func.arguments() %}
         {{- arg.name() }}: {{ arg.type_()|type_ffi -}}{% if loop.last %}{% else %},{% endif %}
     {%- endfor %}
-    {% if func.arguments().len() > 0 %},{% endif %} err: &mut uniffi::deps::ffi_support::ExternError,
+    {% if func.arguments().len() > 0 %},{% endif %} call_status: &mut uniffi::RustCallStatus,
 {%- endmacro -%}
 
 {% macro return_type_func(func) %}{% match func.ffi_func().return_type() %}{% when Some with (return_type) %}{{ return_type|type_ffi }}{%- else -%}(){%- endmatch -%}{%- endmacro -%}
@@ -38,27 +49,99 @@
 {% macro to_rs_constructor_call(obj, cons) %}
 {% match cons.throws() %}
 {% when Some with (e) %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert_with_result(err, || -> Result<{{obj.name()}}, {{e}}> {
-    let _retval = {{ obj.name() }}::{% call to_rs_call(cons) %}?;
-    Ok(_retval)
+uniffi::call_with_result(call_status, || -> Result<u64, {{e}}> {
+    let _new = {{ obj.name() }}::{% call to_rs_call(cons) %}?;
+    Ok(UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert(_new).into_u64())
 })
 {% else %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert_with_output(err, || {
-    {{ obj.name() }}::{% call to_rs_call(cons) %}
+uniffi::call_with_output(call_status, || {
+    let _new = {{ obj.name() }}::{% call to_rs_call(cons) %};
+    UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.insert(_new).into_u64()
 })
 {% endmatch %}
 {% endmacro %}
 
+{%- macro method_ffi_func(obj, meth) %}
+    #[allow(clippy::all)]
+    #[no_mangle]
+    pub extern "C" fn {{ meth.ffi_func().name() }}(
+        {%- call arg_list_ffi_decl(meth.ffi_func()) %}
+    ) -> {% call return_type_func(meth) %} {
+        uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
+        // If the method does not have the same signature as declared in the IDL, then
+        // this attempt to call it will fail with a (somewhat) helpful compiler error.
+        {%- if meth.is_async() && meth.is_static() %}
+        {% call to_rs_async_call(format!("{}::{}", obj.name(), meth.name()), meth, "") %}
+        {%- else if meth.is_async() %}
+        {% call to_rs_async_call(format!("{}::{}", obj.name(), meth.name()), meth, obj.name()) %}
+        {%- else if meth.is_static() %}
+        {% call to_rs_static_method_call(obj, meth) %}
+        {%- else if obj.is_threadsafe() %}
+        {% call to_rs_threadsafe_method_call(obj, meth) %}
+        {%- else %}
+        {% call to_rs_method_call(obj, meth) %}
+        {%- endif %}
+    }
+{%- endmacro %}
+
 {% macro to_rs_method_call(obj, meth) %}
 {% match meth.throws() %}
 {% when Some with (e) %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.call_with_result_mut(err, {{ meth.first_argument().name() }}, |obj| -> Result<{% call return_type_func(meth) %}, {{e}}> {
-    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%}?;
+uniffi::call_with_result(call_status, || -> Result<{% call return_type_func(meth) %}, {{e}}> {
+    {%- call _lift_args_rs(meth) %}
+    let _retval = UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.get_mut_u64({{ meth.first_argument().name() }}, |obj| -> Result<_, uniffi::deps::ffi_support::HandleError> {
+        Ok({{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%})
+    }).expect("{{ obj.name() }} handle is invalid or has already been freed")?;
+    Ok({% call ret(meth) %})
+})
+{% else %}
+uniffi::call_with_output(call_status, || {
+    {%- call _lift_args_rs(meth) %}
+    let _retval = UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.get_mut_u64({{ meth.first_argument().name() }}, |obj| -> Result<_, uniffi::deps::ffi_support::HandleError> {
+        Ok({{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%})
+    }).expect("{{ obj.name() }} handle is invalid or has already been freed");
+    {% call ret(meth) %}
+})
+{% endmatch %}
+{% endmacro %}
+
+{#-
+// Threadsafe objects aren't locked for the call, so it only holds a reference to the object.
+-#}
+{% macro to_rs_threadsafe_method_call(obj, meth) %}
+{% match meth.throws() %}
+{% when Some with (e) %}
+uniffi::call_with_result(call_status, || -> Result<{% call return_type_func(meth) %}, {{e}}> {
+    {%- call _lift_args_rs(meth) %}
+    let obj = UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.get_u64({{ meth.first_argument().name() }})
+        .expect("{{ obj.name() }} handle is invalid or has already been freed");
+    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("&obj", meth) -%}?;
     Ok({% call ret(meth) %})
 })
 {% else %}
-UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.call_with_output_mut(err, {{ meth.first_argument().name() }}, |obj| {
-    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("obj", meth) -%};
+uniffi::call_with_output(call_status, || {
+    {%- call _lift_args_rs(meth) %}
+    let obj = UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.get_u64({{ meth.first_argument().name() }})
+        .expect("{{ obj.name() }} handle is invalid or has already been freed");
+    let _retval = {{ obj.name() }}::{%- call to_rs_call_with_prefix("&obj", meth) -%};
+    {% call ret(meth) %}
+})
+{% endmatch %}
+{% endmacro %}
+
+{#-
+// Static methods don't have an instance, so they're called like a top-level function.
+-#}
+{% macro to_rs_static_method_call(obj, meth) %}
+{% match meth.throws() %}
+{% when Some with (e) %}
+uniffi::call_with_result(call_status, || -> Result<{% call return_type_func(meth) %}, {{e}}> {
+    let _retval = {{ obj.name() }}::{% call to_rs_call(meth) %}?;
+    Ok({% call ret(meth) %})
+})
+{% else %}
+uniffi::call_with_output(call_status, || {
+    let _retval = {{ obj.name() }}::{% call to_rs_call(meth) %};
     {% call ret(meth) %}
 })
 {% endmatch %}
@@ -67,14 +150,54 @@ UNIFFI_HANDLE_MAP_{{ obj.name()|upper }}.call_with_output_mut(err, {{ meth.first
 {% macro to_rs_function_call(func) %}
 {% match func.throws() %}
 {% when Some with (e) %}
-uniffi::deps::ffi_support::call_with_result(err, || -> Result<{% call return_type_func(func) %}, {{e}}> {
+uniffi::call_with_result(call_status, || -> Result<{% call return_type_func(func) %}, {{e}}> {
     let _retval = {% call to_rs_call(func) %}?;
     Ok({% call ret(func) %})
 })
 {% else %}
-uniffi::deps::ffi_support::call_with_output(err, || {
+uniffi::call_with_output(call_status, || {
     let _retval = {% call to_rs_call(func) %};
     {% call ret(func) %}
 })
 {% endmatch %}
 {% endmacro %}
+
+{#-
+// Async functions and methods lift their arguments and start the future, then return straight away.
+// The future owns everything it uses, including a reference to the object for a method on one,
+// and sends its serialized result to the foreign-language callback once it completes.
+-#}
+{% macro to_rs_async_call(path, func, obj_name) %}
+uniffi::call_with_output(call_status, || {
+    {%- call _lift_args_rs(func) %}
+    {%- if !obj_name.is_empty() %}
+    let obj = UNIFFI_HANDLE_MAP_{{ obj_name|upper }}.get_u64(handle)
+        .expect("{{ obj_name }} handle is invalid or has already been freed");
+    {%- endif %}
+    {%- match func.throws() %}
+    {%- when Some with (e) %}
+    uniffi::run_future_with_result(uniffi_callback, uniffi_callback_data, async move {
+        let _retval = {{ path }}({% call _async_arg_list_rs(func, obj_name) %}).await?;
+        Ok::<_, {{ e }}>({% call async_ret(func) %})
+    })
+    {%- else %}
+    uniffi::run_future_with_output(uniffi_callback, uniffi_callback_data, async move {
+        let _retval = {{ path }}({% call _async_arg_list_rs(func, obj_name) %}).await;
+        {% call async_ret(func) %}
+    })
+    {%- endmatch %}
+})
+{% endmacro %}
+
+{%- macro _async_arg_list_rs(func, obj_name) %}
+    {%- if !obj_name.is_empty() %}&obj{% if func.arguments().len() > 0 %}, {% endif %}{% endif %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.by_ref() %}&{% endif %}{{ arg.name() }}
+        {%- if !loop.last %}, {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// The result of an async call is always passed back serialized into a `RustBuffer`.
+-#}
+{% macro async_ret(func) %}{% match func.return_type() %}{% when Some with (return_type) %}uniffi::lower_into_buffer::<{{ return_type|type_rs }}>(_retval){% else %}{ let () = _retval; uniffi::RustBuffer::new() }{% endmatch %}{% endmacro %}
@@ -4,8 +4,8 @@
 
 {% include "RustBuffer.rs" %}
 
-// We generate error mappings into ffi_support::ExternErrors
-// so that the errors can propagate through the FFI
+// For each error declared in the IDL, we provide the traits for serializing it into
+// a RustBuffer, so that the errors can propagate through the FFI
 {% for e in ci.iter_error_definitions() %}
 {% include "ErrorTemplate.rs" %}
 {% endfor %}
@@ -14,9 +14,9 @@
 // rust `enum`. We provide the traits for sending it across the FFI, which will fail to
 // compile if the provided struct has a different shape to the one declared in the IDL.
 //
-// The enum will be sent over the FFI as a u32, with values assigned according to the
-// order of items *as declared in the IDL file*. This might be different to the order
-// of items as declared in the rust code, but no harm will come from it.
+// The enum will be serialized over the FFI as a u32 tag followed by any fields, with tag
+// values assigned according to the order of items *as declared in the IDL file*. This might
+// be different to the order of items as declared in the rust code, but no harm will come from it.
 {% for e in ci.iter_enum_definitions() %}
 {% include "EnumTemplate.rs" %}
 {% endfor %}
@@ -29,6 +29,43 @@
 {% include "RecordTemplate.rs" %}
 {% endfor %}
 
+// For each union type used in the IDL, we generate a rust `enum` with one variant per member type,
+// named after the member types (e.g. `StringOrPoint::Point(..)` for `(string or Point)`). It's
+// serialized over the FFI as a u32 tag identifying the member type, followed by the value itself.
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Union with (name, members) %}
+{% include "UnionTemplate.rs" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+// For each custom type declared in the IDL, we assume the caller has provided a corresponding
+// rust type of the same name that implements `uniffi::CustomTypeWrapper`. We provide the traits
+// for sending it across the FFI using the representation of the builtin type that it wraps.
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Custom with (name, builtin) %}
+{% include "CustomTypeTemplate.rs" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+// For each external type declared in the IDL, we assume the caller has imported the rust type of the
+// same name from the crate of the component that defines it. That crate's scaffolding provides the
+// traits for sending it across the FFI, so all we do here is check that they're really there.
+{% for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::External with (name, namespace) %}
+const _: fn() = || {
+    fn assert_via_ffi<T: uniffi::ViaFfi>() {}
+    // If this fails to compile, import `{{ name }}` from the crate for the `{{ namespace }}` component.
+    assert_via_ffi::<{{ name }}>();
+};
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
 // For each top-level function declared in the IDL, we assume the caller has provided a corresponding
 // rust function of the same name. We provide a `pub extern "C"` wrapper that does type conversions to
 // send data across the FFI, which will fail to compile if the provided function does not match what's
@@ -37,16 +74,47 @@
 {% include "TopLevelFunctionTemplate.rs" %}
 {% endfor -%}
 
+// For each constant in the namespace's `[Constants]` interface, we assume the caller has defined a
+// `const` of the same name. The bindings define the constants straight from the IDL, so we check here
+// that the rust side agrees. A mismatched type or value will fail to compile.
+{%- for c in ci.iter_constant_definitions() %}
+#[allow(clippy::all)]
+const _: {{ c.type_()|type_rs }} = {{ c.name() }};
+#[allow(clippy::all)]
+const _: () = assert!({{ c.name() }} == {{ c.value()|literal_rs }}, "{{ c.name() }} doesn't match the value in the IDL");
+{%- endfor %}
+
 // For each Object definition, we assume the caller has provided an appropriately-shaped `struct`
 // with an `impl` for each method on the object. We create a `ConcurrentHandleMap` for safely handing
 // out references to these structs to foreign language code, and we provide a `pub extern "C"` function
 // corresponding to each method.
 //
+// Interfaces that other interfaces inherit from are a trait instead, which the structs for the
+// inheriting interfaces implement, and their methods are called through those structs' handle maps.
+//
 // If the caller's implementation of the struct does not match with the methods or types specified
 // in the IDL, then the rust compiler will complain with a (hopefully at least somewhat helpful!)
 // error message when processing this generated code.
 {% for obj in ci.iter_object_definitions() %}
+{%- if obj.is_base() %}
+{% include "BaseObjectTemplate.rs" %}
+{%- else %}
 {% include "ObjectTemplate.rs" %}
+{%- endif %}
+{% endfor %}
+
+// For each type of item that's returned from an `[Iterator]` function or method, we create a handle map
+// for the iterators that have been handed out to foreign language code, and provide the `pub extern "C"`
+// functions for taking a batch of items from one of them, and for freeing it.
+{% for item_type in ci.iter_iterator_item_types() %}
+{% include "IteratorTemplate.rs" %}
+{% endfor %}
+
+// For each Callback Interface definition, we generate a rust trait that the foreign language
+// code will implement, along with a proxy struct that forwards calls across the FFI. Rust code
+// that accepts a callback interface receives it as a `Box<dyn Trait>`.
+{% for cbi in ci.iter_callback_interface_definitions() %}
+{% include "CallbackInterfaceTemplate.rs" %}
 {% endfor %}
 
 {%- import "macros.rs" as rs -%}
@@ -0,0 +1,60 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::process::{Command, Output};
+
+fn diff(old: &str, new: &str, languages: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_uniffi-bindgen"));
+    command.current_dir(env!("CARGO_MANIFEST_DIR")).arg("diff");
+    for language in languages {
+        command.args(&["--language", language]);
+    }
+    command
+        .args(&[old, new])
+        .output()
+        .expect("failed to run uniffi-bindgen")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_unchanged_interface_succeeds() {
+    let output = diff("tests/diff/old.idl", "tests/diff/old.idl", &[]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+}
+
+// Adding a variant to an enum breaks exhaustive matches in Kotlin and Swift, but not Python.
+#[test]
+fn test_breaking_change_fails() {
+    let output = diff("tests/diff/old.idl", "tests/diff/new.idl", &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "breaking (kotlin, swift): variant `Triangle` was added to enum `Shape`\n"
+    );
+}
+
+#[test]
+fn test_only_the_given_languages_are_checked() {
+    let output = diff(
+        "tests/diff/old.idl",
+        "tests/diff/new.idl",
+        &["python", "swift"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "breaking (swift): variant `Triangle` was added to enum `Shape`\n"
+    );
+
+    let output = diff("tests/diff/old.idl", "tests/diff/new.idl", &["python"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "compatible: variant `Triangle` was added to enum `Shape`\n"
+    );
+}
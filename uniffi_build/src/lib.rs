@@ -21,6 +21,10 @@ use std::process::Command;
 /// Given an IDL file named `example.idl`, the generated scaffolding will be written
 /// into a file named `example.uniffi.rs` in the `$OUT_DIR` directory.
 ///
+/// For a component that exports its interface from its rust code rather than declaring it
+/// in an IDL file, give the path to its `lib.rs` instead. The scaffolding is then named after
+/// the component's namespace, to be included with `uniffi_macros::include_scaffolding!`.
+///
 /// If the "builtin-bindgen" feature is enabled then this will take a dependency on
 /// the `uniffi_bindgen` crate and call its methods directly, rather than using the
 /// command-line tool. This is mostly useful for developers who are working on uniffi
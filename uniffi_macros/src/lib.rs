@@ -4,14 +4,88 @@
 
 //! Macros for `uniffi`.
 //!
-//! Currently this is just for easily generating integration tests, but maybe
-//! we'll put some other code-annotation helper macros in here at some point.
+//! These are for easily generating integration tests, and for defining a component's
+//! interface with annotations on its rust code instead of in an IDL file.
 
 use quote::{format_ident, quote};
 use std::env;
 use std::path::PathBuf;
 use syn::{bracketed, punctuated::Punctuated, LitStr, Token};
 
+/// Export an item as part of the component interface, in place of declaring it in an IDL file.
+///
+/// This can go on a function, which becomes a function in the namespace, on a struct with named
+/// fields, which becomes a dictionary, on an enum, or on the `impl` block that holds the constructors
+/// and methods of an interface. Use `#[uniffi_macros::export(error)]` for an enum that's used as an
+/// error, and `#[uniffi_macros::export(threadsafe)]` for the `impl` block of a `[Threadsafe]` interface.
+///
+/// The attribute doesn't change the item. Instead, `uniffi_bindgen` reads the exported items from
+/// the crate's source when it's given the path to `lib.rs` in place of an IDL file, and this just
+/// checks that they're things it knows how to export. Since it only reads the top level of `lib.rs`,
+/// exporting an item from any other module fails to compile rather than leaving it out.
+#[proc_macro_attribute]
+pub fn export(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let flags =
+        syn::parse_macro_input!(args with Punctuated::<syn::Ident, Token![,]>::parse_terminated);
+    let parsed = item.clone();
+    let allowed_flags: &[&str] = match syn::parse_macro_input!(parsed as syn::Item) {
+        syn::Item::Fn(_) | syn::Item::Struct(_) => &[],
+        syn::Item::Enum(_) => &["error"],
+        syn::Item::Impl(_) => &["threadsafe"],
+        other => {
+            return syn::Error::new_spanned(
+                other,
+                "only functions, structs, enums and impl blocks can be exported",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    for flag in &flags {
+        if !allowed_flags.iter().any(|allowed| flag == allowed) {
+            return syn::Error::new_spanned(flag, "this item can't be exported with this flag")
+                .to_compile_error()
+                .into();
+        }
+    }
+    // A macro can't tell which module it's in, but the compiler can check once it knows.
+    // The module path of the crate root is just the crate name, with no `::` in it.
+    let item = proc_macro2::TokenStream::from(item);
+    proc_macro::TokenStream::from(quote! {
+        #item
+        const _: () = {
+            let path = module_path!().as_bytes();
+            let mut i = 0;
+            while i < path.len() {
+                if path[i] == b':' {
+                    panic!("only the items at the top level of lib.rs can be exported");
+                }
+                i += 1;
+            }
+        };
+    })
+}
+
+/// Include the scaffolding for a component whose interface is exported from its rust code.
+///
+/// The argument is the namespace of the component, which names the generated scaffolding file
+/// in `$OUT_DIR`, so `include_scaffolding!("example")` is the same as:
+///
+/// ```text
+/// include!(concat!(env!("OUT_DIR"), "/example.uniffi.rs"));
+/// ```
+#[proc_macro]
+pub fn include_scaffolding(namespace: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let namespace = syn::parse_macro_input!(namespace as LitStr);
+    let filename = format!("/{}.uniffi.rs", namespace.value());
+    proc_macro::TokenStream::from(quote! {
+        include!(concat!(env!("OUT_DIR"), #filename));
+    })
+}
+
 /// A macro to build testcases for a component's generated bindings.
 ///
 /// This macro provides some plumbing to write automated tests for the generated
@@ -22,10 +96,16 @@ use syn::{bracketed, punctuated::Punctuated, LitStr, Token};
 /// environment to let it load the component bindings, and will pass iff the script
 /// exits successfully.
 ///
-/// To use it, invoke the macro with the idl file as the first argument, then
+/// To use it, invoke the macro with the idl file (or the `lib.rs` of a component that
+/// exports its interface from rust code) as the first argument, then
 /// one or more file paths relative to the crate root directory.
 /// It will produce one `#[test]` function per file, in a manner designed to
 /// play nicely with `cargo test` and its test filtering options.
+///
+/// If the component uses external types from other components, give a list of idl
+/// files as the first argument instead, starting with the component's own idl file
+/// and followed by those of the components that it uses types from. Their bindings
+/// will be generated alongside the component's own bindings for the scripts to use.
 #[proc_macro]
 pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let paths = syn::parse_macro_input!(paths as FilePaths);
@@ -33,7 +113,7 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
     let pkg_dir = env::var("CARGO_MANIFEST_DIR")
         .expect("Missing $CARGO_MANIFEST_DIR, cannot build tests for generated bindings");
     // For each file found, generate a matching testcase.
-    let idl_file = &paths.idl_file;
+    let idl_files = &paths.idl_files;
     let test_functions = paths.test_scripts
         .iter()
         .map(|file_path| {
@@ -50,7 +130,7 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
             quote! {
                 #[test]
                 fn #test_name () -> uniffi::deps::anyhow::Result<()> {
-                    uniffi::testing::run_foreign_language_testcase(#pkg_dir, #idl_file, #test_file_path)
+                    uniffi::testing::run_foreign_language_testcase_with_idls(#pkg_dir, &[#(#idl_files),*], #test_file_path)
                 }
             }
         })
@@ -64,13 +144,25 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
 /// Newtype to simplifying parsing a list of file paths from macro input.
 #[derive(Debug)]
 struct FilePaths {
-    idl_file: String,
+    idl_files: Vec<String>,
     test_scripts: Vec<String>,
 }
 
 impl syn::parse::Parse for FilePaths {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let idl_file: LitStr = input.parse()?;
+        let idl_files = if input.peek(LitStr) {
+            vec![input.parse::<LitStr>()?.value()]
+        } else {
+            let idl_contents;
+            bracketed!(idl_contents in input);
+            Punctuated::<LitStr, Token![,]>::parse_terminated(&idl_contents)?
+                .iter()
+                .map(|s| s.value())
+                .collect()
+        };
+        if idl_files.is_empty() {
+            return Err(input.error("expected at least one idl file"));
+        }
         let _comma: Token![,] = input.parse()?;
         let array_contents;
         bracketed!(array_contents in input);
@@ -79,7 +171,7 @@ impl syn::parse::Parse for FilePaths {
             .map(|s| s.value())
             .collect();
         Ok(FilePaths {
-            idl_file: idl_file.value(),
+            idl_files,
             test_scripts,
         })
     }